@@ -0,0 +1,199 @@
+//! Labelled recipient addresses, with checks against the live chain.
+//!
+//! ```ignore
+//! let mut book = AddressBook::new();
+//! book.add("savings", "Vx0abc...");
+//! book.add_named("alice", "0x1234...", "alice.vex");
+//!
+//! for issue in book.validate(&client).await {
+//!     if !issue.is_ok() { println!("{:?}", issue); }
+//! }
+//! book.prune_invalid();
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::address_utils;
+use crate::wallet_client::WalletClient;
+
+/// One saved recipient.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressBookEntry {
+    pub label: String,
+    /// Address as entered (Vx0, Vx1, or 0x).
+    pub address: String,
+    /// `.vex` name the address was looked up from, if any.
+    pub name: Option<String>,
+}
+
+/// Result of checking one entry with [`AddressBook::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressBookIssue {
+    Ok { label: String },
+    /// The stored address doesn't parse.
+    Invalid { label: String, reason: String },
+    /// The entry's `.vex` name now resolves elsewhere (`resolved`), or not at
+    /// all (`None`).
+    NameMismatch {
+        label: String,
+        name: String,
+        stored: String,
+        resolved: Option<String>,
+    },
+}
+
+impl AddressBookIssue {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, AddressBookIssue::Ok { .. })
+    }
+}
+
+/// Ordered list of saved recipients. Serde-serializable for persistence.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AddressBook {
+    entries: Vec<AddressBookEntry>,
+}
+
+impl AddressBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an entry. The address isn't checked here; see [`validate`](Self::validate).
+    pub fn add(&mut self, label: &str, address: &str) {
+        self.entries.push(AddressBookEntry {
+            label: label.to_string(),
+            address: address.to_string(),
+            name: None,
+        });
+    }
+
+    /// Add an entry that was resolved from a `.vex` name.
+    pub fn add_named(&mut self, label: &str, address: &str, name: &str) {
+        self.entries.push(AddressBookEntry {
+            label: label.to_string(),
+            address: address.to_string(),
+            name: Some(name.to_string()),
+        });
+    }
+
+    /// Look up an entry by label.
+    pub fn get(&self, label: &str) -> Option<&AddressBookEntry> {
+        self.entries.iter().find(|e| e.label == label)
+    }
+
+    pub fn entries(&self) -> &[AddressBookEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Check every entry, in order: that its address parses and, for named
+    /// entries, that the name still resolves to the same account.
+    ///
+    /// A name whose lookup fails (including network errors) is reported as a
+    /// [`NameMismatch`](AddressBookIssue::NameMismatch) with `resolved: None`.
+    pub async fn validate(&self, client: &WalletClient) -> Vec<AddressBookIssue> {
+        let mut issues = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            let label = entry.label.clone();
+            let stored = match address_utils::parse_address(&entry.address) {
+                Ok(addr) => addr,
+                Err(e) => {
+                    issues.push(AddressBookIssue::Invalid { label, reason: e.to_string() });
+                    continue;
+                }
+            };
+            let Some(name) = &entry.name else {
+                issues.push(AddressBookIssue::Ok { label });
+                continue;
+            };
+
+            let resolved = match client.resolve_name(name).await {
+                Ok(resolved) => resolved,
+                Err(e) => {
+                    log::debug!("Could not resolve {}: {}", name, e);
+                    None
+                }
+            };
+            let matches = resolved
+                .as_deref()
+                .and_then(|r| address_utils::parse_address(r).ok())
+                .is_some_and(|r| r == stored);
+            issues.push(if matches {
+                AddressBookIssue::Ok { label }
+            } else {
+                AddressBookIssue::NameMismatch {
+                    label,
+                    name: name.clone(),
+                    stored: entry.address.clone(),
+                    resolved,
+                }
+            });
+        }
+        issues
+    }
+
+    /// Remove entries whose address doesn't parse; returns how many were removed.
+    pub fn prune_invalid(&mut self) -> usize {
+        let before = self.entries.len();
+        self.entries
+            .retain(|e| address_utils::parse_address(&e.address).is_ok());
+        before - self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::MockTransport;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_validate_and_prune() {
+        let alice = format!("0x{}", hex::encode([1u8; 32]));
+        let bob = format!("0x{}", hex::encode([2u8; 32]));
+        let bob_new = format!("0x{}", hex::encode([3u8; 32]));
+
+        let mut book = AddressBook::new();
+        book.add("savings", &format!("0x{}", hex::encode([9u8; 20])));
+        book.add("typo", "0x12345");
+        book.add_named("alice", &alice, "alice.vex");
+        book.add_named("bob", &bob, " Bob.VEX");
+        book.add("garbage", "not an address");
+
+        let mock = MockTransport::new()
+            .with_result("vex_resolveName", json!(alice))
+            .with_result("vex_resolveName", json!(bob_new));
+        let client = WalletClient::with_mock(mock.clone());
+
+        let issues = book.validate(&client).await;
+        assert_eq!(issues.len(), 5);
+        assert!(issues[0].is_ok());
+        assert!(matches!(&issues[1], AddressBookIssue::Invalid { label, .. } if label == "typo"));
+        assert!(issues[2].is_ok());
+        assert_eq!(
+            issues[3],
+            AddressBookIssue::NameMismatch {
+                label: "bob".into(),
+                name: " Bob.VEX".into(),
+                stored: bob,
+                resolved: Some(bob_new),
+            }
+        );
+        assert!(matches!(&issues[4], AddressBookIssue::Invalid { label, .. } if label == "garbage"));
+        assert_eq!(mock.call_count("vex_resolveName"), 2);
+        // Looked up the way VnsClient would
+        assert_eq!(mock.calls()[1].1, json!(["bob"]));
+
+        assert_eq!(book.prune_invalid(), 2);
+        assert_eq!(book.len(), 3);
+        assert!(book.get("typo").is_none());
+    }
+}
@@ -4,8 +4,20 @@
 //! - **Vx0** (native): Base58-encoded with SHA256 checksum, 20-byte payload in 32-byte container
 //! - **0x** (hex): 20-byte (EVM compat) or 32-byte (internal state)
 //!
+//! Native addresses for contracts and tokens use the same encoding with a
+//! **Vx1** prefix instead; see [`classify`].
+//!
 //! This module provides conversions between all formats.
+//!
+//! ## Comparing addresses and signatures
+//!
+//! `==` is fine for public, non-authenticating checks: matching a recipient,
+//! filtering history, looking up a token. Anywhere a comparison decides whether
+//! a key, signature, or account is accepted — i.e. it authenticates — use
+//! [`ct_eq`] or [`ct_eq_bytes`], which take the same time whether the inputs
+//! differ in the first byte or the last.
 
+use subtle::ConstantTimeEq;
 use thiserror::Error;
 use vexidus_types::{Address, VexidusAddress};
 
@@ -17,6 +29,22 @@ pub enum AddressError {
     HexDecode(#[from] hex::FromHexError),
     #[error("Vexidus address error: {0}")]
     Checksum(String),
+    #[error("Expected a user address, got contract address {0}")]
+    ContractAddress(String),
+    #[error("Can't tell whether {0} is a user or a contract; pass its Vx0 form")]
+    UnknownKind(String),
+}
+
+/// What an address belongs to, from [`classify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    /// An account controlled by a key (`Vx0`).
+    User,
+    /// A contract or token (`Vx1`).
+    Contract,
+    /// Hex that could be either: accounts and Vx1 contracts share the
+    /// zero-padded 20-byte layout.
+    Unknown,
 }
 
 /// Derive a Vx0 address from an Ed25519 public key.
@@ -41,6 +69,54 @@ pub fn vx0_to_bytes(vx0: &str) -> Result<[u8; 32], AddressError> {
     Ok(addr)
 }
 
+/// Encode a 32-byte internal address as a Vx1 string; the Vx1 counterpart of
+/// [`bytes_to_vx0`], with the same 20-byte-payload caveat.
+pub fn bytes_to_vx1(addr: &[u8; 32]) -> String {
+    vx1_from_payload(&addr[12..])
+}
+
+/// Vx0 and Vx1 share the base58 payload-and-checksum encoding; only the
+/// prefix differs.
+fn vx1_from_payload(payload: &[u8]) -> String {
+    let vx0 = VexidusAddress::from_payload(payload).to_string();
+    format!("Vx1{}", &vx0[3..])
+}
+
+/// What kind of address `addr` claims to be.
+///
+/// `Vx0` is a user and `Vx1` a contract; both must carry a valid checksum.
+/// The prefix is the only thing that tells them apart — both encode the same
+/// 20-byte payload — so this reports what the string says, not what the chain
+/// holds at that address. Hex doesn't record the kind: a 32-byte address
+/// using all 32 bytes can only be a contract or token mint, but 20-byte and
+/// zero-padded 32-byte hex is [`AddressKind::Unknown`].
+pub fn classify(addr: &str) -> Result<AddressKind, AddressError> {
+    if addr.starts_with("Vx0") || addr.starts_with("Vx1") {
+        vx0_to_bytes(addr)?;
+        return Ok(if addr.starts_with("Vx1") { AddressKind::Contract } else { AddressKind::User });
+    }
+    let bytes = parse_address(addr)?;
+    if bytes.0[..12].iter().all(|b| *b == 0) {
+        Ok(AddressKind::Unknown)
+    } else {
+        Ok(AddressKind::Contract)
+    }
+}
+
+/// [`parse_address`] for places that need an account, such as a sender or a
+/// staker: accepts only `Vx0` input, rejecting contracts and hex whose kind
+/// [`classify`] can't tell.
+///
+/// A guard against pasting the wrong kind of address, not an access check —
+/// it trusts the prefix, and anyone can write a contract's payload as `Vx0`.
+pub fn require_user_address(addr: &str) -> Result<Address, AddressError> {
+    match classify(addr)? {
+        AddressKind::User => parse_address(addr),
+        AddressKind::Contract => Err(AddressError::ContractAddress(addr.to_string())),
+        AddressKind::Unknown => Err(AddressError::UnknownKind(addr.to_string())),
+    }
+}
+
 /// Convert a Vx0 address to 0x hex string (full 32-byte representation).
 ///
 /// ```ignore
@@ -59,6 +135,55 @@ pub fn vx0_to_evm(vx0: &str) -> Result<String, AddressError> {
     Ok(format!("0x{}", hex::encode(&bytes[12..])))
 }
 
+/// Encode a 32-byte internal address as a Vx0 string.
+///
+/// Only the 20-byte payload (last 20 bytes) is encoded; the first 12 bytes are
+/// ignored, so this is only meaningful for account addresses, which are
+/// zero-padded. Use [`hex_to_vx0`] to reject non-account addresses instead.
+pub fn bytes_to_vx0(addr: &[u8; 32]) -> String {
+    VexidusAddress::from_payload(&addr[12..]).to_string()
+}
+
+/// Convert a 0x hex address (20-byte EVM or 32-byte internal) to Vx0.
+///
+/// A 20-byte address is the payload itself. A 32-byte address must be
+/// zero-padded in its first 12 bytes — anything else (e.g. a token mint) has no
+/// Vx0 form and is rejected rather than truncated.
+pub fn hex_to_vx0(hex_addr: &str) -> Result<String, AddressError> {
+    let stripped = hex_addr
+        .strip_prefix("0x")
+        .or_else(|| hex_addr.strip_prefix("0X"))
+        .unwrap_or(hex_addr);
+    let bytes = hex::decode(stripped)?;
+    match bytes.len() {
+        20 => Ok(VexidusAddress::from_payload(&bytes).to_string()),
+        32 if bytes[..12].iter().all(|b| *b == 0) => {
+            Ok(VexidusAddress::from_payload(&bytes[12..]).to_string())
+        }
+        32 => Err(AddressError::InvalidFormat(format!(
+            "32-byte address {} has non-zero leading bytes; only zero-padded account addresses have a Vx0 form",
+            hex_addr
+        ))),
+        n => Err(AddressError::InvalidFormat(format!(
+            "Expected 20 (EVM) or 32 (internal) bytes, got {}",
+            n
+        ))),
+    }
+}
+
+/// Constant-time address equality, for authentication checks.
+pub fn ct_eq(a: &Address, b: &Address) -> bool {
+    a.0.ct_eq(&b.0).into()
+}
+
+/// Constant-time equality for signatures, public keys, and other byte strings.
+///
+/// Slices of different lengths compare unequal; only the length itself is not
+/// hidden.
+pub fn ct_eq_bytes(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
 /// Validate a Vx0/Vx1 address string (prefix + base58 + checksum).
 pub fn is_valid_vx0(addr: &str) -> bool {
     if !VexidusAddress::is_valid(addr) {
@@ -109,6 +234,46 @@ pub fn parse_address(input: &str) -> Result<Address, AddressError> {
     }
 }
 
+/// Parse each input with [`parse_address`], keeping one result per input in
+/// order — useful for reporting every bad entry at once.
+pub fn parse_addresses(inputs: &[&str]) -> Vec<Result<Address, AddressError>> {
+    inputs.iter().map(|input| parse_address(input)).collect()
+}
+
+/// Parse every input, failing fast with the index of the first bad entry.
+pub fn parse_addresses_strict(inputs: &[&str]) -> Result<Vec<Address>, (usize, AddressError)> {
+    inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| parse_address(input).map_err(|e| (i, e)))
+        .collect()
+}
+
+/// Deterministic mint address for a bridged (or chain-native) token.
+///
+/// The preimage is the UTF-8 string `"{chain}_{contract}"`, hashed with
+/// Blake3; the 32-byte digest is the address. For example
+/// `bridged_mint("ethereum", "USDC")` is `blake3("ethereum_USDC")` =
+/// `0x579b84767a8dfd4aabe7b1e25f1294a1ae08bd255a85115e2a9d3d2f7a3e9c72`. These are
+/// placeholder mints until the bridge assigns real ones, so tools that derive
+/// them independently must use exactly this preimage.
+pub fn bridged_mint(chain: &str, contract: &str) -> Address {
+    let preimage = format!("{}_{}", chain, contract);
+    Address(*blake3::hash(preimage.as_bytes()).as_bytes())
+}
+
+/// Mint address for a symbol in the default
+/// [`TokenRegistry`](crate::intent_parser::TokenRegistry), via [`bridged_mint`].
+///
+/// Case-insensitive. Returns `None` for unknown symbols and for VXS, which is
+/// native (`Address::ZERO`) rather than minted.
+pub fn bridged_mint_from_symbol(symbol: &str) -> Option<Address> {
+    crate::intent_parser::BUILTIN_TOKENS
+        .iter()
+        .find(|(known, ..)| known.eq_ignore_ascii_case(symbol))
+        .map(|(_, chain, contract, _)| bridged_mint(chain, contract))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,6 +324,84 @@ mod tests {
         assert_eq!(addr.0, [7u8; 32]);
     }
 
+    #[test]
+    fn test_vx0_reverse_roundtrip() {
+        let vx0 = vx0_from_pubkey(&[42u8; 32]);
+        let bytes = vx0_to_bytes(&vx0).unwrap();
+        assert_eq!(bytes_to_vx0(&bytes), vx0);
+
+        assert_eq!(hex_to_vx0(&vx0_to_hex(&vx0).unwrap()).unwrap(), vx0);
+        assert_eq!(hex_to_vx0(&vx0_to_evm(&vx0).unwrap()).unwrap(), vx0);
+
+        // Token mints fill all 32 bytes and have no Vx0 form
+        assert!(hex_to_vx0(&format!("0x{}", hex::encode([7u8; 32]))).is_err());
+        assert!(hex_to_vx0("0x1234").is_err());
+    }
+
+    #[test]
+    fn test_vx1_and_classify() {
+        let user = vx0_from_pubkey(&[42u8; 32]);
+        let contract = bytes_to_vx1(&vx0_to_bytes(&user).unwrap());
+        assert!(contract.starts_with("Vx1"));
+        assert!(is_valid_vx0(&contract));
+        assert_eq!(&contract[3..], &user[3..]);
+
+        // Both prefixes round-trip through bytes
+        assert_eq!(bytes_to_vx0(&vx0_to_bytes(&user).unwrap()), user);
+        assert_eq!(bytes_to_vx1(&vx0_to_bytes(&contract).unwrap()), contract);
+        assert_eq!(parse_address(&contract).unwrap(), parse_address(&user).unwrap());
+
+        assert_eq!(classify(&user).unwrap(), AddressKind::User);
+        assert_eq!(classify(&contract).unwrap(), AddressKind::Contract);
+        // Hex of either one is ambiguous
+        assert_eq!(classify(&vx0_to_evm(&user).unwrap()).unwrap(), AddressKind::Unknown);
+        assert_eq!(classify(&vx0_to_hex(&contract).unwrap()).unwrap(), AddressKind::Unknown);
+        let mint = format!("0x{}", hex::encode(bridged_mint("ethereum", "USDC").0));
+        assert_eq!(classify(&mint).unwrap(), AddressKind::Contract);
+        let mut corrupt = contract.clone();
+        corrupt.pop();
+        assert!(classify(&corrupt).is_err());
+
+        assert_eq!(require_user_address(&user).unwrap(), parse_address(&user).unwrap());
+        assert!(matches!(require_user_address(&contract), Err(AddressError::ContractAddress(a)) if a == contract));
+        assert!(matches!(require_user_address(&mint), Err(AddressError::ContractAddress(_))));
+        // The contract's hex form can't slip through as a user
+        let contract_hex = vx0_to_hex(&contract).unwrap();
+        assert!(matches!(require_user_address(&contract_hex), Err(AddressError::UnknownKind(_))));
+    }
+
+    #[test]
+    fn test_parse_addresses_batch() {
+        let a = format!("0x{}", hex::encode([1u8; 32]));
+        let b = format!("0x{}", hex::encode([2u8; 20]));
+        let inputs = [a.as_str(), "not-an-address", b.as_str(), "0x1234"];
+
+        let results = parse_addresses(&inputs);
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap().0, [1u8; 32]);
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_ref().unwrap().0[12..], [2u8; 20]);
+        assert!(results[3].is_err());
+
+        let (index, _) = parse_addresses_strict(&inputs).unwrap_err();
+        assert_eq!(index, 1);
+        let ok = parse_addresses_strict(&[b.as_str(), a.as_str()]).unwrap();
+        assert_eq!(ok[1].0, [1u8; 32]);
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        let a = Address([1u8; 32]);
+        let mut b = a;
+        assert!(ct_eq(&a, &b));
+        b.0[31] = 2;
+        assert!(!ct_eq(&a, &b));
+
+        assert!(ct_eq_bytes(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!ct_eq_bytes(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!ct_eq_bytes(&[1, 2, 3], &[1, 2]));
+    }
+
     #[test]
     fn test_validation() {
         let pubkey = [99u8; 32];
@@ -179,4 +422,16 @@ mod tests {
         assert!(parse_address("0x1234").is_err()); // Wrong length
         assert!(parse_address("Vx0INVALID").is_err()); // Bad checksum
     }
+
+    #[test]
+    fn test_bridged_mint_stable() {
+        let usdc = bridged_mint("ethereum", "USDC");
+        assert_eq!(
+            hex::encode(usdc.0),
+            "579b84767a8dfd4aabe7b1e25f1294a1ae08bd255a85115e2a9d3d2f7a3e9c72"
+        );
+        assert_eq!(bridged_mint_from_symbol("usdc"), Some(usdc));
+        assert_eq!(bridged_mint_from_symbol("WETH"), Some(bridged_mint("ethereum", "ETH")));
+        assert_eq!(bridged_mint_from_symbol("VXS"), None);
+    }
 }
@@ -0,0 +1,145 @@
+//! Merkle trees over airdrop allocations.
+//!
+//! A distributor publishes the [`root`](MerkleTree::root) of a tree built from
+//! every eligible `(address, amount)` pair; each claimant then presents a
+//! [`MerkleProof`] that their pair is one of the leaves.
+//!
+//! ```ignore
+//! let tree = build_merkle_tree(&[(alice, 500), (bob, 250)]);
+//! let proof = tree.proof(&alice).unwrap();
+//! assert!(verify_proof(&tree.root(), &alice, 500, &proof));
+//! ```
+//!
+//! Hashing is Blake3 with domain-separated leaves and nodes:
+//! `leaf = blake3(0x00 || address || amount (u128 LE))` and
+//! `node = blake3(0x01 || min(a, b) || max(a, b))`. Sorting each pair means a
+//! proof is just the sibling hashes, with no left/right flags. A level with an
+//! odd node out promotes it unchanged.
+//!
+//! There is no claim operation in `vexidus_types::Operation` yet, so claims
+//! can't be emitted from [`BundleBuilder`](crate::bundle::BundleBuilder); the
+//! proof has to be handed to whatever contract or endpoint pays out the drop.
+
+use serde::{Deserialize, Serialize};
+use vexidus_types::{Address, Hash};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Sibling hashes from a leaf up to the root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub siblings: Vec<Hash>,
+}
+
+/// A built airdrop tree. Keeps every level so proofs don't need rehashing.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    entries: Vec<(Address, u128)>,
+    /// `levels[0]` is the leaves, the last level is the root alone.
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// The root to publish. All zeroes for an empty tree.
+    pub fn root(&self) -> Hash {
+        Hash(self.levels.last().and_then(|l| l.first()).copied().unwrap_or([0u8; 32]))
+    }
+
+    /// Proof for `address`'s allocation, or `None` if it isn't in the tree.
+    ///
+    /// If an address appears more than once, the first entry is proven.
+    pub fn proof(&self, address: &Address) -> Option<MerkleProof> {
+        let mut index = self.entries.iter().position(|(a, _)| a == address)?;
+        let mut siblings = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                siblings.push(Hash(*sibling));
+            }
+            index /= 2;
+        }
+        Some(MerkleProof { siblings })
+    }
+
+    /// Allocations the tree was built from, in leaf order.
+    pub fn entries(&self) -> &[(Address, u128)] {
+        &self.entries
+    }
+}
+
+/// Build a tree from `(address, amount)` allocations, in the order given.
+pub fn build_merkle_tree(entries: &[(Address, u128)]) -> MerkleTree {
+    let leaves: Vec<[u8; 32]> = entries.iter().map(|(a, amt)| leaf_hash(a, *amt)).collect();
+    let mut levels = vec![leaves];
+    while levels.last().is_some_and(|l| l.len() > 1) {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => node_hash(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    MerkleTree { entries: entries.to_vec(), levels }
+}
+
+/// Check that `(address, amount)` is a leaf of the tree with `root`.
+pub fn verify_proof(root: &Hash, address: &Address, amount: u128, proof: &MerkleProof) -> bool {
+    let computed = proof
+        .siblings
+        .iter()
+        .fold(leaf_hash(address, amount), |acc, sibling| node_hash(&acc, &sibling.0));
+    computed == root.0
+}
+
+fn leaf_hash(address: &Address, amount: u128) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(&address.0);
+    hasher.update(&amount.to_le_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(lo);
+    hasher.update(hi);
+    *hasher.finalize().as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proof_roundtrip_and_tamper() {
+        let entries: Vec<(Address, u128)> =
+            (1..=5u8).map(|i| (Address([i; 32]), i as u128 * 100)).collect();
+        let tree = build_merkle_tree(&entries);
+        let root = tree.root();
+
+        for (address, amount) in &entries {
+            let proof = tree.proof(address).unwrap();
+            assert!(verify_proof(&root, address, *amount, &proof));
+        }
+
+        let (carol, _) = entries[2];
+        let proof = tree.proof(&carol).unwrap();
+        assert!(!verify_proof(&root, &carol, 301, &proof));
+        assert!(!verify_proof(&root, &entries[3].0, 300, &proof));
+        assert!(tree.proof(&Address([9u8; 32])).is_none());
+
+        // A single-entry tree's root is its leaf, with an empty proof
+        let single = build_merkle_tree(&entries[..1]);
+        let proof = single.proof(&entries[0].0).unwrap();
+        assert!(proof.siblings.is_empty());
+        assert!(verify_proof(&single.root(), &entries[0].0, 100, &proof));
+        assert_eq!(build_merkle_tree(&[]).root(), Hash([0u8; 32]));
+    }
+}
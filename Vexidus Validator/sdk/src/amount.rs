@@ -0,0 +1,104 @@
+//! Display formatting and checked arithmetic for [`Amount`].
+//!
+//! `Amount` is defined in `vexidus-types`, so the helpers come as the
+//! [`AmountExt`] extension trait; bring it into scope to use them:
+//!
+//! ```ignore
+//! use vexidus_sdk::AmountExt;
+//! use vexidus_types::Amount;
+//!
+//! let amount = Amount::from_human("1.5", 9)?; // Amount(1_500_000_000)
+//! assert_eq!(amount.to_human(9), "1.5");
+//! let min_out = amount.checked_mul_bps(9_950); // 0.5% slippage
+//! ```
+
+use anyhow::Result;
+use vexidus_types::Amount;
+
+use crate::intent_parser::{from_raw_amount, to_raw_amount};
+
+/// Basis points in one whole (100%).
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Human-readable conversion and overflow-checked math on [`Amount`].
+pub trait AmountExt: Sized {
+    /// Parse a human-readable amount of a token with `decimals` places:
+    /// `from_human("1.5", 9)` is `Amount(1_500_000_000)`. Exact; see
+    /// [`to_raw_amount`] for the accepted forms.
+    fn from_human(s: &str, decimals: u8) -> Result<Self>;
+
+    /// Format for display without trailing zeros: `Amount(1_000_000_000)` with
+    /// 9 decimals is `"1"`. See [`from_raw_amount`].
+    fn to_human(&self, decimals: u8) -> String;
+
+    /// `self + other`, or `None` on overflow.
+    fn checked_add(&self, other: Self) -> Option<Self>;
+
+    /// `self - other`, or `None` if `other` is larger.
+    fn checked_sub(&self, other: Self) -> Option<Self>;
+
+    /// `self * bps / 10_000`, rounded down, or `None` if the result overflows
+    /// (only possible for `bps` above 10 000). Never overflows on the
+    /// intermediate product.
+    fn checked_mul_bps(&self, bps: u16) -> Option<Self>;
+}
+
+impl AmountExt for Amount {
+    fn from_human(s: &str, decimals: u8) -> Result<Self> {
+        Ok(Amount(to_raw_amount(s, decimals)?))
+    }
+
+    fn to_human(&self, decimals: u8) -> String {
+        from_raw_amount(self.0, decimals)
+    }
+
+    fn checked_add(&self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Amount)
+    }
+
+    fn checked_sub(&self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Amount)
+    }
+
+    fn checked_mul_bps(&self, bps: u16) -> Option<Self> {
+        let bps = bps as u128;
+        // Split so neither product can overflow unless the result does
+        let (whole, rest) = (self.0 / BPS_DENOMINATOR, self.0 % BPS_DENOMINATOR);
+        whole
+            .checked_mul(bps)?
+            .checked_add(rest * bps / BPS_DENOMINATOR)
+            .map(Amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_human_round_trip() {
+        assert_eq!(Amount(1_000_000_000).to_human(9), "1");
+        assert_eq!(Amount(1_500_000).to_human(6), "1.5");
+        assert_eq!(Amount::from_human("1", 9).unwrap(), Amount(1_000_000_000));
+        assert_eq!(Amount::from_human("0.000000001", 9).unwrap(), Amount(1));
+        assert!(Amount::from_human("0.0000000001", 9).is_err());
+        assert!(Amount::from_human("abc", 9).is_err());
+    }
+
+    #[test]
+    fn test_checked_math_boundaries() {
+        let max = Amount(u128::MAX);
+        assert_eq!(max.checked_add(Amount(0)), Some(max));
+        assert_eq!(max.checked_add(Amount(1)), None);
+        assert_eq!(Amount(1).checked_sub(Amount(1)), Some(Amount(0)));
+        assert_eq!(Amount(0).checked_sub(Amount(1)), None);
+
+        // u128::MAX * 9_950 overflows, but the split product doesn't
+        let expected = u128::MAX / 10_000 * 9_950 + (u128::MAX % 10_000) * 9_950 / 10_000;
+        assert_eq!(max.checked_mul_bps(9_950), Some(Amount(expected)));
+        assert_eq!(max.checked_mul_bps(10_000), Some(max));
+        assert_eq!(max.checked_mul_bps(10_001), None);
+        assert_eq!(Amount(1_999).checked_mul_bps(5_000), Some(Amount(999)));
+        assert_eq!(Amount(1_000).checked_mul_bps(0), Some(Amount(0)));
+    }
+}
@@ -0,0 +1,174 @@
+//! Blocking wrappers around the async clients (`blocking` feature).
+//!
+//! For scripts and CLI tools that don't run their own Tokio runtime. Each
+//! client owns a private single-threaded runtime and blocks on the async
+//! client's future:
+//!
+//! ```ignore
+//! let client = BlockingWalletClient::new("http://localhost:9933");
+//! let balance = client.get_balance("Vx0abc...", "VXS")?;
+//! ```
+//!
+//! Don't create, call, or drop these clients inside an async context (a
+//! `#[tokio::main]` function or a spawned task): Tokio panics when a runtime
+//! is blocked on or shut down from within another. Use [`WalletClient`] and
+//! [`DexClient`] there instead.
+
+use anyhow::Result;
+use serde_json::Value;
+use std::future::Future;
+use tokio::runtime::Runtime;
+
+use crate::dex::{DexClient, PoolInfo, SwapQuote};
+use crate::transport::MockTransport;
+use crate::wallet::WalletKeypair;
+use crate::wallet_client::{WalletClient, WalletClientError};
+
+fn runtime() -> Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to start the blocking client's Tokio runtime")
+}
+
+/// Blocking [`WalletClient`].
+pub struct BlockingWalletClient {
+    inner: WalletClient,
+    runtime: Runtime,
+}
+
+impl BlockingWalletClient {
+    /// Create a client pointing at a Vexidus node RPC endpoint.
+    pub fn new(rpc_url: &str) -> Self {
+        Self::from_async(WalletClient::new(rpc_url))
+    }
+
+    /// Create a client backed by canned responses (see [`MockTransport`]).
+    pub fn with_mock(mock: MockTransport) -> Self {
+        Self::from_async(WalletClient::with_mock(mock))
+    }
+
+    /// Wrap an already-configured async client.
+    pub fn from_async(inner: WalletClient) -> Self {
+        Self { inner, runtime: runtime() }
+    }
+
+    /// The wrapped async client.
+    pub fn inner(&self) -> &WalletClient {
+        &self.inner
+    }
+
+    /// Run any async [`WalletClient`] call to completion.
+    pub fn block_on<'a, F: Future + 'a>(&'a self, call: impl FnOnce(&'a WalletClient) -> F) -> F::Output {
+        self.runtime.block_on(call(&self.inner))
+    }
+
+    /// See [`WalletClient::get_balance`].
+    pub fn get_balance(&self, address: &str, token: &str) -> Result<String, WalletClientError> {
+        self.runtime.block_on(self.inner.get_balance(address, token))
+    }
+
+    /// See [`WalletClient::get_nonce`].
+    pub fn get_nonce(&self, address: &str) -> Result<u64, WalletClientError> {
+        self.runtime.block_on(self.inner.get_nonce(address))
+    }
+
+    /// See [`WalletClient::transfer`].
+    pub fn transfer(
+        &self,
+        wallet: &WalletKeypair,
+        to: &str,
+        token: &str,
+        amount: u128,
+    ) -> Result<String, WalletClientError> {
+        self.runtime.block_on(self.inner.transfer(wallet, to, token, amount))
+    }
+}
+
+/// Blocking [`DexClient`].
+pub struct BlockingDexClient {
+    inner: DexClient,
+    runtime: Runtime,
+}
+
+impl BlockingDexClient {
+    /// Create a DEX client.
+    pub fn new(rpc_url: &str) -> Self {
+        Self::from_async(DexClient::new(rpc_url))
+    }
+
+    /// Create a client backed by canned responses (see [`MockTransport`]).
+    pub fn with_mock(mock: MockTransport) -> Self {
+        Self::from_async(DexClient::with_mock(mock))
+    }
+
+    /// Wrap an already-configured async client.
+    pub fn from_async(inner: DexClient) -> Self {
+        Self { inner, runtime: runtime() }
+    }
+
+    /// The wrapped async client.
+    pub fn inner(&self) -> &DexClient {
+        &self.inner
+    }
+
+    /// Run any async [`DexClient`] call to completion.
+    pub fn block_on<'a, F: Future + 'a>(&'a self, call: impl FnOnce(&'a DexClient) -> F) -> F::Output {
+        self.runtime.block_on(call(&self.inner))
+    }
+
+    /// See [`DexClient::get_pool`].
+    pub fn get_pool(&self, token_a: &str, token_b: &str) -> Result<PoolInfo> {
+        self.runtime.block_on(self.inner.get_pool(token_a, token_b))
+    }
+
+    /// See [`DexClient::quote_swap`].
+    pub fn quote_swap(&self, from_token: &str, to_token: &str, amount_in: &str) -> Result<SwapQuote> {
+        self.runtime.block_on(self.inner.quote_swap(from_token, to_token, amount_in))
+    }
+
+    /// See [`DexClient::swap`].
+    pub fn swap(
+        &self,
+        wallet: &WalletKeypair,
+        from_token: &str,
+        to_token: &str,
+        amount_in: u128,
+        slippage_bps: u16,
+    ) -> Result<Value> {
+        self.runtime
+            .block_on(self.inner.swap(wallet, from_token, to_token, amount_in, slippage_bps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_blocking_clients() {
+        let client = BlockingWalletClient::new("http://localhost:9933");
+        assert_eq!(client.inner().rpc_url(), "http://localhost:9933");
+
+        let mock = MockTransport::new()
+            .with_result("vex_getBalance", json!("1500"))
+            .with_result("eth_getTransactionCount", json!("0x9"));
+        let client = BlockingWalletClient::with_mock(mock.clone());
+        assert_eq!(client.get_balance("0xaa", "VXS").unwrap(), "1500");
+        assert_eq!(client.block_on(|c| c.get_nonce("0xaa")).unwrap(), 9);
+        assert_eq!(mock.call_count("vex_getBalance"), 1);
+
+        let mock = MockTransport::new().with_result(
+            "vex_quoteSwap",
+            json!({
+                "amount_out": "1980", "price_impact_percent": "0.10", "fee": "3",
+                "pool_address": "0xpool", "reserve_in": "1000000", "reserve_out": "2000000"
+            }),
+        );
+        let dex = BlockingDexClient::with_mock(mock);
+        let quote = dex.quote_swap("VXS", "USDC", "1000").unwrap();
+        assert_eq!(quote.amount_out, "1980");
+        assert_eq!(quote.amount_in, "1000");
+    }
+}
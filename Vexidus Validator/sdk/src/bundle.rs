@@ -10,14 +10,69 @@
 //!     .nonce(3)
 //!     .max_gas(100_000)
 //!     .valid_for(3600)
-//!     .sign(&wallet);
+//!     .sign(&wallet)?;
 //! ```
-
+//!
+//! ## Execution semantics
+//!
+//! What happens to the rest of a bundle when one operation fails is up to the
+//! node; the SDK can't choose. [`TransactionBundle`] (defined in
+//! `vexidus-types`) has no field for an execution mode, and its signed hash
+//! covers only the fields it defines, so an SDK-side flag would be neither
+//! signed nor seen by the node. To be sure operations succeed or fail
+//! independently, submit them as separate bundles;
+//! [`WalletClient::simulate_bundle`](crate::WalletClient::simulate_bundle)
+//! reports per-operation outcomes before you do.
+//!
+//! ## Gas limits
+//!
+//! The node enforces only the bundle-wide `max_gas`. Per-operation limits
+//! ([`BundleBuilder::op_gas_limit`]) are a budgeting aid: they set the default
+//! `max_gas` to their sum, but the bundle has no field to carry them, so one
+//! operation can still use gas budgeted for another.
+//!
+//! ## Offline signing
+//!
+//! For air-gapped signing, [`BundleBuilder::build_unsigned_encoded`] turns a
+//! bundle into text for the offline machine, which signs it with
+//! [`WalletKeypair::sign_encoded_bundle`] and hands back only the signature;
+//! the online side attaches it with [`attach_encoded_signature`] and submits.
+//!
+//! The encoding is `0x` followed by the hex of the bundle's Borsh bytes — the
+//! same payload `vex_submitBundle` takes. [`decode_bundle`] ignores case and
+//! whitespace, so the text can be split into chunks of any size (e.g. one per
+//! QR code) and rejoined by concatenation; uppercased, it fits a QR code's
+//! alphanumeric mode. Signatures are `0x` plus 128 hex digits.
+//!
+//! ## Memos
+//!
+//! Bundles can't carry a memo or other free-form data yet: `Operation` has no
+//! memo variant and [`TransactionBundle`] no field for one, and an SDK-side
+//! memo would go unsigned and unseen for the same reason as an execution mode
+//! (see [Execution semantics](#execution-semantics)). Until `vexidus-types`
+//! gains one, tell deposits apart by giving each customer their own deposit
+//! address rather than a shared address plus memo.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use thiserror::Error;
+use vexidus_types::intent::{Constraints, Goal};
 use vexidus_types::*;
 
 use crate::address_utils::{self, AddressError};
+use crate::intent;
 use crate::wallet::WalletKeypair;
+use crate::wallet_client::{WalletClient, WalletClientError};
+
+/// Default cap on operations per bundle; see [`BundleBuilder::max_operations`].
+pub const DEFAULT_MAX_OPERATIONS: usize = 100;
+
+/// Highest validator commission the chain accepts, in basis points (50%).
+pub const MAX_COMMISSION_BPS: u16 = 5_000;
+
+/// Headroom [`BundleBuilder::with_estimated_gas`] adds on top of the node's
+/// estimate, in percent, for state that changes before inclusion.
+pub const GAS_ESTIMATE_MARGIN_PERCENT: u64 = 20;
 
 #[derive(Error, Debug)]
 pub enum BundleError {
@@ -25,6 +80,61 @@ pub enum BundleError {
     Address(#[from] AddressError),
     #[error("No operations specified")]
     NoOperations,
+    #[error("Goal requires a solver and can't be built into a bundle directly")]
+    RequiresSolver,
+    #[error("Exposure limit exceeded for token 0x{}: {total} > {max}", hex::encode(.token.0))]
+    ExposureLimitExceeded { token: Address, total: u128, max: u128 },
+    #[error("Invalid public key: {0}")]
+    InvalidPublicKey(String),
+    #[error("Bundle encoding doesn't match the expected layout: {0}")]
+    SerializationMismatch(String),
+    #[error("Signing key does not match bundle sender: expected {expected}, got {actual}")]
+    SenderKeyMismatch { expected: String, actual: String },
+    #[error("Bundle expired at {0}")]
+    Expired(u64),
+    #[error("Commission rate {0} bps exceeds the maximum of {MAX_COMMISSION_BPS}")]
+    InvalidCommission(u16),
+    #[error("Recipient {index} is invalid: {source}")]
+    InvalidRecipient { index: usize, source: AddressError },
+    #[error("Bundle has {count} operations, more than the maximum of {max}")]
+    TooManyOperations { count: usize, max: usize },
+    #[error("Invalid encoded bundle: {0}")]
+    InvalidEncoding(String),
+    #[error("Can't swap or pair token 0x{} with itself", hex::encode(.0.0))]
+    InvalidSwap(Address),
+    #[error("Goal amount is zero")]
+    ZeroAmount,
+    #[error("Only VXS can be staked, not token 0x{}", hex::encode(.0.0))]
+    InvalidStakeToken(Address),
+    #[error("Goal has no min_output constraint to bound its slippage")]
+    MissingMinOutput,
+    #[error("Transfer has no recipient (Address::ZERO)")]
+    MissingRecipient,
+}
+
+/// Per-operation gas costs published by the node (`vex_feeSchedule`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    /// Gas charged once per bundle, on top of its operations.
+    #[serde(default)]
+    pub base_gas: u64,
+    /// Gas per operation, keyed by variant name (see [`operation_name`]).
+    #[serde(default)]
+    pub operations: HashMap<String, u64>,
+    /// Base gas price in nanoVXS per gas unit.
+    #[serde(default)]
+    pub gas_price: u64,
+}
+
+impl FeeSchedule {
+    /// Gas for one operation, falling back to the built-in constant if the
+    /// schedule doesn't list it.
+    pub fn operation_gas(&self, op: &Operation) -> u64 {
+        self.operations
+            .get(operation_name(op))
+            .copied()
+            .unwrap_or_else(|| default_operation_gas(op))
+    }
 }
 
 /// Fluent builder for constructing transaction bundles.
@@ -32,10 +142,17 @@ pub struct BundleBuilder {
     sender: Address,
     operations: Vec<Operation>,
     max_gas: u64,
+    /// Whether [`max_gas`](Self::max_gas) was called; see
+    /// [`effective_max_gas`](Self::effective_max_gas).
+    max_gas_set: bool,
+    /// Per-operation limits, indexed like `operations`.
+    op_gas_limits: Vec<Option<u64>>,
     max_priority_fee: u64,
     valid_until: Timestamp,
     nonce: u64,
     sender_pubkey: Option<Vec<u8>>,
+    exposure_limits: Vec<(Address, u128)>,
+    max_operations: usize,
 }
 
 impl BundleBuilder {
@@ -46,10 +163,14 @@ impl BundleBuilder {
             sender: addr,
             operations: Vec::new(),
             max_gas: 100_000,
+            max_gas_set: false,
+            op_gas_limits: Vec::new(),
             max_priority_fee: 0,
             valid_until: Timestamp::now() + 3600,
             nonce: 0,
             sender_pubkey: None,
+            exposure_limits: Vec::new(),
+            max_operations: DEFAULT_MAX_OPERATIONS,
         })
     }
 
@@ -70,6 +191,44 @@ impl BundleBuilder {
         Ok(self)
     }
 
+    /// One transfer of `token` per `(recipient, amount)`, e.g. for payroll or
+    /// airdrops.
+    ///
+    /// Either every transfer is added or none is: an unparseable recipient
+    /// fails with [`InvalidRecipient`](BundleError::InvalidRecipient) naming
+    /// its index, and going over [`max_operations`](Self::max_operations)
+    /// fails with [`TooManyOperations`](BundleError::TooManyOperations).
+    pub fn transfer_many(mut self, token: &str, recipients: &[(&str, u128)]) -> Result<Self, BundleError> {
+        let count = self.operations.len() + recipients.len();
+        if count > self.max_operations {
+            return Err(BundleError::TooManyOperations { count, max: self.max_operations });
+        }
+        let token_addr = parse_token(token)?;
+        let transfers = recipients
+            .iter()
+            .enumerate()
+            .map(|(index, (to, amount))| {
+                let to = address_utils::parse_address(to)
+                    .map_err(|source| BundleError::InvalidRecipient { index, source })?;
+                Ok(Operation::Transfer { to, token: token_addr, amount: Amount(*amount) })
+            })
+            .collect::<Result<Vec<_>, BundleError>>()?;
+        self.operations.extend(transfers);
+        Ok(self)
+    }
+
+    /// [`transfer`](Self::transfer) with a per-operation gas limit; see
+    /// [`op_gas_limit`](Self::op_gas_limit).
+    pub fn transfer_with_gas(
+        self,
+        to: &str,
+        token: &str,
+        amount: u128,
+        gas_limit: u64,
+    ) -> Result<Self, BundleError> {
+        Ok(self.transfer(to, token, amount)?.op_gas_limit(gas_limit))
+    }
+
     /// Add a VSA v2 AddKey operation.
     pub fn add_key(mut self, pubkey: Vec<u8>, key_type: KeyType, role: KeyRole) -> Self {
         self.operations
@@ -77,6 +236,30 @@ impl BundleBuilder {
         self
     }
 
+    /// Add a backup key with the Recovery role (social or hardware recovery).
+    ///
+    /// What a Recovery key may sign is up to the node; this SDK's own check,
+    /// [`role_permits`], only lets it sign a RotateKey. The pubkey must be 32
+    /// bytes for Ed25519, or 33/65 bytes for Secp256k1.
+    pub fn add_recovery_key(
+        self,
+        recovery_pubkey: Vec<u8>,
+        key_type: KeyType,
+    ) -> Result<Self, BundleError> {
+        let valid = match key_type {
+            KeyType::Ed25519 => recovery_pubkey.len() == 32,
+            KeyType::Secp256k1 => matches!(recovery_pubkey.len(), 33 | 65),
+        };
+        if !valid {
+            return Err(BundleError::InvalidPublicKey(format!(
+                "{} bytes is not a valid {:?} key",
+                recovery_pubkey.len(),
+                key_type
+            )));
+        }
+        Ok(self.add_key(recovery_pubkey, key_type, KeyRole::Recovery))
+    }
+
     /// Add a VSA v2 RemoveKey operation.
     pub fn remove_key(mut self, pubkey_hash: Hash) -> Self {
         self.operations
@@ -148,10 +331,16 @@ impl BundleBuilder {
         self
     }
 
-    /// Set validator commission rate (basis points, max 5000 = 50%).
-    pub fn set_commission(mut self, rate: u16) -> Self {
+    /// Set validator commission rate in basis points (500 = 5%). Rates above
+    /// [`MAX_COMMISSION_BPS`] (50%) fail with
+    /// [`InvalidCommission`](BundleError::InvalidCommission), since the node
+    /// would reject the bundle only after it was paid for.
+    pub fn set_commission(mut self, rate: u16) -> Result<Self, BundleError> {
+        if rate > MAX_COMMISSION_BPS {
+            return Err(BundleError::InvalidCommission(rate));
+        }
         self.operations.push(Operation::SetCommission { rate });
-        self
+        Ok(self)
     }
 
     /// Self-unjail after jail period has elapsed.
@@ -364,8 +553,7 @@ impl BundleBuilder {
         amount_b: u128,
         lp_lock_duration: u64,
     ) -> Result<Self, BundleError> {
-        let addr_a = parse_token(token_a)?;
-        let addr_b = parse_token(token_b)?;
+        let (addr_a, addr_b) = parse_token_pair(token_a, token_b)?;
         self.operations.push(Operation::CreatePool {
             token_a: addr_a,
             token_b: addr_b,
@@ -386,8 +574,7 @@ impl BundleBuilder {
         amount_b: u128,
         min_lp_tokens: u128,
     ) -> Result<Self, BundleError> {
-        let addr_a = parse_token(token_a)?;
-        let addr_b = parse_token(token_b)?;
+        let (addr_a, addr_b) = parse_token_pair(token_a, token_b)?;
         self.operations.push(Operation::AddLiquidity {
             token_a: addr_a,
             token_b: addr_b,
@@ -429,8 +616,7 @@ impl BundleBuilder {
         amount_in: u128,
         min_amount_out: u128,
     ) -> Result<Self, BundleError> {
-        let from_addr = parse_token(from_token)?;
-        let to_addr = parse_token(to_token)?;
+        let (from_addr, to_addr) = parse_token_pair(from_token, to_token)?;
         self.operations.push(Operation::Swap {
             from_token: from_addr,
             to_token: to_addr,
@@ -460,6 +646,53 @@ impl BundleBuilder {
     /// Set the maximum gas the sender is willing to pay.
     pub fn max_gas(mut self, g: u64) -> Self {
         self.max_gas = g;
+        self.max_gas_set = true;
+        self
+    }
+
+    /// Budget `gas_limit` for the most recently added operation. A no-op
+    /// before any operation is added.
+    ///
+    /// Client-side only; see "Gas limits" in the [module docs](crate::bundle).
+    pub fn op_gas_limit(mut self, gas_limit: u64) -> Self {
+        let len = self.operations.len();
+        if len > 0 {
+            self.op_gas_limits.resize(len, None);
+            self.op_gas_limits[len - 1] = Some(gas_limit);
+        }
+        self
+    }
+
+    /// Per-operation gas limits, one entry per operation (`None` if unset).
+    pub fn op_gas_limits(&self) -> Vec<Option<u64>> {
+        let mut limits = self.op_gas_limits.clone();
+        limits.resize(self.operations.len(), None);
+        limits
+    }
+
+    /// The `max_gas` the bundle will be built with.
+    ///
+    /// If any operation has a limit and [`max_gas`](Self::max_gas) wasn't
+    /// called, the sum of every operation's limit (its built-in estimate where
+    /// unset). Otherwise the usual default: 100,000, raised by heavier
+    /// operations.
+    pub fn effective_max_gas(&self) -> u64 {
+        if self.max_gas_set || self.op_gas_limits.iter().all(Option::is_none) {
+            return self.max_gas;
+        }
+        self.operations
+            .iter()
+            .zip(self.op_gas_limits())
+            .map(|(op, limit)| limit.unwrap_or_else(|| default_operation_gas(op)))
+            .fold(0u64, u64::saturating_add)
+    }
+
+    /// Cap the number of operations (default [`DEFAULT_MAX_OPERATIONS`]), so a
+    /// bundle the node would reject as oversized fails here instead.
+    /// Checked by [`transfer_many`](Self::transfer_many) and
+    /// [`try_build`](Self::try_build).
+    pub fn max_operations(mut self, max: usize) -> Self {
+        self.max_operations = max;
         self
     }
 
@@ -482,14 +715,107 @@ impl BundleBuilder {
         self
     }
 
+    /// Estimate the gas this bundle will consume.
+    ///
+    /// Uses the node's published `schedule` when given (see
+    /// [`WalletClient::fee_schedule`](crate::WalletClient::fee_schedule)),
+    /// otherwise built-in per-operation constants that may lag chain params.
+    pub fn estimate_gas(&self, schedule: Option<&FeeSchedule>) -> u64 {
+        estimate_operations_gas(&self.operations, schedule)
+    }
+
+    /// Ask the node what the bundle will cost
+    /// ([`WalletClient::estimate_gas`]) and set [`max_gas`](Self::max_gas) to
+    /// that plus [`GAS_ESTIMATE_MARGIN_PERCENT`].
+    ///
+    /// Call after adding every operation. Fails with
+    /// [`WouldRevert`](WalletClientError::WouldRevert) if the node reports the
+    /// bundle would revert.
+    pub async fn with_estimated_gas(self, client: &WalletClient) -> Result<Self, WalletClientError> {
+        let gas = client.estimate_gas(&self.preview()).await?;
+        let margin = gas.saturating_mul(GAS_ESTIMATE_MARGIN_PERCENT) / 100;
+        Ok(self.max_gas(gas.saturating_add(margin)))
+    }
+
+    /// Cap the total raw amount of `token` this bundle may move.
+    ///
+    /// Sums transfers, swap inputs, and liquidity deposits of `token` (plus
+    /// stakes and delegations for VXS, `Address::ZERO`). Call once per token;
+    /// setting a token again replaces its limit. Client-side only — enforced
+    /// by [`try_build`](Self::try_build), not by the node.
+    pub fn with_exposure_limit(mut self, token: Address, max: u128) -> Self {
+        self.exposure_limits.retain(|(t, _)| *t != token);
+        self.exposure_limits.push((token, max));
+        self
+    }
+
     // --- Build ---
 
-    /// Build an unsigned bundle (empty signature).
+    /// Build an unsigned bundle after checking it: at least one and at most
+    /// [`max_operations`](Self::max_operations) operations, `valid_until`
+    /// still in the future, commission rates within
+    /// [`MAX_COMMISSION_BPS`], and the client-side exposure limits.
+    ///
+    /// Swaps carry a minimum output rather than a slippage percentage, so
+    /// there is no slippage value to range-check here; slippage on intents is
+    /// checked by [`IntentBuilder::build`](crate::IntentBuilder::build).
+    pub fn try_build(self) -> Result<TransactionBundle, BundleError> {
+        if self.operations.is_empty() {
+            return Err(BundleError::NoOperations);
+        }
+        if self.operations.len() > self.max_operations {
+            return Err(BundleError::TooManyOperations {
+                count: self.operations.len(),
+                max: self.max_operations,
+            });
+        }
+        if self.valid_until.0 <= Timestamp::now().0 {
+            return Err(BundleError::Expired(self.valid_until.0));
+        }
+        for op in &self.operations {
+            if let Operation::SetCommission { rate } = op {
+                if *rate > MAX_COMMISSION_BPS {
+                    return Err(BundleError::InvalidCommission(*rate));
+                }
+            }
+        }
+        for (token, max) in &self.exposure_limits {
+            let total = self
+                .operations
+                .iter()
+                .map(|op| token_outflow(op, token))
+                .fold(0u128, u128::saturating_add);
+            if total > *max {
+                return Err(BundleError::ExposureLimitExceeded { token: *token, total, max: *max });
+            }
+        }
+        Ok(self.build())
+    }
+
+    /// The unsigned bundle as it would be built now, leaving the builder intact.
+    fn preview(&self) -> TransactionBundle {
+        TransactionBundle {
+            user_account: self.sender,
+            operations: self.operations.clone(),
+            max_gas: self.effective_max_gas(),
+            max_priority_fee: self.max_priority_fee,
+            valid_until: self.valid_until,
+            nonce: Nonce(self.nonce),
+            signature: Signature(vec![]),
+            expiry_timestamp: None,
+            sender_pubkey: self.sender_pubkey.clone(),
+        }
+    }
+
+    /// Build an unsigned bundle (empty signature) without any of
+    /// [`try_build`](Self::try_build)'s checks — for callers that want an
+    /// empty or already-expired bundle, e.g. to hash or sign in tests.
     pub fn build(self) -> TransactionBundle {
+        let max_gas = self.effective_max_gas();
         TransactionBundle {
             user_account: self.sender,
             operations: self.operations,
-            max_gas: self.max_gas,
+            max_gas,
             max_priority_fee: self.max_priority_fee,
             valid_until: self.valid_until,
             nonce: Nonce(self.nonce),
@@ -499,16 +825,360 @@ impl BundleBuilder {
         }
     }
 
-    /// Build and sign the bundle with a wallet keypair.
-    pub fn sign(self, wallet: &WalletKeypair) -> TransactionBundle {
-        let mut bundle = self.build();
+    /// [`build`](Self::build) and [`encode_bundle`] for an offline signer; see
+    /// [offline signing](crate::bundle#offline-signing).
+    pub fn build_unsigned_encoded(self) -> Result<String, BundleError> {
+        encode_bundle(&self.build())
+    }
+
+    /// Check the bundle with [`try_build`](Self::try_build) and sign it with a
+    /// wallet keypair.
+    pub fn sign(self, wallet: &WalletKeypair) -> Result<TransactionBundle, BundleError> {
+        let mut bundle = self.try_build()?;
+        bundle.signature = wallet.sign_bundle(&bundle);
+        Ok(bundle)
+    }
+
+    /// Like [`sign`](Self::sign), but first checks that `wallet` owns the
+    /// sender account and runs [`try_build`](Self::try_build)'s guardrails.
+    ///
+    /// The sender must be the address derived from the wallet's public key
+    /// (and match [`sender_pubkey`](Self::sender_pubkey) if one was set).
+    /// Multi-key VSA accounts signed by a secondary key should use `sign`.
+    pub fn sign_checked(self, wallet: &WalletKeypair) -> Result<TransactionBundle, BundleError> {
+        let derived = address_utils::parse_address(&wallet.vx0_address())?;
+        if !address_utils::ct_eq(&derived, &self.sender) {
+            return Err(BundleError::SenderKeyMismatch {
+                expected: format!("0x{}", hex::encode(self.sender.0)),
+                actual: format!("0x{}", hex::encode(derived.0)),
+            });
+        }
+        if let Some(pubkey) = &self.sender_pubkey {
+            if !address_utils::ct_eq_bytes(pubkey, &wallet.public_key_bytes()) {
+                return Err(BundleError::SenderKeyMismatch {
+                    expected: format!("pubkey 0x{}", hex::encode(pubkey)),
+                    actual: format!("pubkey 0x{}", hex::encode(wallet.public_key_bytes())),
+                });
+            }
+        }
+        let mut bundle = self.try_build()?;
         bundle.signature = wallet.sign_bundle(&bundle);
-        bundle
+        Ok(bundle)
+    }
+}
+
+/// Estimate gas for a list of operations; see [`BundleBuilder::estimate_gas`].
+pub fn estimate_operations_gas(operations: &[Operation], schedule: Option<&FeeSchedule>) -> u64 {
+    match schedule {
+        Some(schedule) => operations
+            .iter()
+            .fold(schedule.base_gas, |acc, op| acc.saturating_add(schedule.operation_gas(op))),
+        None => operations
+            .iter()
+            .fold(0u64, |acc, op| acc.saturating_add(default_operation_gas(op))),
+    }
+}
+
+/// Build a bundle directly from a simple intent goal, skipping the solver.
+///
+/// Each goal becomes one operation. Swaps and liquidity provision need
+/// `constraints.min_output` — the swap's minimum out, or the minimum LP tokens
+/// minted — and fail with [`MissingMinOutput`](BundleError::MissingMinOutput)
+/// without it. A stake naming a validator becomes a Delegate and must be of
+/// VXS. Goals for which [`intent::requires_solver`] is true return
+/// [`BundleError::RequiresSolver`].
+pub fn from_goal(
+    sender: &str,
+    goal: &Goal,
+    constraints: &Constraints,
+) -> Result<BundleBuilder, BundleError> {
+    if intent::requires_solver(goal) {
+        return Err(BundleError::RequiresSolver);
+    }
+    let nonzero = |amount: &Amount| if amount.0 == 0 { Err(BundleError::ZeroAmount) } else { Ok(*amount) };
+    let min_output = || constraints.min_output.map(|a| a.0).ok_or(BundleError::MissingMinOutput);
+    let mut builder = BundleBuilder::new(sender)?;
+    let operation = match goal {
+        Goal::Swap { from_token, to_token, amount } => {
+            if from_token == to_token {
+                return Err(BundleError::InvalidSwap(*from_token));
+            }
+            Operation::Swap {
+                from_token: *from_token,
+                to_token: *to_token,
+                amount_in: nonzero(amount)?.0,
+                min_amount_out: min_output()?,
+            }
+        }
+        Goal::ProvideLiquidity { token_a, token_b, amount_a, amount_b } => {
+            if token_a == token_b {
+                return Err(BundleError::InvalidSwap(*token_a));
+            }
+            builder.max_gas = builder.max_gas.max(150_000);
+            Operation::AddLiquidity {
+                token_a: *token_a,
+                token_b: *token_b,
+                amount_a: nonzero(amount_a)?.0,
+                amount_b: nonzero(amount_b)?.0,
+                min_lp_tokens: min_output()?,
+            }
+        }
+        Goal::Stake { token, amount, validator: Some(validator) } => {
+            if *token != Address::ZERO {
+                return Err(BundleError::InvalidStakeToken(*token));
+            }
+            Operation::Delegate { validator: *validator, amount: nonzero(amount)? }
+        }
+        Goal::Transfer { to, token, amount } => {
+            if *to == Address::ZERO {
+                return Err(BundleError::MissingRecipient);
+            }
+            Operation::Transfer { to: *to, token: *token, amount: nonzero(amount)? }
+        }
+        Goal::Unstake { amount } => Operation::Unstake { amount: nonzero(amount)? },
+        Goal::Undelegate { validator, amount } => {
+            Operation::Undelegate { validator: *validator, amount: nonzero(amount)? }
+        }
+        Goal::ClaimRewards => Operation::ClaimRewards,
+        Goal::Stake { validator: None, .. }
+        | Goal::Bridge { .. }
+        | Goal::Composite(_)
+        | Goal::Custom(_) => return Err(BundleError::RequiresSolver),
+    };
+    builder.operations.push(operation);
+    Ok(builder)
+}
+
+/// Check `bundle`'s Borsh encoding against the layout this SDK was written
+/// for, and its signature against `sender_pubkey` when both are set.
+///
+/// The expected bytes are assembled field by field, independently of
+/// `TransactionBundle`'s derived encoding:
+///
+/// ```text
+/// user_account      [u8; 32]
+/// operations        u32 LE count, then each operation's Borsh bytes
+/// max_gas           u64 LE
+/// max_priority_fee  u64 LE
+/// valid_until       u64 LE
+/// nonce             u64 LE
+/// signature         u32 LE length, then the bytes
+/// expiry_timestamp  0, or 1 then u64 LE
+/// sender_pubkey     0, or 1 then u32 LE length and the bytes
+/// ```
+///
+/// So a `vexidus-types` that adds, drops or reorders a field fails here with
+/// the first field that moved, instead of the node misreading submissions.
+/// The signature check recomputes the signing hash through
+/// `TransactionBundle::verify_signature`, catching a bundle mutated after
+/// signing.
+pub fn assert_roundtrip(bundle: &TransactionBundle) -> Result<(), BundleError> {
+    let bytes = borsh::to_vec(bundle)
+        .map_err(|e| BundleError::SerializationMismatch(format!("encode failed: {}", e)))?;
+    check_layout(bundle, &bytes)?;
+    match (&bundle.sender_pubkey, bundle.signature.0.is_empty()) {
+        (Some(pubkey), false) => {
+            let pubkey = <[u8; 32]>::try_from(pubkey.as_slice())
+                .map_err(|_| BundleError::InvalidPublicKey(format!("{} bytes, expected 32", pubkey.len())))?;
+            if !bundle.verify_signature(&PublicKey(pubkey)) {
+                return Err(BundleError::SerializationMismatch(
+                    "signature doesn't verify against sender_pubkey".into(),
+                ));
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Walk `bytes` through the layout documented on [`assert_roundtrip`],
+/// naming the first field that doesn't match.
+fn check_layout(bundle: &TransactionBundle, bytes: &[u8]) -> Result<(), BundleError> {
+    let length = |n: usize| (n as u32).to_le_bytes().to_vec();
+    let option = |value: Option<Vec<u8>>| match value {
+        Some(mut bytes) => {
+            bytes.insert(0, 1);
+            bytes
+        }
+        None => vec![0],
+    };
+    let mut fields = vec![
+        ("user_account".to_string(), bundle.user_account.0.to_vec()),
+        ("operations.len".to_string(), length(bundle.operations.len())),
+    ];
+    for (i, op) in bundle.operations.iter().enumerate() {
+        let encoded = borsh::to_vec(op)
+            .map_err(|e| BundleError::SerializationMismatch(format!("encode failed: {}", e)))?;
+        fields.push((format!("operations[{}] ({})", i, operation_name(op)), encoded));
+    }
+    fields.extend([
+        ("max_gas".to_string(), bundle.max_gas.to_le_bytes().to_vec()),
+        ("max_priority_fee".to_string(), bundle.max_priority_fee.to_le_bytes().to_vec()),
+        ("valid_until".to_string(), bundle.valid_until.0.to_le_bytes().to_vec()),
+        ("nonce".to_string(), bundle.nonce.0.to_le_bytes().to_vec()),
+        ("signature".to_string(), [length(bundle.signature.0.len()), bundle.signature.0.clone()].concat()),
+        ("expiry_timestamp".to_string(), option(bundle.expiry_timestamp.map(|t| t.0.to_le_bytes().to_vec()))),
+        (
+            "sender_pubkey".to_string(),
+            option(bundle.sender_pubkey.as_ref().map(|pk| [length(pk.len()), pk.clone()].concat())),
+        ),
+    ]);
+
+    let mut rest = bytes;
+    for (field, expected) in fields {
+        rest = rest
+            .strip_prefix(expected.as_slice())
+            .ok_or_else(|| BundleError::SerializationMismatch(format!("{} differs", field)))?;
+    }
+    if !rest.is_empty() {
+        return Err(BundleError::SerializationMismatch(format!("{} unexpected trailing bytes", rest.len())));
+    }
+    Ok(())
+}
+
+/// Encode a bundle as `0x` + hex of its Borsh bytes; see
+/// [offline signing](crate::bundle#offline-signing).
+pub fn encode_bundle(bundle: &TransactionBundle) -> Result<String, BundleError> {
+    let bytes = borsh::to_vec(bundle).map_err(|e| BundleError::InvalidEncoding(e.to_string()))?;
+    Ok(format!("0x{}", hex::encode(bytes)))
+}
+
+/// Decode [`encode_bundle`] output (optional `0x`, any case, whitespace
+/// between chunks ignored). The whole input must be one bundle.
+pub fn decode_bundle(encoded: &str) -> Result<TransactionBundle, BundleError> {
+    let bytes = decode_hex(encoded)?;
+    borsh::from_slice(&bytes).map_err(|e| BundleError::InvalidEncoding(e.to_string()))
+}
+
+/// Set `bundle`'s signature from the hex an offline signer returned.
+pub fn attach_encoded_signature(mut bundle: TransactionBundle, signature: &str) -> Result<TransactionBundle, BundleError> {
+    let bytes = decode_hex(signature)?;
+    if bytes.len() != 64 {
+        return Err(BundleError::InvalidEncoding(format!("signature is {} bytes, expected 64", bytes.len())));
+    }
+    bundle.signature = Signature(bytes);
+    Ok(bundle)
+}
+
+fn decode_hex(encoded: &str) -> Result<Vec<u8>, BundleError> {
+    let compact: String = encoded.chars().filter(|c| !c.is_whitespace()).collect();
+    let digits = compact
+        .strip_prefix("0x")
+        .or_else(|| compact.strip_prefix("0X"))
+        .unwrap_or(&compact);
+    hex::decode(digits).map_err(|e| BundleError::InvalidEncoding(e.to_string()))
+}
+
+/// Raw amount of `token` an operation sends out of the account.
+fn token_outflow(op: &Operation, token: &Address) -> u128 {
+    match op {
+        Operation::Transfer { token: t, amount, .. } if t == token => amount.0,
+        Operation::Swap { from_token, amount_in, .. } if from_token == token => *amount_in,
+        Operation::AddLiquidity { token_a, token_b, amount_a, amount_b, .. }
+        | Operation::CreatePool { token_a, token_b, amount_a, amount_b, .. } => {
+            let mut total = 0u128;
+            if token_a == token {
+                total = total.saturating_add(*amount_a);
+            }
+            if token_b == token {
+                total = total.saturating_add(*amount_b);
+            }
+            total
+        }
+        Operation::Stake { amount, .. } | Operation::Delegate { amount, .. }
+            if *token == Address::ZERO =>
+        {
+            amount.0
+        }
+        _ => 0,
+    }
+}
+
+/// Variant name of an operation, as used in fee schedules and node responses.
+pub fn operation_name(op: &Operation) -> &'static str {
+    match op {
+        Operation::Transfer { .. } => "Transfer",
+        Operation::AddKey { .. } => "AddKey",
+        Operation::RemoveKey { .. } => "RemoveKey",
+        Operation::RotateKey { .. } => "RotateKey",
+        Operation::Stake { .. } => "Stake",
+        Operation::Unstake { .. } => "Unstake",
+        Operation::ClaimUnstake => "ClaimUnstake",
+        Operation::Delegate { .. } => "Delegate",
+        Operation::Undelegate { .. } => "Undelegate",
+        Operation::ClaimRewards => "ClaimRewards",
+        Operation::SetCommission { .. } => "SetCommission",
+        Operation::Unjail => "Unjail",
+        Operation::SetAutoCompound { .. } => "SetAutoCompound",
+        Operation::SetPoolConfig { .. } => "SetPoolConfig",
+        Operation::SetValidatorMetadata { .. } => "SetValidatorMetadata",
+        Operation::ScheduleUpgrade { .. } => "ScheduleUpgrade",
+        Operation::CancelUpgrade { .. } => "CancelUpgrade",
+        Operation::VoteUpgrade { .. } => "VoteUpgrade",
+        Operation::CreateProposal { .. } => "CreateProposal",
+        Operation::VoteProposal { .. } => "VoteProposal",
+        Operation::ExecuteProposal { .. } => "ExecuteProposal",
+        Operation::CancelProposal { .. } => "CancelProposal",
+        Operation::CreateMultiSig { .. } => "CreateMultiSig",
+        Operation::ProposeMultiSigTx { .. } => "ProposeMultiSigTx",
+        Operation::ApproveMultiSigTx { .. } => "ApproveMultiSigTx",
+        Operation::RevokeMultiSigApproval { .. } => "RevokeMultiSigApproval",
+        Operation::BridgeDeposit { .. } => "BridgeDeposit",
+        Operation::Intent { .. } => "Intent",
+        Operation::CreatePool { .. } => "CreatePool",
+        Operation::AddLiquidity { .. } => "AddLiquidity",
+        Operation::RemoveLiquidity { .. } => "RemoveLiquidity",
+        Operation::Swap { .. } => "Swap",
+        Operation::RegisterName { .. } => "RegisterName",
+        _ => "Other",
+    }
+}
+
+/// Whether a VSA v2 key with `role` may sign `op`, by this SDK's policy.
+///
+/// Owner keys may do anything; Admin keys anything except key management;
+/// Transfer keys only transfers; Recovery keys only key rotation.
+///
+/// This is a client-side check only: the policy is written here, not read
+/// from the node, which applies its own rules when the bundle is submitted.
+/// A `true` doesn't mean the node will accept the bundle, nor a `false` that
+/// it would refuse it.
+pub fn role_permits(role: &KeyRole, op: &Operation) -> bool {
+    match role {
+        KeyRole::Owner => true,
+        KeyRole::Admin => !matches!(
+            op,
+            Operation::AddKey { .. } | Operation::RemoveKey { .. } | Operation::RotateKey { .. }
+        ),
+        KeyRole::Transfer => matches!(op, Operation::Transfer { .. }),
+        KeyRole::Recovery => matches!(op, Operation::RotateKey { .. }),
+    }
+}
+
+/// Built-in gas constants. Operations that raise the builder's `max_gas`
+/// floor use that floor; the rest fit under the default `max_gas` of 100_000
+/// and use their own smaller estimates (21_000 for a transfer).
+fn default_operation_gas(op: &Operation) -> u64 {
+    match op {
+        Operation::Transfer { .. } => 21_000,
+        Operation::Swap { .. } => 100_000,
+        Operation::AddLiquidity { .. }
+        | Operation::RemoveLiquidity { .. }
+        | Operation::BridgeDeposit { .. } => 150_000,
+        Operation::CreatePool { .. } => 300_000,
+        Operation::Intent { .. }
+        | Operation::ScheduleUpgrade { .. }
+        | Operation::CreateProposal { .. }
+        | Operation::CreateMultiSig { .. }
+        | Operation::ProposeMultiSigTx { .. }
+        | Operation::ApproveMultiSigTx { .. }
+        | Operation::RegisterName { .. } => 200_000,
+        _ => 50_000,
     }
 }
 
 /// Parse a token identifier: "VXS" → Address::ZERO, otherwise parse as address.
-fn parse_token(token: &str) -> Result<Address, AddressError> {
+pub(crate) fn parse_token(token: &str) -> Result<Address, AddressError> {
     if token.eq_ignore_ascii_case("VXS") {
         Ok(Address::ZERO)
     } else {
@@ -516,6 +1186,16 @@ fn parse_token(token: &str) -> Result<Address, AddressError> {
     }
 }
 
+/// Parse both sides of a swap or pool, which must be different tokens.
+pub(crate) fn parse_token_pair(token_a: &str, token_b: &str) -> Result<(Address, Address), BundleError> {
+    let addr_a = parse_token(token_a)?;
+    let addr_b = parse_token(token_b)?;
+    if addr_a == addr_b {
+        return Err(BundleError::InvalidSwap(addr_a));
+    }
+    Ok((addr_a, addr_b))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -549,12 +1229,43 @@ mod tests {
             .unwrap()
             .transfer(&to, "VXS", 5_000_000_000)
             .unwrap()
-            .sign(&wallet);
+            .sign(&wallet)
+            .unwrap();
 
         assert_eq!(bundle.signature.0.len(), 64);
         assert!(bundle.verify_signature(&wallet.public_key()));
     }
 
+    #[test]
+    fn test_sign_checked_sender_mismatch() {
+        let owner = WalletKeypair::generate();
+        let other = WalletKeypair::generate();
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+        let builder = || {
+            BundleBuilder::new(&owner.vx0_address())
+                .unwrap()
+                .transfer(&to, "VXS", 1_000)
+                .unwrap()
+        };
+
+        match builder().sign_checked(&other) {
+            Err(BundleError::SenderKeyMismatch { expected, actual }) => {
+                assert_eq!(expected, owner.hex_address());
+                assert_eq!(actual, other.hex_address());
+            }
+            _ => panic!("Expected SenderKeyMismatch"),
+        }
+
+        let bundle = builder().sign_checked(&owner).unwrap();
+        assert!(bundle.verify_signature(&owner.public_key()));
+
+        // A revealed pubkey must belong to the signer too
+        let revealed = builder()
+            .sender_pubkey(other.public_key_bytes().to_vec())
+            .sign_checked(&owner);
+        assert!(matches!(revealed, Err(BundleError::SenderKeyMismatch { .. })));
+    }
+
     #[test]
     fn test_multi_operation_bundle() {
         let sender = format!("0x{}", hex::encode([1u8; 32]));
@@ -604,6 +1315,36 @@ mod tests {
         assert_eq!(bundle.operations.len(), 3);
     }
 
+    #[test]
+    fn test_add_recovery_key() {
+        let sender = format!("0x{}", hex::encode([1u8; 32]));
+        let bundle = BundleBuilder::new(&sender)
+            .unwrap()
+            .add_recovery_key(vec![9u8; 32], KeyType::Ed25519)
+            .unwrap()
+            .build();
+
+        match &bundle.operations[0] {
+            Operation::AddKey { pubkey, key_type, role } => {
+                assert_eq!(pubkey, &vec![9u8; 32]);
+                assert_eq!(*key_type, KeyType::Ed25519);
+                assert_eq!(*role, KeyRole::Recovery);
+            }
+            _ => panic!("Expected AddKey operation"),
+        }
+        assert!(role_permits(&KeyRole::Recovery, &Operation::RotateKey {
+            old_pubkey_hash: Hash([0u8; 32]),
+            new_pubkey: vec![1u8; 32],
+            new_key_type: KeyType::Ed25519,
+        }));
+        assert!(!role_permits(&KeyRole::Recovery, &bundle.operations[0]));
+
+        let short = BundleBuilder::new(&sender)
+            .unwrap()
+            .add_recovery_key(vec![9u8; 31], KeyType::Ed25519);
+        assert!(matches!(short, Err(BundleError::InvalidPublicKey(_))));
+    }
+
     #[test]
     fn test_create_pool() {
         let sender = format!("0x{}", hex::encode([1u8; 32]));
@@ -626,6 +1367,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_same_token_swap_and_pool_rejected() {
+        let sender = format!("0x{}", hex::encode([1u8; 32]));
+        let builder = || BundleBuilder::new(&sender).unwrap();
+        let vxs_hex = format!("0x{}", hex::encode(Address::ZERO.0));
+        assert!(matches!(builder().swap("VXS", "vxs", 1, 0), Err(BundleError::InvalidSwap(a)) if a == Address::ZERO));
+        assert!(matches!(builder().swap("VXS", &vxs_hex, 1, 0), Err(BundleError::InvalidSwap(_))));
+        assert!(matches!(builder().create_pool("VXS", "VXS", 1, 1, 0), Err(BundleError::InvalidSwap(_))));
+        assert!(matches!(builder().add_liquidity("VXS", "VXS", 1, 1, 0), Err(BundleError::InvalidSwap(_))));
+        let goal = Goal::Swap { from_token: Address::ZERO, to_token: Address::ZERO, amount: Amount(1) };
+        assert!(matches!(from_goal(&sender, &goal, &Constraints::default()), Err(BundleError::InvalidSwap(_))));
+        assert!(builder().swap("VXS", &format!("0x{}", hex::encode([5u8; 32])), 1, 0).is_ok());
+    }
+
     #[test]
     fn test_swap_operation() {
         let sender = format!("0x{}", hex::encode([1u8; 32]));
@@ -647,4 +1402,303 @@ mod tests {
             _ => panic!("Expected Swap"),
         }
     }
+
+    #[test]
+    fn test_from_goal() {
+        let sender = format!("0x{}", hex::encode([1u8; 32]));
+        let usdc = Address([5u8; 32]);
+        let validator = Address([3u8; 32]);
+        let min_900 = Constraints { min_output: Some(Amount(900)), ..Default::default() };
+        let none = Constraints::default();
+        let build = |goal: &Goal, constraints: &Constraints| from_goal(&sender, goal, constraints);
+        let op = |goal: &Goal, constraints: &Constraints| build(goal, constraints).unwrap().build().operations.remove(0);
+
+        // Swaps and liquidity take their minimum from the constraints, never 0 by default
+        let swap = Goal::Swap { from_token: Address::ZERO, to_token: usdc, amount: Amount(1_000) };
+        match op(&swap, &min_900) {
+            Operation::Swap { amount_in, min_amount_out, .. } => assert_eq!((amount_in, min_amount_out), (1_000, 900)),
+            other => panic!("Expected Swap, got {:?}", other),
+        }
+        assert!(matches!(build(&swap, &none), Err(BundleError::MissingMinOutput)));
+
+        let liquidity = Goal::ProvideLiquidity { token_a: Address::ZERO, token_b: usdc, amount_a: Amount(10), amount_b: Amount(20) };
+        match op(&liquidity, &min_900) {
+            Operation::AddLiquidity { amount_a, amount_b, min_lp_tokens, .. } => {
+                assert_eq!((amount_a, amount_b, min_lp_tokens), (10, 20, 900))
+            }
+            other => panic!("Expected AddLiquidity, got {:?}", other),
+        }
+        assert!(matches!(build(&liquidity, &none), Err(BundleError::MissingMinOutput)));
+
+        // Staking is VXS-only
+        let stake = Goal::Stake { token: Address::ZERO, amount: Amount(5), validator: Some(validator) };
+        assert!(matches!(op(&stake, &none), Operation::Delegate { amount: Amount(5), .. }));
+        let stake_usdc = Goal::Stake { token: usdc, amount: Amount(5), validator: Some(validator) };
+        assert!(matches!(build(&stake_usdc, &none), Err(BundleError::InvalidStakeToken(t)) if t == usdc));
+
+        let transfer = Goal::Transfer { to: Address([2u8; 32]), token: usdc, amount: Amount(7) };
+        assert!(matches!(op(&transfer, &none), Operation::Transfer { token, amount: Amount(7), .. } if token == usdc));
+        let unresolved = Goal::Transfer { to: Address::ZERO, token: usdc, amount: Amount(7) };
+        assert!(matches!(build(&unresolved, &none), Err(BundleError::MissingRecipient)));
+
+        assert!(matches!(op(&Goal::Unstake { amount: Amount(8) }, &none), Operation::Unstake { amount: Amount(8) }));
+        assert!(matches!(build(&Goal::Unstake { amount: Amount(0) }, &none), Err(BundleError::ZeroAmount)));
+        assert!(matches!(
+            op(&Goal::Undelegate { validator, amount: Amount(9) }, &none),
+            Operation::Undelegate { validator: v, amount: Amount(9) } if v == validator
+        ));
+        assert!(matches!(op(&Goal::ClaimRewards, &none), Operation::ClaimRewards));
+
+        // Everything else needs a solver
+        let bridge = Goal::Bridge {
+            source_chain: "solana".into(),
+            token_symbol: "SOL".into(),
+            amount: Amount(1),
+            proof: vexidus_types::bridge::BridgeProofType::Legacy,
+        };
+        for goal in [
+            Goal::Stake { token: Address::ZERO, amount: Amount(5), validator: None },
+            bridge,
+            Goal::Composite(vec![swap.clone()]),
+            Goal::Custom("something clever".into()),
+        ] {
+            assert!(matches!(build(&goal, &min_900), Err(BundleError::RequiresSolver)), "{:?}", goal);
+        }
+    }
+
+    #[test]
+    fn test_exposure_limit() {
+        let sender = format!("0x{}", hex::encode([1u8; 32]));
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+        let usdc = Address([5u8; 32]);
+        let usdc_hex = format!("0x{}", hex::encode(usdc.0));
+
+        let builder = || {
+            BundleBuilder::new(&sender)
+                .unwrap()
+                .transfer(&to, &usdc_hex, 600)
+                .unwrap()
+                .transfer(&to, &usdc_hex, 500)
+                .unwrap()
+                .transfer(&to, "VXS", 10_000)
+                .unwrap()
+        };
+
+        let result = builder()
+            .with_exposure_limit(usdc, 1_000)
+            .with_exposure_limit(Address::ZERO, 1_000_000)
+            .try_build();
+        match result {
+            Err(BundleError::ExposureLimitExceeded { token, total, max }) => {
+                assert_eq!(token, usdc);
+                assert_eq!(total, 1_100);
+                assert_eq!(max, 1_000);
+            }
+            _ => panic!("Expected ExposureLimitExceeded"),
+        }
+
+        assert!(builder().with_exposure_limit(usdc, 1_100).try_build().is_ok());
+        assert!(builder().with_exposure_limit(Address::ZERO, 9_999).try_build().is_err());
+    }
+
+    #[test]
+    fn test_offline_signing_roundtrip() {
+        let wallet = WalletKeypair::generate();
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+        let encoded = BundleBuilder::new(&wallet.hex_address())
+            .unwrap()
+            .transfer(&to, "VXS", 5_000)
+            .unwrap()
+            .nonce(4)
+            .build_unsigned_encoded()
+            .unwrap();
+        assert!(encoded.starts_with("0x"));
+
+        // Offline: chunked through QR codes, uppercased, signed
+        let upper = encoded.to_uppercase();
+        let chunks: Vec<&str> = upper.as_bytes().chunks(40).map(|c| std::str::from_utf8(c).unwrap()).collect();
+        let signature = wallet.sign_encoded_bundle(&chunks.join("\n")).unwrap();
+        assert_eq!(signature.len(), 2 + 128);
+
+        // Online: attach and submit
+        let bundle = attach_encoded_signature(decode_bundle(&encoded).unwrap(), &signature).unwrap();
+        assert_eq!(bundle.nonce.0, 4);
+        assert!(bundle.verify_signature(&wallet.public_key()));
+        assert_roundtrip(&bundle).unwrap();
+
+        assert!(matches!(decode_bundle("0xzz"), Err(BundleError::InvalidEncoding(_))));
+        assert!(matches!(decode_bundle(&encoded[..encoded.len() - 2]), Err(BundleError::InvalidEncoding(_))));
+        assert!(attach_encoded_signature(bundle, "0xabcd").is_err());
+        assert!(wallet.sign_encoded_bundle("0x00").is_err());
+    }
+
+    #[test]
+    fn test_transfer_many() {
+        let sender = format!("0x{}", hex::encode([1u8; 32]));
+        let addresses: Vec<String> = (0..50u8).map(|i| format!("0x{}", hex::encode([i + 10; 32]))).collect();
+        let recipients: Vec<(&str, u128)> =
+            addresses.iter().enumerate().map(|(i, a)| (a.as_str(), 1_000 + i as u128)).collect();
+
+        let bundle = BundleBuilder::new(&sender)
+            .unwrap()
+            .transfer_many("VXS", &recipients)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(bundle.operations.len(), 50);
+        match &bundle.operations[49] {
+            Operation::Transfer { to, token, amount } => {
+                assert_eq!(*to, Address([59u8; 32]));
+                assert_eq!(*token, Address::ZERO);
+                assert_eq!(*amount, Amount(1_049));
+            }
+            other => panic!("Expected Transfer, got {:?}", other),
+        }
+
+        let mut bad = recipients.clone();
+        bad[7].0 = "0x1234";
+        let err = BundleBuilder::new(&sender).unwrap().transfer_many("VXS", &bad).err().unwrap();
+        assert!(matches!(err, BundleError::InvalidRecipient { index: 7, .. }));
+
+        let err = BundleBuilder::new(&sender)
+            .unwrap()
+            .max_operations(49)
+            .transfer_many("VXS", &recipients)
+            .err()
+            .unwrap();
+        assert!(matches!(err, BundleError::TooManyOperations { count: 50, max: 49 }));
+    }
+
+    #[test]
+    fn test_try_build_validation() {
+        let wallet = WalletKeypair::generate();
+        let sender = wallet.hex_address();
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+
+        let empty = BundleBuilder::new(&sender).unwrap();
+        assert!(matches!(empty.try_build(), Err(BundleError::NoOperations)));
+        let empty = BundleBuilder::new(&sender).unwrap();
+        assert!(matches!(empty.sign(&wallet), Err(BundleError::NoOperations)));
+        // build() skips validation
+        assert!(BundleBuilder::new(&sender).unwrap().build().operations.is_empty());
+
+        let expired = BundleBuilder::new(&sender).unwrap().transfer(&to, "VXS", 1).unwrap().valid_for(0);
+        assert!(matches!(expired.sign(&wallet), Err(BundleError::Expired(_))));
+
+        let commission = |rate| BundleBuilder::new(&sender).unwrap().set_commission(rate);
+        assert!(commission(MAX_COMMISSION_BPS).unwrap().try_build().is_ok());
+        assert!(matches!(commission(5_001), Err(BundleError::InvalidCommission(5_001))));
+    }
+
+    #[test]
+    fn test_assert_roundtrip() {
+        let sender = format!("0x{}", hex::encode([1u8; 32]));
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+        let wallet = WalletKeypair::generate();
+        let bundle = BundleBuilder::new(&sender)
+            .unwrap()
+            .transfer(&to, "VXS", 1_000)
+            .unwrap()
+            .swap("VXS", &to, 500, 1)
+            .unwrap()
+            .sign(&wallet)
+            .unwrap();
+        assert!(assert_roundtrip(&bundle).is_ok());
+        let mismatch = |result| match result {
+            Err(BundleError::SerializationMismatch(msg)) => msg,
+            other => panic!("Expected SerializationMismatch, got {:?}", other),
+        };
+
+        // A schema that swapped two fields, or grew one, no longer lines up
+        let bytes = borsh::to_vec(&bundle).unwrap();
+        let gas_at = 32 + 4 + bundle.operations.iter().map(|op| borsh::to_vec(op).unwrap().len()).sum::<usize>();
+        let mut swapped = bytes.clone();
+        swapped[gas_at..gas_at + 16].rotate_left(8);
+        assert_ne!(bundle.max_gas, bundle.max_priority_fee);
+        assert!(mismatch(check_layout(&bundle, &swapped)).contains("max_gas"));
+        let mut grown = bytes.clone();
+        grown.push(0);
+        assert!(mismatch(check_layout(&bundle, &grown)).contains("trailing"));
+        let mut drifted = bundle.clone();
+        drifted.operations[1] = Operation::Swap {
+            from_token: Address::ZERO,
+            to_token: Address([2u8; 32]),
+            amount_in: 500,
+            min_amount_out: 0,
+        };
+        assert!(mismatch(check_layout(&drifted, &bytes)).contains("operations[1] (Swap)"));
+
+        // Changed after signing: the signature no longer covers the hash
+        let signed = BundleBuilder::new(&wallet.hex_address())
+            .unwrap()
+            .sender_pubkey(wallet.public_key().0.to_vec())
+            .transfer(&to, "VXS", 1_000)
+            .unwrap()
+            .sign(&wallet)
+            .unwrap();
+        assert!(assert_roundtrip(&signed).is_ok());
+        let mut tampered = signed.clone();
+        tampered.nonce = Nonce(signed.nonce.0 + 1);
+        assert!(mismatch(assert_roundtrip(&tampered)).contains("signature"));
+    }
+
+    #[test]
+    fn test_per_operation_gas_limits() {
+        let sender = format!("0x{}", hex::encode([1u8; 32]));
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+
+        let builder = BundleBuilder::new(&sender)
+            .unwrap()
+            .op_gas_limit(1) // nothing to apply to yet
+            .transfer_with_gas(&to, "VXS", 10, 21_000)
+            .unwrap()
+            .claim_rewards()
+            .op_gas_limit(80_000);
+        assert_eq!(builder.op_gas_limits(), vec![Some(21_000), Some(80_000)]);
+        assert_eq!(builder.build().max_gas, 101_000);
+
+        // Operations without a limit contribute their estimate
+        let builder = BundleBuilder::new(&sender)
+            .unwrap()
+            .transfer_with_gas(&to, "VXS", 10, 21_000)
+            .unwrap()
+            .unjail();
+        assert_eq!(builder.op_gas_limits(), vec![Some(21_000), None]);
+        let unjail_gas = default_operation_gas(&Operation::Unjail);
+        assert_eq!(builder.effective_max_gas(), 21_000 + unjail_gas);
+
+        // An explicit max_gas wins; no limits keeps the old default
+        let builder = BundleBuilder::new(&sender)
+            .unwrap()
+            .transfer_with_gas(&to, "VXS", 10, 21_000)
+            .unwrap()
+            .max_gas(50_000);
+        assert_eq!(builder.build().max_gas, 50_000);
+        assert_eq!(BundleBuilder::new(&sender).unwrap().build().max_gas, 100_000);
+    }
+
+    #[test]
+    fn test_estimate_gas_with_schedule() {
+        let sender = format!("0x{}", hex::encode([1u8; 32]));
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+        let token_b = format!("0x{}", hex::encode([5u8; 32]));
+
+        let builder = BundleBuilder::new(&sender)
+            .unwrap()
+            .transfer(&to, "VXS", 100)
+            .unwrap()
+            .swap("VXS", &token_b, 1_000, 900)
+            .unwrap();
+
+        // Built-in constants
+        assert_eq!(builder.estimate_gas(None), 21_000 + 100_000);
+
+        // Node schedule overrides Transfer; Swap falls back to the constant
+        let schedule = FeeSchedule {
+            base_gas: 5_000,
+            operations: HashMap::from([("Transfer".to_string(), 30_000)]),
+            gas_price: 10,
+        };
+        assert_eq!(builder.estimate_gas(Some(&schedule)), 5_000 + 30_000 + 100_000);
+    }
 }
@@ -1,12 +1,115 @@
 //! RPC client for interacting with a Vexidus node.
 
 use anyhow::Result;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{json, Value};
+use std::time::Duration;
+use vexidus_types::Timestamp;
+
+use crate::bundle::{BundleBuilder, BundleError, MAX_COMMISSION_BPS};
+use crate::transport::{collect_pages, split_page, MockTransport, RetryPolicy, Transport};
+use crate::wallet::WalletKeypair;
+use crate::wallet_client::WalletClientError;
+
+/// A validator's delegator-facing APY after commission.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorApy {
+    pub address: String,
+    /// Commission in basis points (500 = 5%).
+    pub commission_bps: u64,
+    /// Global APY (percent) minus the validator's commission share.
+    pub effective_apy: f64,
+}
+
+/// A validator's entry from `vex_getValidator` / `vex_listValidators`.
+///
+/// Fields the node leaves out take their defaults; the full response is
+/// available from [`ValidatorClient::get_validator_raw`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidatorInfo {
+    pub address: String,
+    /// Ed25519 public key, hex.
+    pub pubkey: String,
+    /// Self-bonded plus delegated VXS, in raw units.
+    #[serde(alias = "totalStake", deserialize_with = "amount_string")]
+    pub stake: String,
+    /// Commission in basis points (500 = 5%).
+    #[serde(alias = "commission", alias = "commissionRate")]
+    pub commission_rate: u64,
+    #[serde(alias = "jailed", alias = "isJailed")]
+    pub is_jailed: bool,
+    /// Unix time (seconds) the jail cooldown ends, while jailed.
+    #[serde(alias = "jailReleaseTime")]
+    pub jail_release_time: Option<u64>,
+    /// Why the validator was jailed (e.g. missed blocks), if the node says.
+    #[serde(alias = "jailReason")]
+    pub jail_reason: Option<String>,
+    /// Reputation score (0.5 to 1.0) weighting leader selection and rewards.
+    #[serde(alias = "performanceScore")]
+    pub performance_score: Option<f64>,
+    /// Profile set with [`ValidatorClient::set_validator_metadata`].
+    pub metadata: ValidatorMetadata,
+}
+
+/// Whether a validator is jailed, and when it can unjail itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JailStatus {
+    pub jailed: bool,
+    pub reason: Option<String>,
+    /// Unix time (seconds) the cooldown ends; `None` if the node doesn't say.
+    pub release_time: Option<u64>,
+}
+
+impl JailStatus {
+    /// Whether an unjail submitted at unix time `now` would be past the
+    /// cooldown. False when not jailed, and when the node doesn't report a
+    /// release time, since there's no way to tell the cooldown has ended.
+    pub fn can_unjail_at(&self, now: u64) -> bool {
+        self.jailed && self.release_time.is_some_and(|release| now >= release)
+    }
+}
+
+/// A validator's on-chain profile.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidatorMetadata {
+    pub name: String,
+    pub description: String,
+    pub website: String,
+    #[serde(alias = "avatarUrl")]
+    pub avatar_url: String,
+}
+
+/// One delegation from `vex_getDelegations`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Delegation {
+    pub delegator: String,
+    pub validator: String,
+    /// Delegated VXS, in raw units.
+    #[serde(deserialize_with = "amount_string")]
+    pub amount: String,
+}
+
+/// Network-wide staking summary from `vex_stakingInfo`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StakingInfo {
+    /// Total VXS staked, in raw units, as the node reports it.
+    #[serde(alias = "totalStaked", deserialize_with = "amount_string")]
+    pub total_staked: String,
+    #[serde(alias = "validatorCount")]
+    pub validator_count: u64,
+    /// Annual yield in percent (5.0 = 5%); `None` if the node doesn't report one.
+    #[serde(deserialize_with = "percent")]
+    pub apy: Option<f64>,
+}
 
 /// Vexidus validator RPC client.
 pub struct ValidatorClient {
     rpc_url: String,
-    client: reqwest::Client,
+    transport: Transport,
 }
 
 impl ValidatorClient {
@@ -14,32 +117,81 @@ impl ValidatorClient {
     pub fn new(rpc_url: &str) -> Self {
         Self {
             rpc_url: rpc_url.to_string(),
-            client: reqwest::Client::new(),
+            transport: Transport::http(rpc_url),
         }
     }
 
+    /// Create a client backed by canned responses (see [`MockTransport`]).
+    pub fn with_mock(mock: MockTransport) -> Self {
+        Self {
+            rpc_url: "mock://".to_string(),
+            transport: Transport::mock(mock),
+        }
+    }
+
+    /// Set the per-request timeout (default [`DEFAULT_TIMEOUT`](crate::transport::DEFAULT_TIMEOUT), 30s).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.transport.set_timeout(timeout);
+        self
+    }
+
+    /// Retry idempotent reads up to `max` times on transient failures, with
+    /// exponential backoff starting at `backoff` plus jitter. Writes are never
+    /// retried.
+    pub fn with_retries(mut self, max: u32, backoff: Duration) -> Self {
+        self.transport.set_retry_policy(RetryPolicy { max_retries: max, backoff });
+        self
+    }
+
+    /// Record request params on the client's `rpc` tracing spans (`tracing`
+    /// feature). Off by default, since params can contain addresses.
+    #[cfg(feature = "tracing")]
+    pub fn with_log_params(mut self, enabled: bool) -> Self {
+        self.transport.set_log_params(enabled);
+        self
+    }
+
+    /// Effective per-request timeout.
+    pub fn timeout(&self) -> Duration {
+        self.transport.timeout()
+    }
+
+    /// Effective retry policy for reads.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.transport.retry_policy()
+    }
+
+    /// RPC endpoint this client talks to.
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
     /// Raw JSON-RPC call.
+    /// Idempotent read, retried per the client's [`RetryPolicy`].
     async fn rpc_call(&self, method: &str, params: Value) -> Result<Value> {
-        let body = json!({
-            "jsonrpc": "2.0",
-            "method": method,
-            "params": params,
-            "id": 1
-        });
+        Ok(self.transport.call_read(method, params).await?)
+    }
 
-        let resp = self.client
-            .post(&self.rpc_url)
-            .json(&body)
-            .send()
-            .await?
-            .json::<Value>()
-            .await?;
+    /// State-changing call, sent exactly once.
+    async fn rpc_write(&self, method: &str, params: Value) -> Result<Value> {
+        Ok(self.transport.call(method, params).await?)
+    }
 
-        if let Some(error) = resp.get("error") {
-            anyhow::bail!("RPC error: {}", error);
-        }
+    /// Call any node RPC method directly, for methods the SDK doesn't wrap yet.
+    ///
+    /// Bypasses the typed helpers: the `result` comes back as raw JSON and no
+    /// retry policy is applied.
+    pub async fn raw_call(&self, method: &str, params: Value) -> Result<Value> {
+        self.rpc_write(method, params).await
+    }
 
-        Ok(resp["result"].clone())
+    /// Send several calls in one JSON-RPC batch; see
+    /// [`WalletClient::batch_call`](crate::WalletClient::batch_call).
+    pub async fn batch_call(
+        &self,
+        requests: &[(String, Value)],
+    ) -> Result<Vec<std::result::Result<Value, WalletClientError>>> {
+        Ok(self.transport.call_batch(requests).await?)
     }
 
     // --- Staking Operations ---
@@ -48,29 +200,73 @@ impl ValidatorClient {
     /// `from`: staker address (hex), `amount`: VXS in human units (e.g. "100000"),
     /// `validator_pubkey`: 64 hex chars of the Ed25519 public key.
     pub async fn stake(&self, from: &str, amount: &str, validator_pubkey: &str) -> Result<String> {
-        let result = self.rpc_call("vex_stake", json!([from, amount, validator_pubkey])).await?;
+        let result = self.rpc_write("vex_stake", json!([from, amount, validator_pubkey])).await?;
         Ok(result.as_str().unwrap_or_default().to_string())
     }
 
     /// Begin unstaking VXS (starts 21-day unbonding).
     pub async fn unstake(&self, from: &str, amount: &str) -> Result<String> {
-        let result = self.rpc_call("vex_unstake", json!([from, amount])).await?;
+        let result = self.rpc_write("vex_unstake", json!([from, amount])).await?;
         Ok(result.as_str().unwrap_or_default().to_string())
     }
 
     /// Set validator commission rate.
-    /// `rate` is in basis points (100 = 1%, max 5000 = 50%).
+    /// `rate` is in basis points (100 = 1%, max 5000 = 50%); higher rates fail
+    /// with [`BundleError::InvalidCommission`] without calling the node.
     pub async fn set_commission(&self, from: &str, rate: u16) -> Result<String> {
-        let result = self.rpc_call("vex_setCommission", json!([from, rate])).await?;
+        if rate > MAX_COMMISSION_BPS {
+            return Err(BundleError::InvalidCommission(rate).into());
+        }
+        let result = self.rpc_write("vex_setCommission", json!([from, rate])).await?;
         Ok(result.as_str().unwrap_or_default().to_string())
     }
 
     /// Self-unjail after the jail cooldown period has elapsed.
     pub async fn unjail(&self, from: &str) -> Result<String> {
-        let result = self.rpc_call("vex_unjail", json!([from])).await?;
+        let result = self.rpc_write("vex_unjail", json!([from])).await?;
         Ok(result.as_str().unwrap_or_default().to_string())
     }
 
+    /// Whether `address` is currently jailed.
+    pub async fn is_jailed(&self, address: &str) -> Result<bool> {
+        Ok(self.get_validator(address).await?.is_jailed)
+    }
+
+    /// `address`'s jail state, from [`get_validator`](Self::get_validator).
+    pub async fn jail_status(&self, address: &str) -> Result<JailStatus> {
+        let info = self.get_validator(address).await?;
+        Ok(JailStatus {
+            jailed: info.is_jailed,
+            reason: info.jail_reason,
+            release_time: info.jail_release_time,
+        })
+    }
+
+    /// Unjail `from` if it's jailed and past its cooldown, returning the tx
+    /// hash; `None` if it isn't jailed, the cooldown hasn't elapsed, or the
+    /// node doesn't report when it ends (see [`JailStatus::can_unjail_at`]).
+    ///
+    /// Unlike [`unjail`](Self::unjail), this submits an `Unjail` bundle signed
+    /// by `keypair` (which must be `from`'s key) at `from`'s pending nonce, so
+    /// the node doesn't need to hold the key. Safe to call on a timer.
+    pub async fn auto_unjail(&self, from: &str, keypair: &WalletKeypair) -> Result<Option<String>> {
+        let status = self.jail_status(from).await?;
+        if !status.can_unjail_at(Timestamp::now().0) {
+            return Ok(None);
+        }
+        // "pending" so a bundle still in the pool isn't reused
+        let nonce = self.rpc_call("eth_getTransactionCount", json!([from, "pending"])).await?;
+        let nonce = nonce
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Nonce for {} is not a hex string: {}", from, nonce))?;
+        let nonce = u64::from_str_radix(nonce.trim_start_matches("0x"), 16)?;
+        let bundle = BundleBuilder::new(from)?.nonce(nonce).unjail().sign(keypair)?;
+        let bundle_hex = format!("0x{}", hex::encode(borsh::to_vec(&bundle)?));
+        let result = self.rpc_write("vex_submitBundle", json!([bundle_hex])).await?;
+        log::info!("Validator {} was jailed ({:?}); submitted unjail", from, status.reason);
+        Ok(Some(result.as_str().unwrap_or_default().to_string()))
+    }
+
     /// Set on-chain validator profile metadata.
     pub async fn set_validator_metadata(
         &self,
@@ -80,7 +276,7 @@ impl ValidatorClient {
         website: &str,
         avatar_url: &str,
     ) -> Result<String> {
-        let result = self.rpc_call(
+        let result = self.rpc_write(
             "vex_setValidatorMetadata",
             json!([from, name, description, website, avatar_url]),
         ).await?;
@@ -90,30 +286,150 @@ impl ValidatorClient {
     // --- Read Operations ---
 
     /// Get delegations for an address (as delegator).
-    pub async fn get_delegations(&self, address: &str) -> Result<Value> {
+    pub async fn get_delegations(&self, address: &str) -> Result<Vec<Delegation>> {
+        let result = self.get_delegations_raw(address).await?;
+        if result.is_null() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// [`get_delegations`](Self::get_delegations) as the node's raw JSON.
+    pub async fn get_delegations_raw(&self, address: &str) -> Result<Value> {
         self.rpc_call("vex_getDelegations", json!([address])).await
     }
 
-    /// Get validator info by address.
-    pub async fn get_validator(&self, address: &str) -> Result<Value> {
+    /// Get validator info by address. Errors if `address` isn't a validator.
+    pub async fn get_validator(&self, address: &str) -> Result<ValidatorInfo> {
+        let result = self.get_validator_raw(address).await?;
+        if result.is_null() {
+            anyhow::bail!("{} is not a validator", address);
+        }
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// [`get_validator`](Self::get_validator) as the node's raw JSON.
+    pub async fn get_validator_raw(&self, address: &str) -> Result<Value> {
         self.rpc_call("vex_getValidator", json!([address])).await
     }
 
-    /// List active validators.
-    pub async fn list_validators(&self, limit: u32) -> Result<Value> {
+    /// List validators as `vex_listValidators` returns them. The node may
+    /// include jailed ones; check [`ValidatorInfo::is_jailed`] to skip them.
+    pub async fn list_validators(&self, limit: u32) -> Result<Vec<ValidatorInfo>> {
+        let result = self.list_validators_raw(limit).await?;
+        if result.is_null() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// [`list_validators`](Self::list_validators) as the node's raw JSON.
+    pub async fn list_validators_raw(&self, limit: u32) -> Result<Value> {
         self.rpc_call("vex_listValidators", json!([limit])).await
     }
 
+    /// One page of up to `limit` validators starting at `cursor`, and the
+    /// cursor of the next page. See [paging](crate::transport#paging).
+    pub async fn list_validators_paged(
+        &self,
+        cursor: Option<String>,
+        limit: u32,
+    ) -> Result<(Vec<ValidatorInfo>, Option<String>)> {
+        let result = self.rpc_call("vex_listValidators", json!([limit, cursor])).await?;
+        let (items, next) = split_page(result);
+        Ok((serde_json::from_value(items)?, next))
+    }
+
+    /// Every validator the node lists, jailed or not, fetched `page_size` at a
+    /// time with [`list_validators_paged`](Self::list_validators_paged).
+    pub async fn list_all_validators(&self, page_size: u32) -> Result<Vec<ValidatorInfo>> {
+        collect_pages(
+            |cursor| self.list_validators_paged(cursor, page_size),
+            |cursor| anyhow::anyhow!("Node repeated validator cursor {}", cursor),
+        )
+        .await
+    }
+
     /// Get global staking info (total staked, validator count, APY).
-    pub async fn staking_info(&self) -> Result<Value> {
+    pub async fn staking_info(&self) -> Result<StakingInfo> {
+        Ok(serde_json::from_value(self.staking_info_raw().await?)?)
+    }
+
+    /// [`staking_info`](Self::staking_info) as the node's raw JSON, including
+    /// any fields [`StakingInfo`] doesn't know yet.
+    pub async fn staking_info_raw(&self) -> Result<Value> {
         self.rpc_call("vex_stakingInfo", json!([])).await
     }
 
+    /// Projected reward, in raw units, for staking `stake_amount` raw units for
+    /// `days` days at the current global APY, compounded:
+    /// `stake * ((1 + apy)^(days / 365) - 1)`.
+    ///
+    /// An estimate: it ignores commission (see
+    /// [`effective_apy`](Self::effective_apy)) and future APY changes, and is
+    /// computed in `f64`. 0 if the node reports no APY, or a non-positive one.
+    pub async fn estimate_rewards(&self, stake_amount: u128, days: u32) -> Result<u128> {
+        let apy = self.staking_info().await?.apy.unwrap_or(0.0);
+        Ok(compound_reward(stake_amount, apy, days))
+    }
+
+    /// Effective APY (percent) a delegator earns with `validator`:
+    /// `global_apy * (1 - commission)`.
+    pub async fn effective_apy(&self, validator: &str) -> Result<f64> {
+        let global = self.global_apy().await?;
+        let info = self.get_validator(validator).await?;
+        Ok(apply_commission(global, info.commission_rate))
+    }
+
+    /// Of the first `limit` validators the node lists, the unjailed ones, best
+    /// effective delegator APY first. Jailed validators earn nothing until
+    /// they unjail, so they're left out.
+    pub async fn rank_validators_by_apy(&self, limit: u32) -> Result<Vec<ValidatorApy>> {
+        let global = self.global_apy().await?;
+        let mut ranked: Vec<ValidatorApy> = self
+            .list_validators(limit)
+            .await?
+            .into_iter()
+            .filter(|v| !v.is_jailed)
+            .map(|v| ValidatorApy {
+                commission_bps: v.commission_rate.min(10_000),
+                effective_apy: apply_commission(global, v.commission_rate),
+                address: v.address,
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.effective_apy.total_cmp(&a.effective_apy));
+        Ok(ranked)
+    }
+
+    /// Global staking APY (percent) from [`staking_info`](Self::staking_info).
+    async fn global_apy(&self) -> Result<f64> {
+        self.staking_info()
+            .await?
+            .apy
+            .ok_or_else(|| anyhow::anyhow!("Staking info has no APY"))
+    }
+
     /// Get VXS balance for an address.
     pub async fn get_balance(&self, address: &str) -> Result<Value> {
         self.rpc_call("vex_getBalance", json!([address, "VXS"])).await
     }
 
+    /// Request testnet faucet funds for each address (**testnet only** — mainnet
+    /// nodes reject `vex_faucet`).
+    ///
+    /// `amount` is in raw units. Returns one tx hash per address, in order;
+    /// stops at the first failure.
+    pub async fn faucet_fund(&self, addresses: &[&str], amount: u128) -> Result<Vec<String>> {
+        let mut hashes = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            let result = self
+                .rpc_write("vex_faucet", json!([address, amount.to_string()]))
+                .await?;
+            hashes.push(result.as_str().unwrap_or_default().to_string());
+        }
+        Ok(hashes)
+    }
+
     /// Generate a new keypair on the server (for testing only).
     pub async fn generate_keypair(&self) -> Result<Value> {
         self.rpc_call("vex_generateKeypair", json!([])).await
@@ -133,6 +449,38 @@ impl ValidatorClient {
     }
 }
 
+fn apply_commission(global_apy: f64, commission_bps: u64) -> f64 {
+    global_apy * (1.0 - commission_bps.min(10_000) as f64 / 10_000.0)
+}
+
+/// Interest on `stake` over `days` at `apy_percent`, compounded annually-equivalent.
+fn compound_reward(stake: u128, apy_percent: f64, days: u32) -> u128 {
+    if apy_percent.is_nan() || apy_percent <= 0.0 || stake == 0 || days == 0 {
+        return 0;
+    }
+    let growth = (1.0 + apy_percent / 100.0).powf(days as f64 / 365.0) - 1.0;
+    // Saturates at u128::MAX rather than wrapping
+    (stake as f64 * growth) as u128
+}
+
+/// A raw amount the node may send as a decimal string or a number; null → "".
+fn amount_string<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<String, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::String(s) => s,
+        Value::Number(n) => n.to_string(),
+        _ => String::new(),
+    })
+}
+
+/// A percentage as a number or a string like `"5.2%"`; anything else is `None`.
+fn percent<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<f64>, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().trim_end_matches('%').parse().ok(),
+        _ => None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,4 +490,202 @@ mod tests {
         let client = ValidatorClient::new("http://localhost:9933");
         assert_eq!(client.rpc_url, "http://localhost:9933");
     }
+
+    #[tokio::test]
+    async fn test_effective_apy_ranking() {
+        let mock = MockTransport::new()
+            .with_result("vex_stakingInfo", json!({ "total_staked": "1000", "apy": 10.0 }))
+            .with_result("vex_getValidator", json!({ "address": "0xaa", "commission_rate": 1_000 }))
+            .with_result(
+                "vex_listValidators",
+                json!([
+                    { "address": "0xaa", "commission_rate": 1_000 },
+                    { "address": "0xbb", "commission_rate": 500 },
+                    { "address": "0xcc", "commission_rate": 0, "is_jailed": true },
+                ]),
+            );
+        let client = ValidatorClient::with_mock(mock);
+
+        let apy = client.effective_apy("0xaa").await.unwrap();
+        assert!((apy - 9.0).abs() < 1e-9);
+
+        let ranked = client.rank_validators_by_apy(10).await.unwrap();
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].address, "0xbb");
+        assert!((ranked[0].effective_apy - 9.5).abs() < 1e-9);
+        assert_eq!(ranked[1].address, "0xaa");
+        assert_eq!(ranked[1].commission_bps, 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_typed_validator_reads() {
+        let validator = json!({
+            "address": "0xaa",
+            "pubkey": "ab".repeat(32),
+            "stake": "1000000000000",
+            "commission_rate": 500,
+            "is_jailed": true,
+            "jail_release_time": 1_700_003_600u64,
+            "performance_score": 0.8,
+            "metadata": {
+                "name": "My Validator",
+                "description": "Reliable validator node",
+                "website": "https://mysite.com",
+                "avatar_url": "https://mysite.com/avatar.png"
+            },
+            "uptime": "99.9%"
+        });
+        let mock = MockTransport::new()
+            .with_result("vex_getValidator", validator.clone())
+            .with_result("vex_getValidator", validator.clone())
+            .with_result("vex_getValidator", Value::Null)
+            .with_result(
+                "vex_listValidators",
+                json!([validator, { "address": "0xbb", "totalStake": 5, "commission": 100, "jailed": false }]),
+            )
+            .with_result(
+                "vex_getDelegations",
+                json!([{ "delegator": "0xcc", "validator": "0xaa", "amount": 2_000_000_000u64 }]),
+            );
+        let client = ValidatorClient::with_mock(mock);
+
+        let info = client.get_validator("0xaa").await.unwrap();
+        assert_eq!(info.stake, "1000000000000");
+        assert_eq!(info.commission_rate, 500);
+        assert!(info.is_jailed);
+        assert_eq!(info.jail_release_time, Some(1_700_003_600));
+        assert_eq!(info.performance_score, Some(0.8));
+        assert_eq!(info.metadata.avatar_url, "https://mysite.com/avatar.png");
+        // Unknown fields only in the raw form
+        assert_eq!(client.get_validator_raw("0xaa").await.unwrap()["uptime"], "99.9%");
+        assert!(client.get_validator("0xdd").await.unwrap_err().to_string().contains("not a validator"));
+
+        let validators = client.list_validators(10).await.unwrap();
+        assert_eq!(validators[1].stake, "5");
+        assert_eq!(validators[1].commission_rate, 100);
+        assert!(!validators[1].is_jailed);
+        assert_eq!(validators[1].metadata, ValidatorMetadata::default());
+        assert_eq!(client.list_validators_raw(10).await.unwrap()[0]["uptime"], "99.9%");
+
+        let delegations = client.get_delegations("0xcc").await.unwrap();
+        assert_eq!(
+            delegations,
+            vec![Delegation { delegator: "0xcc".into(), validator: "0xaa".into(), amount: "2000000000".into() }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_commission_range() {
+        let mock = MockTransport::new().with_result("vex_setCommission", json!("0xset"));
+        let client = ValidatorClient::with_mock(mock.clone());
+
+        assert_eq!(client.set_commission("0xaa", 5_000).await.unwrap(), "0xset");
+        let err = client.set_commission("0xaa", 5_001).await.unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(BundleError::InvalidCommission(5_001))));
+        assert_eq!(mock.call_count("vex_setCommission"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_jail_status_and_auto_unjail() {
+        let wallet = WalletKeypair::generate();
+        let from = wallet.hex_address();
+        let jailed = |release: u64| {
+            json!({ "address": from, "jailed": true, "jailReason": "missed 5 blocks", "jailReleaseTime": release })
+        };
+        let mock = MockTransport::new()
+            .with_result("vex_getValidator", json!({ "address": from, "is_jailed": false }))
+            .with_result("vex_getValidator", json!({ "address": from, "is_jailed": false }))
+            .with_result("vex_getValidator", jailed(u64::MAX))
+            .with_result("vex_getValidator", jailed(u64::MAX))
+            .with_result("vex_getValidator", jailed(1))
+            .with_result("eth_getTransactionCount", json!("0x5"))
+            .with_result("vex_submitBundle", json!("0xunjailed"));
+        let client = ValidatorClient::with_mock(mock.clone());
+
+        // Not jailed
+        assert!(!client.is_jailed(&from).await.unwrap());
+        assert_eq!(client.auto_unjail(&from, &wallet).await.unwrap(), None);
+
+        // Jailed, cooling down
+        let status = client.jail_status(&from).await.unwrap();
+        assert_eq!(status.reason.as_deref(), Some("missed 5 blocks"));
+        assert!(!status.can_unjail_at(Timestamp::now().0));
+        assert_eq!(client.auto_unjail(&from, &wallet).await.unwrap(), None);
+        assert_eq!(mock.call_count("vex_submitBundle"), 0);
+
+        // Jailed, eligible
+        assert_eq!(client.auto_unjail(&from, &wallet).await.unwrap().as_deref(), Some("0xunjailed"));
+        let (_, params) = mock.calls().into_iter().find(|(m, _)| m == "vex_submitBundle").unwrap();
+        let bytes = hex::decode(params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+        let bundle: vexidus_types::TransactionBundle = borsh::from_slice(&bytes).unwrap();
+        assert!(matches!(bundle.operations[..], [vexidus_types::Operation::Unjail]));
+        assert_eq!(bundle.nonce.0, 5);
+        let (_, params) = mock.calls().into_iter().find(|(m, _)| m == "eth_getTransactionCount").unwrap();
+        assert_eq!(params, json!([from, "pending"]));
+
+        // Unknown release time: don't guess the cooldown is over
+        let unknown = JailStatus { jailed: true, reason: None, release_time: None };
+        assert!(!unknown.can_unjail_at(u64::MAX));
+        let mock = MockTransport::new()
+            .with_result("vex_getValidator", json!({ "address": from, "jailed": true }));
+        let client = ValidatorClient::with_mock(mock.clone());
+        assert_eq!(client.auto_unjail(&from, &wallet).await.unwrap(), None);
+        assert_eq!(mock.call_count("eth_getTransactionCount"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_auto_unjail_rejects_non_string_nonce() {
+        let wallet = WalletKeypair::generate();
+        let from = wallet.hex_address();
+        let mock = MockTransport::new()
+            .with_result("vex_getValidator", json!({ "address": from, "jailed": true, "jailReleaseTime": 1 }))
+            .with_result("eth_getTransactionCount", Value::Null);
+        let client = ValidatorClient::with_mock(mock.clone());
+        assert!(client.auto_unjail(&from, &wallet).await.is_err());
+        assert_eq!(mock.call_count("vex_submitBundle"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_list_validators_paged() {
+        let mock = MockTransport::new()
+            .with_result("vex_listValidators", json!({ "items": [{ "address": "0xaa" }], "nextCursor": "p2" }))
+            .with_result("vex_listValidators", json!({ "items": [{ "address": "0xbb" }] }))
+            .with_result("vex_listValidators", json!({ "items": [], "nextCursor": "p2" }));
+        let client = ValidatorClient::with_mock(mock.clone());
+
+        let (page, next) = client.list_validators_paged(None, 1).await.unwrap();
+        assert_eq!((page[0].address.as_str(), next.as_deref()), ("0xaa", Some("p2")));
+        let (page, next) = client.list_validators_paged(next, 1).await.unwrap();
+        assert_eq!((page[0].address.as_str(), next), ("0xbb", None));
+        let (_, params) = mock.calls().pop().unwrap();
+        assert_eq!(params, json!([1, "p2"]));
+
+        // A node stuck on one cursor is an error, not an endless loop
+        let mock = MockTransport::new()
+            .with_result("vex_listValidators", json!({ "items": [], "nextCursor": "p2" }));
+        assert!(ValidatorClient::with_mock(mock).list_all_validators(1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_staking_info_and_reward_estimate() {
+        let mock = MockTransport::new()
+            .with_result("vex_stakingInfo", json!({ "totalStaked": 5_000_000, "validatorCount": 7, "apy": "10%" }))
+            .with_result("vex_stakingInfo", json!({ "total_staked": "0", "apy": 0 }))
+            .with_result("vex_stakingInfo", json!({ "total_staked": "0" }));
+        let client = ValidatorClient::with_mock(mock);
+
+        let info = client.staking_info().await.unwrap();
+        assert_eq!(info, StakingInfo { total_staked: "5000000".into(), validator_count: 7, apy: Some(10.0) });
+
+        // A full year at 10% is exactly 10%; half a year compounds to less than 5%
+        let stake = 1_000_000_000_000u128;
+        assert_eq!(compound_reward(stake, 10.0, 365), 100_000_000_000);
+        let half = compound_reward(stake, 10.0, 182);
+        assert!(half > 47_000_000_000 && half < 50_000_000_000, "{}", half);
+
+        // Zero, then missing, APY
+        assert_eq!(client.estimate_rewards(stake, 365).await.unwrap(), 0);
+        assert_eq!(client.estimate_rewards(stake, 365).await.unwrap(), 0);
+        assert!(client.effective_apy("0xaa").await.is_err());
+    }
 }
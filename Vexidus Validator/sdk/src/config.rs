@@ -1,8 +1,30 @@
-//! Validator configuration — TOML-based config and systemd service generation.
+//! Validator configuration — TOML-based config, and service definitions for
+//! systemd, launchd, Windows (`sc.exe`) and containers.
 
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use anyhow::Result;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Binary checksum mismatch: expected {expected}, got {actual}")]
+    BinaryChecksumMismatch { expected: String, actual: String },
+    #[error("p2p_port and rpc_port are both {0}")]
+    PortCollision(u16),
+    #[error("Key file not found: {0}")]
+    MissingKeyFile(String),
+    #[error("Malformed multiaddr {addr:?}: {reason}")]
+    InvalidMultiaddr { addr: String, reason: String },
+    #[error("rpc_url is empty")]
+    EmptyRpcUrl,
+    #[error("binary_sha256 must be 64 hex characters, got {0:?}")]
+    InvalidChecksum(String),
+    #[error("Binary path {0:?} can't be quoted for the ExecStartPre check")]
+    UnquotableBinaryPath(String),
+}
 
 /// Validator node configuration (stored as `validator.toml`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,8 +60,23 @@ pub struct ValidatorConfig {
     /// Enable verbose logging
     #[serde(default)]
     pub verbose: bool,
+
+    /// Expected SHA-256 of the node binary (hex). When set, the generated
+    /// systemd unit re-verifies the binary before every start.
+    #[serde(default)]
+    pub binary_sha256: Option<String>,
 }
 
+/// Label of the generated launchd job.
+const LAUNCHD_LABEL: &str = "io.vexidus.validator";
+/// Name of the generated Windows service.
+const WINDOWS_SERVICE_NAME: &str = "VexidusValidator";
+
+/// Where the generated container files mount `data_dir`.
+const CONTAINER_DATA_DIR: &str = "/data";
+/// Where the generated container files mount the key file (read-only).
+const CONTAINER_KEY_DIR: &str = "/keys";
+
 fn default_rpc_url() -> String { "http://localhost:9933".into() }
 fn default_p2p_port() -> u16 { 9944 }
 fn default_rpc_port() -> u16 { 9933 }
@@ -57,18 +94,56 @@ impl Default for ValidatorConfig {
             data_dir: default_data_dir(),
             bootnodes: None,
             verbose: false,
+            binary_sha256: None,
         }
     }
 }
 
 impl ValidatorConfig {
-    /// Load config from a TOML file.
+    /// Load config from a TOML file and [`validate`](Self::validate) it.
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
         let config: Self = toml::from_str(&content)?;
+        config.validate()?;
         Ok(config)
     }
 
+    /// Check the config for mistakes that would stop the node starting,
+    /// reporting the first one found: an empty `rpc_url`, `p2p_port` equal to
+    /// `rpc_port`, a `binary_sha256` that isn't a SHA-256 hex digest, a
+    /// malformed `external_addr` or bootnode multiaddr, or a `keypair_path`
+    /// that doesn't exist.
+    ///
+    /// Multiaddrs are checked structurally (`/ip4`, `/ip6`, `/dns*`, `/tcp`,
+    /// `/udp`, `/p2p` and value-less transports like `/quic-v1`), not resolved.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.rpc_url.trim().is_empty() {
+            return Err(ConfigError::EmptyRpcUrl);
+        }
+        if self.p2p_port == self.rpc_port {
+            return Err(ConfigError::PortCollision(self.p2p_port));
+        }
+        if let Some(sha) = &self.binary_sha256 {
+            checked_sha256(sha)?;
+        }
+        let bootnodes = self.bootnodes.as_deref().unwrap_or_default();
+        let addrs = self
+            .external_addr
+            .iter()
+            .map(String::as_str)
+            .chain(bootnodes.split(',').map(str::trim).filter(|a| !a.is_empty()));
+        for addr in addrs {
+            check_multiaddr(addr).map_err(|reason| ConfigError::InvalidMultiaddr {
+                addr: addr.to_string(),
+                reason,
+            })?;
+        }
+        if !Path::new(&self.keypair_path).is_file() {
+            return Err(ConfigError::MissingKeyFile(self.keypair_path.clone()));
+        }
+        Ok(())
+    }
+
     /// Save config to a TOML file.
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let content = toml::to_string_pretty(self)?;
@@ -98,10 +173,45 @@ impl ValidatorConfig {
         args
     }
 
+    /// Check that the node binary at `binary_path` hashes to `expected_sha256`.
+    ///
+    /// `expected_sha256` is a hex digest (optional `0x` prefix, any case).
+    pub fn verify_binary(&self, binary_path: &str, expected_sha256: &str) -> Result<(), ConfigError> {
+        use sha2::{Digest, Sha256};
+        let mut file = std::fs::File::open(binary_path)?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut file, &mut hasher)?;
+        let actual = hex::encode(hasher.finalize());
+        let expected = normalize_sha256(expected_sha256);
+        if actual != expected {
+            return Err(ConfigError::BinaryChecksumMismatch { expected, actual });
+        }
+        Ok(())
+    }
+
     /// Generate a systemd service unit file for this validator.
-    pub fn generate_systemd_service(&self, binary_path: &str, working_dir: &str) -> String {
+    ///
+    /// If `binary_sha256` is set, an `ExecStartPre` check refuses to launch a
+    /// binary whose SHA-256 doesn't match. The check runs through `/bin/sh`,
+    /// so the digest must be exactly 64 hex characters (optional `0x`) and
+    /// `binary_path` must not contain quotes, `$`, `` ` `` or `\`; anything
+    /// else is an error rather than being spliced into the shell command.
+    pub fn generate_systemd_service(&self, binary_path: &str, working_dir: &str) -> Result<String, ConfigError> {
         let args = self.to_node_args().join(" ");
-        format!(
+        let verify = match &self.binary_sha256 {
+            Some(sha) => {
+                let sha = checked_sha256(sha)?;
+                if binary_path.contains(['\'', '"', '$', '`', '\\', '\n']) {
+                    return Err(ConfigError::UnquotableBinaryPath(binary_path.to_string()));
+                }
+                format!(
+                    "ExecStartPre=/bin/sh -c 'echo \"{}  {}\" | sha256sum --check --status'\n",
+                    sha, binary_path,
+                )
+            }
+            None => String::new(),
+        };
+        Ok(format!(
 r#"[Unit]
 Description=Vexidus Validator Node
 After=network.target
@@ -112,7 +222,7 @@ Type=simple
 User=vexidus
 Group=vexidus
 WorkingDirectory={working_dir}
-ExecStart={binary_path} {args}
+{verify}ExecStart={binary_path} {args}
 Restart=always
 RestartSec=5
 LimitNOFILE=65536
@@ -124,9 +234,150 @@ NoNewPrivileges=true
 
 [Install]
 WantedBy=multi-user.target
+"#))
+    }
+
+    /// Generate a launchd property list (for `launchctl load`) that keeps this
+    /// validator running on macOS.
+    ///
+    /// Install it as `/Library/LaunchDaemons/io.vexidus.validator.plist`; the
+    /// label is `io.vexidus.validator`. Output goes to `validator.log` in
+    /// `working_dir`.
+    pub fn generate_launchd_plist(&self, binary_path: &str, working_dir: &str) -> String {
+        let arguments: String = std::iter::once(binary_path.to_string())
+            .chain(self.to_node_args())
+            .map(|arg| format!("        <string>{}</string>\n", xml_escape(&arg)))
+            .collect();
+        let working_dir = xml_escape(working_dir);
+        format!(
+r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{LAUNCHD_LABEL}</string>
+    <key>ProgramArguments</key>
+    <array>
+{arguments}    </array>
+    <key>WorkingDirectory</key>
+    <string>{working_dir}</string>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <true/>
+    <key>StandardOutPath</key>
+    <string>{working_dir}/validator.log</string>
+    <key>StandardErrorPath</key>
+    <string>{working_dir}/validator.log</string>
+    <key>SoftResourceLimits</key>
+    <dict>
+        <key>NumberOfFiles</key>
+        <integer>65536</integer>
+    </dict>
+</dict>
+</plist>
 "#)
     }
 
+    /// `sc.exe` command creating a Windows service (`VexidusValidator`) that
+    /// starts this validator at boot. Run it from an elevated prompt, then
+    /// `sc.exe start VexidusValidator`.
+    ///
+    /// The binary must be a service-aware build; wrap a console build with a
+    /// service host such as NSSM instead, using the same arguments
+    /// ([`to_node_args`](Self::to_node_args)).
+    pub fn generate_windows_service_command(&self, binary_path: &str) -> String {
+        let bin_path: Vec<String> = std::iter::once(binary_path.to_string())
+            .chain(self.to_node_args())
+            .map(|arg| windows_quote(&arg))
+            .collect();
+        format!(
+            "sc.exe create {} binPath= \"{}\" start= auto DisplayName= \"Vexidus Validator Node\"",
+            WINDOWS_SERVICE_NAME,
+            bin_path.join(" ").replace('"', "\\\""),
+        )
+    }
+
+    /// Generate a `docker-compose.yml` running this validator from `image`.
+    ///
+    /// `p2p_port` and `rpc_port` are published on the same host ports,
+    /// `data_dir` is bind-mounted at `/data`, and the key file is bind-mounted
+    /// read-only under `/keys`, using the long volume syntax so host paths
+    /// are plain quoted scalars. Relative host paths resolve against the compose file's
+    /// directory. The command is [`to_node_args`](Self::to_node_args) with the
+    /// paths rewritten to their container locations.
+    pub fn generate_compose(&self, image: &str) -> String {
+        let (container, key_file) = self.in_container();
+        let command: Vec<String> = container.to_node_args().iter().map(|a| yaml_str(a)).collect();
+        format!(
+r#"services:
+  validator:
+    image: {image}
+    restart: unless-stopped
+    ports:
+      - "{p2p}:{p2p}"
+      - "{rpc}:{rpc}"
+    volumes:
+      - type: bind
+        source: {data}
+        target: {data_dir}
+      - type: bind
+        source: {key}
+        target: {key_file}
+        read_only: true
+    command: [{command}]
+"#,
+            image = yaml_str(image),
+            p2p = self.p2p_port,
+            rpc = self.rpc_port,
+            data = yaml_str(&self.data_dir),
+            data_dir = yaml_str(CONTAINER_DATA_DIR),
+            key = yaml_str(&self.keypair_path),
+            key_file = yaml_str(&key_file),
+            command = command.join(", "),
+        )
+    }
+
+    /// Generate a Dockerfile that adds `vexidus-node` (from the build context)
+    /// to `base_image` and runs it with this config's arguments.
+    ///
+    /// Ports and paths match [`generate_compose`](Self::generate_compose); the
+    /// key file isn't baked in and must be mounted at run time.
+    pub fn generate_dockerfile(&self, base_image: &str) -> String {
+        let (container, _) = self.in_container();
+        let mut exec = vec!["vexidus-node".to_string()];
+        exec.extend(container.to_node_args());
+        let exec: Vec<String> = exec.iter().map(|a| yaml_str(a)).collect();
+        format!(
+r#"FROM {base_image}
+COPY vexidus-node /usr/local/bin/vexidus-node
+EXPOSE {p2p}/tcp {rpc}/tcp
+VOLUME ["{data_dir}"]
+ENTRYPOINT [{exec}]
+"#,
+            p2p = self.p2p_port,
+            rpc = self.rpc_port,
+            data_dir = CONTAINER_DATA_DIR,
+            exec = exec.join(", "),
+        )
+    }
+
+    /// This config with its paths moved to their container mounts, plus the
+    /// container path of the key file.
+    fn in_container(&self) -> (Self, String) {
+        let key_name = Path::new(&self.keypair_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("validator.key");
+        let key_file = format!("{}/{}", CONTAINER_KEY_DIR, key_name);
+        let config = Self {
+            keypair_path: key_file.clone(),
+            data_dir: CONTAINER_DATA_DIR.into(),
+            ..self.clone()
+        };
+        (config, key_file)
+    }
+
     /// Write the systemd service file to a given path.
     pub fn write_systemd_service<P: AsRef<Path>>(
         &self,
@@ -134,12 +385,77 @@ WantedBy=multi-user.target
         binary_path: &str,
         working_dir: &str,
     ) -> Result<()> {
-        let content = self.generate_systemd_service(binary_path, working_dir);
+        let content = self.generate_systemd_service(binary_path, working_dir)?;
         std::fs::write(path, content)?;
         Ok(())
     }
 }
 
+/// Structural multiaddr check: `/protocol[/value]...` with known protocols.
+fn check_multiaddr(addr: &str) -> Result<(), String> {
+    let rest = addr.strip_prefix('/').ok_or("must start with '/'")?;
+    let mut parts = rest.split('/');
+    let mut any = false;
+    while let Some(protocol) = parts.next() {
+        any = true;
+        let mut value = || parts.next().filter(|v| !v.is_empty()).ok_or(format!("/{} needs a value", protocol));
+        match protocol {
+            "ip4" => {
+                value()?.parse::<std::net::Ipv4Addr>().map_err(|e| format!("bad /ip4: {}", e))?;
+            }
+            "ip6" => {
+                value()?.parse::<std::net::Ipv6Addr>().map_err(|e| format!("bad /ip6: {}", e))?;
+            }
+            "tcp" | "udp" => {
+                value()?.parse::<u16>().map_err(|e| format!("bad /{} port: {}", protocol, e))?;
+            }
+            "dns" | "dns4" | "dns6" | "dnsaddr" | "p2p" => {
+                value()?;
+            }
+            "quic" | "quic-v1" | "ws" | "wss" => {}
+            "" => return Err("empty component".into()),
+            other => return Err(format!("unknown protocol /{}", other)),
+        }
+    }
+    if any { Ok(()) } else { Err("no components".into()) }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Quote a Windows command-line argument if it contains spaces.
+fn windows_quote(arg: &str) -> String {
+    if arg.contains([' ', '\t']) {
+        format!("\"{}\"", arg)
+    } else {
+        arg.to_string()
+    }
+}
+
+/// Double-quoted string, valid as both YAML and a Dockerfile exec-form argument.
+fn yaml_str(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn normalize_sha256(sha: &str) -> String {
+    let sha = sha.trim();
+    sha.strip_prefix("0x").unwrap_or(sha).to_lowercase()
+}
+
+/// [`normalize_sha256`], requiring the result to be a 64-character hex digest.
+fn checked_sha256(sha: &str) -> Result<String, ConfigError> {
+    let normalized = normalize_sha256(sha);
+    if normalized.len() != 64 || !normalized.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(ConfigError::InvalidChecksum(sha.to_string()));
+    }
+    Ok(normalized)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,8 +472,11 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("validator.toml");
 
+        let key_path = dir.path().join("validator.key");
+        std::fs::write(&key_path, "00".repeat(32)).unwrap();
+
         let config = ValidatorConfig {
-            keypair_path: "/opt/vexidus/validator.key".into(),
+            keypair_path: key_path.to_str().unwrap().into(),
             external_addr: Some("/ip4/51.255.80.34/tcp/9944".into()),
             bootnodes: Some("/ip4/10.0.0.1/tcp/9944/p2p/12D3KooW...".into()),
             ..Default::default()
@@ -165,7 +484,7 @@ mod tests {
         config.save(&path).unwrap();
 
         let loaded = ValidatorConfig::load(&path).unwrap();
-        assert_eq!(loaded.keypair_path, "/opt/vexidus/validator.key");
+        assert_eq!(loaded.keypair_path, key_path.to_str().unwrap());
         assert_eq!(loaded.external_addr.unwrap(), "/ip4/51.255.80.34/tcp/9944");
     }
 
@@ -176,15 +495,137 @@ mod tests {
             p2p_port: 9945,
             ..Default::default()
         };
-        let service = config.generate_systemd_service(
-            "/usr/local/bin/vexidus-node",
-            "/opt/vexidus",
-        );
+        let service = config
+            .generate_systemd_service("/usr/local/bin/vexidus-node", "/opt/vexidus")
+            .unwrap();
         assert!(service.contains("ExecStart=/usr/local/bin/vexidus-node"));
         assert!(service.contains("--validator-key"));
         assert!(service.contains("--p2p-port 9945"));
     }
 
+    #[test]
+    fn test_validate() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_path = dir.path().join("validator.key");
+        std::fs::write(&key_path, "00".repeat(32)).unwrap();
+        let valid = ValidatorConfig {
+            keypair_path: key_path.to_str().unwrap().into(),
+            external_addr: Some("/dns4/val.example.com/udp/9944/quic-v1".into()),
+            bootnodes: Some("/ip4/10.0.0.1/tcp/9944/p2p/12D3KooW, /ip6/::1/tcp/9944".into()),
+            ..Default::default()
+        };
+        assert!(valid.validate().is_ok());
+
+        let check = |config: ValidatorConfig| config.validate().unwrap_err();
+        assert!(matches!(
+            check(ValidatorConfig { rpc_url: " ".into(), ..valid.clone() }),
+            ConfigError::EmptyRpcUrl
+        ));
+        assert!(matches!(
+            check(ValidatorConfig { rpc_port: 9944, ..valid.clone() }),
+            ConfigError::PortCollision(9944)
+        ));
+        for bad in ["ip4/1.2.3.4/tcp/1", "/ip4/1.2.3/tcp/1", "/ip4/1.2.3.4/tcp/70000", "/ip4/1.2.3.4/tcp", "/foo/1"] {
+            assert!(
+                matches!(
+                    check(ValidatorConfig { external_addr: Some(bad.into()), ..valid.clone() }),
+                    ConfigError::InvalidMultiaddr { ref addr, .. } if addr == bad
+                ),
+                "{}",
+                bad
+            );
+        }
+        assert!(matches!(
+            check(ValidatorConfig { bootnodes: Some("/ip4/10.0.0.1/tcp/9944,/tcp".into()), ..valid.clone() }),
+            ConfigError::InvalidMultiaddr { .. }
+        ));
+        let missing = dir.path().join("missing.key").to_str().unwrap().to_string();
+        assert!(matches!(
+            check(ValidatorConfig { keypair_path: missing.clone(), ..valid.clone() }),
+            ConfigError::MissingKeyFile(path) if path == missing
+        ));
+
+        // load() rejects what validate() rejects
+        let path = dir.path().join("validator.toml");
+        ValidatorConfig { p2p_port: 9933, ..valid }.save(&path).unwrap();
+        assert!(ValidatorConfig::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_launchd_and_windows_service() {
+        let config = ValidatorConfig {
+            keypair_path: "/usr/local/vexidus/validator.key".into(),
+            p2p_port: 9945,
+            ..Default::default()
+        };
+        let plist = config.generate_launchd_plist("/usr/local/bin/vexidus-node", "/usr/local/vexidus");
+        assert!(plist.starts_with("<?xml version=\"1.0\""));
+        assert!(plist.contains("<key>Label</key>\n    <string>io.vexidus.validator</string>"));
+        let arguments = "<key>ProgramArguments</key>\n    <array>\n        <string>/usr/local/bin/vexidus-node</string>\n";
+        assert!(plist.contains(arguments));
+        assert!(plist.contains(
+            "<string>--validator-key</string>\n        <string>/usr/local/vexidus/validator.key</string>"
+        ));
+        assert!(plist.contains("<string>--p2p-port</string>\n        <string>9945</string>"));
+        assert!(plist.contains("<key>WorkingDirectory</key>\n    <string>/usr/local/vexidus</string>"));
+        assert!(config.generate_launchd_plist("/bin/node", "/a&b").contains("<string>/a&amp;b</string>"));
+
+        let config = ValidatorConfig {
+            keypair_path: r"C:\Program Files\Vexidus\validator.key".into(),
+            ..Default::default()
+        };
+        let command = config.generate_windows_service_command(r"C:\Vexidus\vexidus-node.exe");
+        assert!(command.starts_with(r#"sc.exe create VexidusValidator binPath= "C:\Vexidus\vexidus-node.exe --data-dir"#));
+        assert!(command.contains(r#"--validator-key \"C:\Program Files\Vexidus\validator.key\""#));
+        assert!(command.ends_with(r#"start= auto DisplayName= "Vexidus Validator Node""#));
+    }
+
+    #[test]
+    fn test_container_generation() {
+        let config = ValidatorConfig {
+            keypair_path: "/opt/vexidus/validator.key".into(),
+            p2p_port: 30333,
+            rpc_port: 8545,
+            data_dir: "/srv/vexidus".into(),
+            ..Default::default()
+        };
+        let compose = config.generate_compose("vexidus/node:1.4");
+        let compose: serde_yaml::Value = serde_yaml::from_str(&compose).expect("compose file is valid YAML");
+        let service = &compose["services"]["validator"];
+        assert_eq!(service["image"], "vexidus/node:1.4");
+        assert_eq!(service["ports"][0], "30333:30333");
+        assert_eq!(service["ports"][1], "8545:8545");
+        let volumes = service["volumes"].as_sequence().unwrap();
+        assert_eq!(volumes.len(), 2);
+        assert_eq!(volumes[0]["type"], "bind");
+        assert_eq!(volumes[0]["source"], "/srv/vexidus");
+        assert_eq!(volumes[0]["target"], "/data");
+        assert!(volumes[0].get("read_only").is_none());
+        assert_eq!(volumes[1]["source"], "/opt/vexidus/validator.key");
+        assert_eq!(volumes[1]["target"], "/keys/validator.key");
+        assert_eq!(volumes[1]["read_only"], true);
+        let command: Vec<&str> = service["command"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|arg| arg.as_str().unwrap())
+            .collect();
+        assert!(command.windows(2).any(|w| w == ["--validator-key", "/keys/validator.key"]));
+        assert!(command.windows(2).any(|w| w == ["--data-dir", "/data"]));
+        assert!(command.windows(2).any(|w| w == ["--p2p-port", "30333"]));
+
+        // Paths with YAML-significant characters stay single scalars
+        let config = ValidatorConfig { data_dir: "/srv/a: b #c".into(), ..config };
+        let compose: serde_yaml::Value = serde_yaml::from_str(&config.generate_compose("img")).unwrap();
+        assert_eq!(compose["services"]["validator"]["volumes"][0]["source"], "/srv/a: b #c");
+
+        let dockerfile = config.generate_dockerfile("debian:bookworm-slim");
+        assert!(dockerfile.starts_with("FROM debian:bookworm-slim\n"));
+        assert!(dockerfile.contains("EXPOSE 30333/tcp 8545/tcp"));
+        assert!(dockerfile.contains("ENTRYPOINT [\"vexidus-node\", \"--data-dir\", \"/data\""));
+        assert!(dockerfile.contains("\"--validator-key\", \"/keys/validator.key\""));
+    }
+
     #[test]
     fn test_to_node_args() {
         let config = ValidatorConfig {
@@ -196,4 +637,56 @@ mod tests {
         assert!(args.contains(&"--verbose".to_string()));
         assert!(args.contains(&"--bootnodes".to_string()));
     }
+
+    #[test]
+    fn test_verify_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vexidus-node");
+        std::fs::write(&path, b"hello").unwrap();
+        let path = path.to_str().unwrap();
+
+        // sha256("hello")
+        let digest = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        let config = ValidatorConfig::default();
+        assert!(config.verify_binary(path, digest).is_ok());
+        assert!(config.verify_binary(path, &format!("0x{}", digest.to_uppercase())).is_ok());
+
+        let wrong = "0".repeat(64);
+        assert!(matches!(
+            config.verify_binary(path, &wrong),
+            Err(ConfigError::BinaryChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_systemd_exec_start_pre() {
+        let config = ValidatorConfig::default();
+        let service = config.generate_systemd_service("/usr/local/bin/vexidus-node", "/opt/vexidus").unwrap();
+        assert!(!service.contains("ExecStartPre"));
+
+        let digest = "2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824";
+        let config = ValidatorConfig {
+            binary_sha256: Some(format!("0x{}", digest)),
+            ..Default::default()
+        };
+        let service = config.generate_systemd_service("/usr/local/bin/vexidus-node", "/opt/vexidus").unwrap();
+        assert!(service.contains(&format!(
+            "ExecStartPre=/bin/sh -c 'echo \"{}  /usr/local/bin/vexidus-node\" | sha256sum --check --status'",
+            digest.to_lowercase()
+        )));
+        assert!(matches!(
+            config.generate_systemd_service("/opt/it's/vexidus-node", "/opt/vexidus"),
+            Err(ConfigError::UnquotableBinaryPath(_))
+        ));
+
+        // Anything but a 64-char hex digest would be spliced into the shell
+        for bad in ["abcd", "x' ; rm -rf / ; echo '", &"g".repeat(64), &"a".repeat(65)] {
+            let config = ValidatorConfig { binary_sha256: Some(bad.to_string()), ..Default::default() };
+            assert!(matches!(
+                config.generate_systemd_service("/usr/local/bin/vexidus-node", "/opt/vexidus"),
+                Err(ConfigError::InvalidChecksum(_))
+            ), "{}", bad);
+            assert!(matches!(config.validate(), Err(ConfigError::InvalidChecksum(_))), "{}", bad);
+        }
+    }
 }
@@ -8,13 +8,48 @@
 //! let quote = dex.quote_swap("VXS", "0x..usdc..", "1000000000").await?;
 //! let tx = dex.swap(&wallet, "VXS", "0x..usdc..", 1_000_000_000, 50).await?;
 //! ```
+//!
+//! ## Pool cache
+//!
+//! [`DexClient::with_quote_cache`] reuses [`get_pool`](DexClient::get_pool)
+//! and [`list_pools`](DexClient::list_pools) results for a TTL, so repeated
+//! price checks and [`quote_swap_local`](DexClient::quote_swap_local) quotes
+//! run off cached reserves. Node quotes ([`DexClient::quote_swap`]) depend on
+//! the amount and are never cached. Bundles submitted through the client
+//! clear the cache; after trading elsewhere, call
+//! [`invalidate`](DexClient::invalidate) or
+//! [`clear_cache`](DexClient::clear_cache).
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use web_time::Instant;
+use vexidus_types::intent::Goal;
+use vexidus_types::{Address, Amount, Timestamp};
 
-use crate::bundle::BundleBuilder;
+use crate::amount::AmountExt;
+use crate::bundle::{self, BundleBuilder};
+use crate::intent::ConditionalIntent;
+use crate::intent_parser::{TokenRegistry, DEFAULT_DECIMALS};
+use crate::transport::{collect_pages, split_page, value_to_u128, MockTransport, RetryPolicy, Transport};
 use crate::wallet::WalletKeypair;
+use crate::wallet_client::{decode_logs, DecodedEvent, WalletClientError};
+
+/// Input [`DexClient::pool_fee_bps`] quotes to read the fee off; large
+/// enough that the node rounding the fee down can't hide a basis point.
+const FEE_PROBE_AMOUNT: u128 = 1_000_000_000_000;
+
+/// How many times to re-quote an exact-output input the node prices short.
+const EXACT_OUT_REQUOTES: usize = 3;
+
+/// Page size [`DexClient::best_pool`] lists pools with.
+const POOL_PAGE_SIZE: u32 = 100;
+
+/// Tolerance below spot value accepted by [`DexClient::sweep_dust`] swaps.
+const SWEEP_SLIPPAGE_BPS: u16 = 100;
 
 /// Pool information returned by RPC.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,9 +65,30 @@ pub struct PoolInfo {
     pub created_at: u64,
 }
 
+impl PoolInfo {
+    /// `reserve_a` as a number; errors if the node sent something unparseable.
+    pub fn reserve_a_u128(&self) -> Result<u128> {
+        parse_amount(&self.reserve_a, "reserve_a", &self.address)
+    }
+
+    /// `reserve_b` as a number; errors if the node sent something unparseable.
+    pub fn reserve_b_u128(&self) -> Result<u128> {
+        parse_amount(&self.reserve_b, "reserve_b", &self.address)
+    }
+
+    /// `lp_total_supply` as a number; errors if the node sent something unparseable.
+    pub fn lp_total_supply_u128(&self) -> Result<u128> {
+        parse_amount(&self.lp_total_supply, "lp_total_supply", &self.address)
+    }
+}
+
 /// Swap quote returned by `quote_swap`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwapQuote {
+    /// Input the quote is for. Older nodes omit it from `vex_quoteSwap`, in
+    /// which case [`DexClient::quote_swap`] fills in the requested amount.
+    #[serde(default)]
+    pub amount_in: String,
     pub amount_out: String,
     pub price_impact_percent: String,
     pub fee: String,
@@ -41,72 +97,515 @@ pub struct SwapQuote {
     pub reserve_out: String,
 }
 
+impl SwapQuote {
+    /// Quote a swap locally with the constant-product (`x * y = k`) formula.
+    ///
+    /// The fee is taken from the input: `net = amount_in * (10000 - fee_bps)`,
+    /// `amount_out = net * reserve_out / (reserve_in * 10000 + net)`, rounded
+    /// down like the on-chain AMM. `fee` is in input-token raw units and
+    /// `price_impact_percent` is the curve's price movement excluding the fee,
+    /// `net / (reserve_in + net)`, with two decimals. `pool_address` is left
+    /// empty.
+    pub fn calculate(reserve_in: u128, reserve_out: u128, amount_in: u128, fee_bps: u16) -> SwapQuote {
+        let fee_bps = fee_bps.min(10_000) as u128;
+        let fee = amount_in.saturating_mul(fee_bps) / 10_000;
+        let amount_out = if reserve_in == 0 || reserve_out == 0 {
+            0
+        } else {
+            let net = amount_in.checked_mul(10_000 - fee_bps);
+            let num = net.and_then(|n| n.checked_mul(reserve_out));
+            let den = net.and_then(|n| reserve_in.checked_mul(10_000)?.checked_add(n));
+            match (num, den) {
+                (Some(num), Some(den)) if den > 0 => num / den,
+                // Overflows u128 only for absurd amounts — fall back to float math
+                _ => {
+                    let net = amount_in as f64 * (10_000 - fee_bps) as f64 / 10_000.0;
+                    (net * reserve_out as f64 / (reserve_in as f64 + net)).floor() as u128
+                }
+            }
+        };
+        let net_in = (amount_in - fee) as f64;
+        let impact = if net_in == 0.0 { 0.0 } else { net_in / (reserve_in as f64 + net_in) * 100.0 };
+        SwapQuote {
+            amount_in: amount_in.to_string(),
+            amount_out: amount_out.to_string(),
+            price_impact_percent: format!("{:.2}", impact),
+            fee: fee.to_string(),
+            pool_address: String::new(),
+            reserve_in: reserve_in.to_string(),
+            reserve_out: reserve_out.to_string(),
+        }
+    }
+
+    /// `amount_in` as a number; errors if it's missing or unparseable.
+    pub fn amount_in_u128(&self) -> Result<u128> {
+        parse_amount(&self.amount_in, "amount_in", &self.pool_address)
+    }
+
+    /// `amount_out` as a number; errors if it's unparseable.
+    pub fn amount_out_u128(&self) -> Result<u128> {
+        parse_amount(&self.amount_out, "amount_out", &self.pool_address)
+    }
+
+    /// `fee` as a number; errors if it's unparseable.
+    pub fn fee_u128(&self) -> Result<u128> {
+        parse_amount(&self.fee, "fee", &self.pool_address)
+    }
+
+    /// `fee` as basis points of `amount_in`, rounded to the nearest. Only
+    /// exact for inputs large enough that the fee's rounding doesn't matter
+    /// (see [`DexClient::pool_fee_bps`]).
+    pub fn fee_bps(&self) -> Result<u16> {
+        let amount_in = self.amount_in_u128()?;
+        if amount_in == 0 {
+            anyhow::bail!("Quote from pool {} has no input to take a fee from", self.pool_address);
+        }
+        let fee = self.fee_u128()?;
+        let bps = fee
+            .checked_mul(10_000)
+            .map(|n| (n + amount_in / 2) / amount_in)
+            .filter(|bps| *bps <= 10_000)
+            .ok_or_else(|| anyhow::anyhow!("Fee {} on {} is over 100%", fee, amount_in))?;
+        Ok(bps as u16)
+    }
+
+    /// Quote the smallest input that buys at least `amount_out`, via
+    /// [`amount_in_for_output`]. Rounding can make the quoted `amount_out`
+    /// exceed the request by a few units. `None` if the pool can't pay it.
+    pub fn calculate_exact_out(reserve_in: u128, reserve_out: u128, amount_out: u128, fee_bps: u16) -> Option<SwapQuote> {
+        let amount_in = amount_in_for_output(reserve_in, reserve_out, amount_out, fee_bps)?;
+        Some(Self::calculate(reserve_in, reserve_out, amount_in, fee_bps))
+    }
+}
+
+/// Quoted vs actual outcome of an executed swap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionReport {
+    pub quoted_out: u128,
+    pub actual_out: u128,
+    /// `(quoted - actual) / quoted` in basis points. Negative means the swap
+    /// paid out more than quoted (positive slippage).
+    pub slippage_realized_bps: i32,
+    /// Pool fee charged on the swap, in input-token raw units.
+    pub fee_paid: u128,
+}
+
+impl ExecutionReport {
+    /// Compare a quote against a transaction receipt's `SwapExecuted` log.
+    ///
+    /// Expects `receipt.logs` entries of the form
+    /// `{ "event": "SwapExecuted", "data": { "amount_out": .., "fee": .. } }`.
+    pub fn from_receipt(receipt: &Value, quote: &SwapQuote) -> Result<Self> {
+        Self::from_events(&decode_logs(receipt), quote)
+    }
+
+    /// Compare a quote against already-decoded logs, e.g. from
+    /// [`WalletClient::get_logs`](crate::wallet_client::WalletClient::get_logs).
+    pub fn from_events(events: &[DecodedEvent], quote: &SwapQuote) -> Result<Self> {
+        let swap = events
+            .iter()
+            .find(|event| event.kind == "SwapExecuted")
+            .ok_or_else(|| anyhow::anyhow!("No SwapExecuted event in receipt"))?;
+        let actual_out = swap
+            .amount("amount_out")
+            .ok_or_else(|| anyhow::anyhow!("SwapExecuted event missing amount_out"))?;
+        let quoted_out = quote.amount_out_u128()?;
+        let fee_paid = swap
+            .amount("fee")
+            .or_else(|| quote.fee_u128().ok())
+            .unwrap_or(0);
+
+        let slippage_realized_bps = if quoted_out == 0 {
+            0
+        } else {
+            let diff = quoted_out as i128 - actual_out as i128;
+            (diff * 10_000 / quoted_out as i128).clamp(i32::MIN as i128, i32::MAX as i128) as i32
+        };
+
+        Ok(Self { quoted_out, actual_out, slippage_realized_bps, fee_paid })
+    }
+}
+
+/// Cached pool reads; see [`DexClient::with_quote_cache`].
+#[derive(Default)]
+struct PoolCache {
+    /// Canonical pair → pool in canonical order, and when it was fetched.
+    pools: HashMap<(Address, Address), (PoolInfo, Instant)>,
+    /// `list_pools` limit → listing, and when it was fetched.
+    listings: HashMap<u32, (Vec<PoolInfo>, Instant)>,
+}
+
 /// Async client for VexiDEX pool operations.
 pub struct DexClient {
     rpc_url: String,
-    client: reqwest::Client,
+    transport: Transport,
+    cache_ttl: Duration,
+    cache: Mutex<PoolCache>,
+    registry: TokenRegistry,
 }
 
 impl DexClient {
     /// Create a new DEX client.
     pub fn new(rpc_url: &str) -> Self {
+        Self::with_transport(rpc_url, Transport::http(rpc_url))
+    }
+
+    /// Create a client backed by canned responses (see [`MockTransport`]).
+    pub fn with_mock(mock: MockTransport) -> Self {
+        Self::with_transport("mock://", Transport::mock(mock))
+    }
+
+    fn with_transport(rpc_url: &str, transport: Transport) -> Self {
         Self {
             rpc_url: rpc_url.to_string(),
-            client: reqwest::Client::new(),
+            transport,
+            cache_ttl: Duration::ZERO,
+            cache: Mutex::new(PoolCache::default()),
+            registry: TokenRegistry::default(),
         }
     }
 
-    async fn rpc_call(&self, method: &str, params: Value) -> Result<Value> {
-        let body = json!({
-            "jsonrpc": "2.0",
-            "method": method,
-            "params": params,
-            "id": 1
-        });
-        let resp = self
-            .client
-            .post(&self.rpc_url)
-            .json(&body)
-            .send()
-            .await?
-            .json::<Value>()
-            .await?;
-        if let Some(error) = resp.get("error") {
-            anyhow::bail!("RPC error: {}", error);
+    /// Look up token decimals for [`get_price`](Self::get_price) in
+    /// `registry` instead of [`TokenRegistry::default`].
+    pub fn with_registry(mut self, registry: TokenRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Reuse [`get_pool`](Self::get_pool) and [`list_pools`](Self::list_pools)
+    /// results for `ttl`. Off by default (`Duration::ZERO`). See the
+    /// [module docs](crate::dex#pool-cache).
+    pub fn with_quote_cache(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Drop the cached pool for a pair (either order) and all cached listings.
+    /// Tokens that don't parse were never cached, so they're ignored.
+    pub fn invalidate(&self, token_a: &str, token_b: &str) {
+        let mut cache = self.cache.lock().unwrap();
+        if let (Ok(a), Ok(b)) = (bundle::parse_token(token_a), bundle::parse_token(token_b)) {
+            cache.pools.remove(&canonical_pair(&a, &b));
         }
-        Ok(resp["result"].clone())
+        cache.listings.clear();
+    }
+
+    /// Drop every cached pool and listing.
+    pub fn clear_cache(&self) {
+        *self.cache.lock().unwrap() = PoolCache::default();
+    }
+
+    /// Set the per-request timeout (default [`DEFAULT_TIMEOUT`](crate::transport::DEFAULT_TIMEOUT), 30s).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.transport.set_timeout(timeout);
+        self
+    }
+
+    /// Retry idempotent reads up to `max` times on transient failures, with
+    /// exponential backoff starting at `backoff` plus jitter. Writes are never
+    /// retried.
+    pub fn with_retries(mut self, max: u32, backoff: Duration) -> Self {
+        self.transport.set_retry_policy(RetryPolicy { max_retries: max, backoff });
+        self
+    }
+
+    /// Record request params on the client's `rpc` tracing spans (`tracing`
+    /// feature). Off by default, since params can contain addresses.
+    #[cfg(feature = "tracing")]
+    pub fn with_log_params(mut self, enabled: bool) -> Self {
+        self.transport.set_log_params(enabled);
+        self
+    }
+
+    /// Effective per-request timeout.
+    pub fn timeout(&self) -> Duration {
+        self.transport.timeout()
+    }
+
+    /// Effective retry policy for reads.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.transport.retry_policy()
+    }
+
+    /// RPC endpoint this client talks to.
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// Idempotent read, retried per the client's [`RetryPolicy`].
+    async fn rpc_call(&self, method: &str, params: Value) -> Result<Value> {
+        Ok(self.transport.call_read(method, params).await?)
+    }
+
+    /// State-changing call, sent exactly once.
+    async fn rpc_write(&self, method: &str, params: Value) -> Result<Value> {
+        Ok(self.transport.call(method, params).await?)
+    }
+
+    /// Call any node RPC method directly, for methods the SDK doesn't wrap yet.
+    ///
+    /// Bypasses the typed helpers: the `result` comes back as raw JSON and no
+    /// retry policy is applied.
+    pub async fn raw_call(&self, method: &str, params: Value) -> Result<Value> {
+        self.rpc_write(method, params).await
+    }
+
+    /// Send several calls in one JSON-RPC batch; see
+    /// [`WalletClient::batch_call`](crate::WalletClient::batch_call).
+    pub async fn batch_call(
+        &self,
+        requests: &[(String, Value)],
+    ) -> Result<Vec<std::result::Result<Value, WalletClientError>>> {
+        Ok(self.transport.call_batch(requests).await?)
     }
 
     /// Get pool info by token pair.
+    ///
+    /// The pair is queried in [`canonical_pair`] order, so either argument order
+    /// finds the pool. The returned `token_a`/`reserve_a` fields follow the
+    /// caller's order.
     pub async fn get_pool(&self, token_a: &str, token_b: &str) -> Result<PoolInfo> {
-        let result = self.rpc_call("vex_getPool", json!([token_a, token_b])).await?;
-        Ok(serde_json::from_value(result)?)
+        let addr_a = bundle::parse_token(token_a)?;
+        let addr_b = bundle::parse_token(token_b)?;
+        let (first, second) = canonical_pair(&addr_a, &addr_b);
+        let cached = self.cache.lock().unwrap().pools.get(&(first, second)).cloned();
+        let mut pool = match cached {
+            Some((pool, at)) if at.elapsed() < self.cache_ttl => pool,
+            _ => {
+                let result = self
+                    .rpc_call("vex_getPool", json!([token_arg(&first), token_arg(&second)]))
+                    .await?;
+                let pool: PoolInfo = serde_json::from_value(result)?;
+                if !self.cache_ttl.is_zero() {
+                    self.cache.lock().unwrap().pools.insert((first, second), (pool.clone(), Instant::now()));
+                }
+                pool
+            }
+        };
+        if first != addr_a {
+            std::mem::swap(&mut pool.token_a, &mut pool.token_b);
+            std::mem::swap(&mut pool.reserve_a, &mut pool.reserve_b);
+        }
+        Ok(pool)
     }
 
     /// List all pools (up to limit).
     pub async fn list_pools(&self, limit: u32) -> Result<Vec<PoolInfo>> {
+        if let Some((pools, at)) = self.cache.lock().unwrap().listings.get(&limit) {
+            if at.elapsed() < self.cache_ttl {
+                return Ok(pools.clone());
+            }
+        }
         let result = self.rpc_call("vex_listPools", json!([limit])).await?;
-        Ok(serde_json::from_value(result)?)
+        let pools: Vec<PoolInfo> = serde_json::from_value(result)?;
+        if !self.cache_ttl.is_zero() {
+            self.cache.lock().unwrap().listings.insert(limit, (pools.clone(), Instant::now()));
+        }
+        Ok(pools)
+    }
+
+    /// One page of up to `limit` pools starting at `cursor`, and the cursor of
+    /// the next page. See [paging](crate::transport#paging). Never cached.
+    pub async fn list_pools_paged(&self, cursor: Option<String>, limit: u32) -> Result<(Vec<PoolInfo>, Option<String>)> {
+        let result = self.rpc_call("vex_listPools", json!([limit, cursor])).await?;
+        let (items, next) = split_page(result);
+        Ok((serde_json::from_value(items)?, next))
+    }
+
+    /// Every pool, fetched `page_size` at a time with
+    /// [`list_pools_paged`](Self::list_pools_paged).
+    pub async fn list_all_pools(&self, page_size: u32) -> Result<Vec<PoolInfo>> {
+        collect_pages(
+            |cursor| self.list_pools_paged(cursor, page_size),
+            |cursor| anyhow::anyhow!("Node repeated pool cursor {}", cursor),
+        )
+        .await
+    }
+
+    /// Of all listed pools for the pair, the one that pays out the most for
+    /// selling `amount_in` of `token_a`, by [`quote_swap_local`](Self::quote_swap_local)
+    /// at the node's [`pool_fee_bps`](Self::pool_fee_bps).
+    ///
+    /// Advisory only: the node picks the route when a swap executes, and
+    /// [`swap`](Self::swap) prices against `vex_quoteSwap`, not this. Pages
+    /// through every pool with [`list_all_pools`](Self::list_all_pools). As
+    /// with [`get_pool`](Self::get_pool), the returned `token_a`/`reserve_a`
+    /// fields follow the caller's order. Errors if no pool holds the pair.
+    pub async fn best_pool(&self, token_a: &str, token_b: &str, amount_in: u128) -> Result<PoolInfo> {
+        let addr_a = bundle::parse_token(token_a)?;
+        let addr_b = bundle::parse_token(token_b)?;
+
+        let mut candidates = Vec::new();
+        for mut pool in self.list_all_pools(POOL_PAGE_SIZE).await? {
+            let (Ok(pool_a), Ok(pool_b)) =
+                (bundle::parse_token(&pool.token_a), bundle::parse_token(&pool.token_b))
+            else {
+                continue;
+            };
+            if (pool_a, pool_b) == (addr_b, addr_a) {
+                std::mem::swap(&mut pool.token_a, &mut pool.token_b);
+                std::mem::swap(&mut pool.reserve_a, &mut pool.reserve_b);
+            } else if (pool_a, pool_b) != (addr_a, addr_b) {
+                continue;
+            }
+            candidates.push(pool);
+        }
+        if candidates.is_empty() {
+            anyhow::bail!("No pool exists for {}/{}", token_a, token_b);
+        }
+
+        let fee_bps = self.pool_fee_bps(token_a, token_b).await?;
+        let mut best: Option<(u128, PoolInfo)> = None;
+        for pool in candidates {
+            let amount_out = match self
+                .quote_swap_local(&pool, amount_in, fee_bps)
+                .and_then(|quote| quote.amount_out_u128())
+            {
+                Ok(amount_out) => amount_out,
+                Err(e) => {
+                    log::debug!("Skipping pool {}: {}", pool.address, e);
+                    continue;
+                }
+            };
+            if best.as_ref().is_none_or(|(out, _)| amount_out > *out) {
+                best = Some((amount_out, pool));
+            }
+        }
+        best.map(|(_, pool)| pool)
+            .ok_or_else(|| anyhow::anyhow!("No readable pool for {}/{}", token_a, token_b))
     }
 
     /// Get a swap quote (read-only, no transaction submitted).
     pub async fn quote_swap(&self, from_token: &str, to_token: &str, amount_in: &str) -> Result<SwapQuote> {
         let result = self.rpc_call("vex_quoteSwap", json!([from_token, to_token, amount_in])).await?;
-        Ok(serde_json::from_value(result)?)
+        let mut quote: SwapQuote = serde_json::from_value(result)?;
+        if quote.amount_in.is_empty() {
+            quote.amount_in = amount_in.to_string();
+        }
+        Ok(quote)
+    }
+
+    /// Quote buying at least `amount_out` of `to_token`: the node's
+    /// `vex_quoteSwap` for an input that covers it, starting from
+    /// [`amount_in_for_output`] against the pair's reserves. The
+    /// quoted `amount_out` can exceed the request by rounding. Errors if the
+    /// pool can't pay out `amount_out`.
+    pub async fn quote_swap_exact_out(&self, from_token: &str, to_token: &str, amount_out: u128) -> Result<SwapQuote> {
+        self.node_quote_exact_out(from_token, to_token, amount_out).await
+    }
+
+    /// The fee the node charges on `from_token` → `to_token` swaps, in basis
+    /// points, read off its `vex_quoteSwap` for a large probe input.
+    pub async fn pool_fee_bps(&self, from_token: &str, to_token: &str) -> Result<u16> {
+        self.quote_swap(from_token, to_token, &FEE_PROBE_AMOUNT.to_string()).await?.fee_bps()
+    }
+
+    /// Quote selling `amount_in` of `pool.token_a` for `pool.token_b` from the
+    /// pool's cached reserves, without a node round-trip. See
+    /// [`SwapQuote::calculate`]; take `fee_bps` from
+    /// [`pool_fee_bps`](Self::pool_fee_bps). The result is only as fresh as
+    /// `pool` — re-quote with [`quote_swap`](Self::quote_swap) before
+    /// submitting.
+    pub fn quote_swap_local(&self, pool: &PoolInfo, amount_in: u128, fee_bps: u16) -> Result<SwapQuote> {
+        let reserve_in = pool.reserve_a_u128()?;
+        let reserve_out = pool.reserve_b_u128()?;
+        let mut quote = SwapQuote::calculate(reserve_in, reserve_out, amount_in, fee_bps);
+        quote.pool_address = pool.address.clone();
+        Ok(quote)
     }
 
-    /// Get the spot price of token_a in terms of token_b.
+    /// Compare a pre-trade `quote` with what transaction `tx_hash` actually paid out.
+    pub async fn execution_report(&self, tx_hash: &str, quote: &SwapQuote) -> Result<ExecutionReport> {
+        let receipt = self.rpc_call("vex_getTransactionReceipt", json!([tx_hash])).await?;
+        if receipt.is_null() {
+            anyhow::bail!("Transaction {} not found", tx_hash);
+        }
+        ExecutionReport::from_receipt(&receipt, quote)
+    }
+
+    /// Get the spot price of token_a in terms of token_b, in whole tokens:
+    /// how many token_b one token_a buys.
+    ///
+    /// The raw reserve ratio is scaled by `10^(decimals_a - decimals_b)`, with
+    /// decimals from the client's registry (see [`with_registry`](Self::with_registry));
+    /// tokens it doesn't know are taken to have [`DEFAULT_DECIMALS`]. 0.0 for
+    /// an empty pool; errors if the node's reserves don't parse.
     pub async fn get_price(&self, token_a: &str, token_b: &str) -> Result<f64> {
+        let raw = self.raw_price(token_a, token_b).await?;
+        let decimals = |token: &str| -> Result<i32> {
+            let mint = bundle::parse_token(token)?;
+            Ok(self.registry.decimals_of(&mint).unwrap_or(DEFAULT_DECIMALS) as i32)
+        };
+        Ok(raw * 10f64.powi(decimals(token_a)? - decimals(token_b)?))
+    }
+
+    /// Raw reserve ratio of the pair: smallest units of token_b per smallest
+    /// unit of token_a.
+    async fn raw_price(&self, token_a: &str, token_b: &str) -> Result<f64> {
         let pool = self.get_pool(token_a, token_b).await?;
-        let ra: f64 = pool.reserve_a.parse().unwrap_or(0.0);
-        let rb: f64 = pool.reserve_b.parse().unwrap_or(0.0);
-        if ra == 0.0 { return Ok(0.0); }
-        Ok(rb / ra)
+        let ra = pool.reserve_a_u128()?;
+        let rb = pool.reserve_b_u128()?;
+        if ra == 0 { return Ok(0.0); }
+        Ok(rb as f64 / ra as f64)
+    }
+
+    /// Time-weighted average price of `token_a` in `token_b` over the last
+    /// `window_blocks` blocks.
+    ///
+    /// Reads the pool's price observations (`vex_getPriceHistory`, oldest
+    /// first) and weights each price by the time until the next observation,
+    /// so the newest observation closes the window. Observations price the
+    /// canonical pair's first token, so for the reversed pair each sample is
+    /// inverted before averaging (zero prices are skipped). The node's prices
+    /// aren't decimals-adjusted, so unlike [`get_price`](Self::get_price) this
+    /// is in raw units, and falls back to the spot reserve ratio, with a
+    /// warning, when the node has no history for the pair; RPC errors are
+    /// returned.
+    pub async fn twap(&self, token_a: &str, token_b: &str, window_blocks: u32) -> Result<f64> {
+        let addr_a = bundle::parse_token(token_a)?;
+        let addr_b = bundle::parse_token(token_b)?;
+        let (first, second) = canonical_pair(&addr_a, &addr_b);
+        let history = self
+            .rpc_call(
+                "vex_getPriceHistory",
+                json!([token_arg(&first), token_arg(&second), window_blocks]),
+            )
+            .await?;
+
+        let inverted = first != addr_a;
+        let observations: Vec<(u64, f64)> = history
+            .as_array()
+            .map(|entries| entries.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|o| {
+                let price = match &o["price"] {
+                    Value::String(p) => p.parse().ok(),
+                    other => other.as_f64(),
+                }?;
+                let price = if !inverted {
+                    price
+                } else if price > 0.0 {
+                    1.0 / price
+                } else {
+                    return None;
+                };
+                Some((o["timestamp"].as_u64()?, price))
+            })
+            .collect();
+        match time_weighted_average(&observations) {
+            Some(average) => Ok(average),
+            None => {
+                log::warn!("No price history for {}/{}, using spot price", token_a, token_b);
+                self.raw_price(token_a, token_b).await
+            }
+        }
     }
 
     /// Execute a swap: quote → build → sign → submit.
     ///
-    /// `slippage_bps` is in basis points (e.g., 50 = 0.5%).
+    /// `min_amount_out` is the node's [`quote_swap`](Self::quote_swap) output
+    /// less `slippage_bps` basis points (e.g., 50 = 0.5%).
     pub async fn swap(
         &self,
         wallet: &WalletKeypair,
@@ -115,18 +614,176 @@ impl DexClient {
         amount_in: u128,
         slippage_bps: u16,
     ) -> Result<Value> {
+        bundle::parse_token_pair(from_token, to_token)?;
         // Get quote to calculate min_amount_out
         let quote = self.quote_swap(from_token, to_token, &amount_in.to_string()).await?;
-        let estimated_out: u128 = quote.amount_out.parse().unwrap_or(0);
-        let min_out = estimated_out * (10_000 - slippage_bps as u128) / 10_000;
+        let estimated_out = quote.amount_out_u128()?;
+        let min_out = min_amount_out(estimated_out, slippage_bps)?;
 
         let bundle = BundleBuilder::new(&wallet.hex_address())?
             .swap(from_token, to_token, amount_in, min_out)?
-            .sign(wallet);
+            .sign(wallet)?;
 
         self.submit_bundle(&bundle).await
     }
 
+    /// Buy at least `amount_out` of `to_token`, spending no more than
+    /// `max_amount_in` of `from_token`.
+    ///
+    /// Swap operations are exact-input, so this sells the input the node
+    /// quotes for `amount_out` (see
+    /// [`quote_swap_exact_out`](Self::quote_swap_exact_out)) with
+    /// `min_amount_out` set to `amount_out`, and errors without submitting if
+    /// that input is over `max_amount_in`. If the pool moves against the
+    /// quote before the bundle lands, the swap reverts; re-quote and retry.
+    pub async fn swap_exact_out(
+        &self,
+        wallet: &WalletKeypair,
+        from_token: &str,
+        to_token: &str,
+        amount_out: u128,
+        max_amount_in: u128,
+    ) -> Result<Value> {
+        let quote = self.quote_swap_exact_out(from_token, to_token, amount_out).await?;
+        let amount_in = quote.amount_in_u128()?;
+        if amount_in > max_amount_in {
+            anyhow::bail!(
+                "Buying {} {} takes {} {}, over the {} limit",
+                amount_out, to_token, amount_in, from_token, max_amount_in
+            );
+        }
+
+        let bundle = BundleBuilder::new(&wallet.hex_address())?
+            .swap(from_token, to_token, amount_in, amount_out)?
+            .sign(wallet)?;
+
+        self.submit_bundle(&bundle).await
+    }
+
+    /// Swap enough `from_token` to bring the wallet's `to_token` balance up to
+    /// at least `target_balance` (raw units).
+    ///
+    /// Reads the current balance (`vex_getBalance`) and buys the shortfall
+    /// grossed up by `slippage_bps`, priced by the node's `vex_quoteSwap`.
+    /// `min_amount_out` is that quote less `slippage_bps`, which is never below
+    /// the shortfall: the pool can move by up to the allowance and the balance
+    /// still reaches the target, and past it the swap reverts. The price of the
+    /// margin is ending up to `slippage_bps` over the target.
+    ///
+    /// Returns `{ "submission", "amount_in", "amount_out", "min_amount_out" }`,
+    /// with `amount_out` the node's quote; at or above the target nothing is
+    /// submitted and `submission` is null.
+    pub async fn swap_to_target_balance(
+        &self,
+        wallet: &WalletKeypair,
+        from_token: &str,
+        to_token: &str,
+        target_balance: u128,
+        slippage_bps: u16,
+    ) -> Result<Value> {
+        if slippage_bps >= 10_000 {
+            anyhow::bail!("Slippage of {} bps leaves nothing to aim for", slippage_bps);
+        }
+        let balance = self
+            .rpc_call("vex_getBalance", json!([wallet.hex_address(), to_token]))
+            .await?;
+        let balance = value_to_u128(&balance)
+            .ok_or_else(|| anyhow::anyhow!("Unreadable {} balance: {}", to_token, balance))?;
+        let shortfall = target_balance.saturating_sub(balance);
+        if shortfall == 0 {
+            return Ok(json!({ "submission": null, "amount_in": "0", "amount_out": "0", "min_amount_out": "0" }));
+        }
+
+        // ceil(shortfall / (1 - slippage)), so the quote less slippage still covers it
+        let aim = shortfall
+            .checked_mul(10_000)
+            .map(|n| n.div_ceil(10_000 - slippage_bps as u128))
+            .ok_or_else(|| anyhow::anyhow!("Shortfall {} overflows with slippage", shortfall))?;
+        let quote = self.node_quote_exact_out(from_token, to_token, aim).await?;
+        let amount_in = quote.amount_in_u128()?;
+        let min_out = min_amount_out(quote.amount_out_u128()?, slippage_bps)?;
+
+        let bundle = BundleBuilder::new(&wallet.hex_address())?
+            .swap(from_token, to_token, amount_in, min_out)?
+            .sign(wallet)?;
+        let submission = self.submit_bundle(&bundle).await?;
+        Ok(json!({
+            "submission": submission,
+            "amount_in": amount_in.to_string(),
+            "amount_out": quote.amount_out,
+            "min_amount_out": min_out.to_string(),
+        }))
+    }
+
+    /// The node's [`quote_swap`](Self::quote_swap) for an input that buys at
+    /// least `amount_out`. The input starts from the fee-free
+    /// [`amount_in_for_output`] against the pool's reserves; when the node
+    /// quotes it short (its fee, or the pool moving), it's scaled up by the
+    /// gap and re-quoted, up to [`EXACT_OUT_REQUOTES`] times.
+    async fn node_quote_exact_out(&self, from_token: &str, to_token: &str, amount_out: u128) -> Result<SwapQuote> {
+        let pool = self.get_pool(from_token, to_token).await?;
+        let mut amount_in = amount_in_for_output(pool.reserve_a_u128()?, pool.reserve_b_u128()?, amount_out, 0)
+            .ok_or_else(|| anyhow::anyhow!("Pool {} can't pay out {} {}", pool.address, amount_out, to_token))?;
+        for _ in 0..EXACT_OUT_REQUOTES {
+            let quote = self.quote_swap(from_token, to_token, &amount_in.to_string()).await?;
+            let quoted_out = quote.amount_out_u128()?;
+            if quoted_out >= amount_out {
+                return Ok(quote);
+            }
+            if quoted_out == 0 {
+                break;
+            }
+            amount_in = amount_in
+                .checked_mul(amount_out)
+                .map(|n| n.div_ceil(quoted_out).max(amount_in + 1))
+                .ok_or_else(|| anyhow::anyhow!("Input for {} {} overflows", amount_out, to_token))?;
+        }
+        anyhow::bail!("Node won't quote {} {} for {} {}", amount_out, to_token, amount_in, from_token)
+    }
+
+    /// Keeper loop for a [`ConditionalIntent`]: poll the trigger price every
+    /// `poll_interval` and submit the swap once it fires.
+    ///
+    /// The trigger is compared with [`get_price`](Self::get_price), so it's in
+    /// whole tokens and the client's registry must know both tokens' decimals.
+    /// Runs until the trigger fires or the intent's deadline passes, so the
+    /// calling process must stay alive — nothing on-chain watches the price.
+    /// Slippage comes from the intent's `max_slippage` (default 0.5%).
+    pub async fn poll_and_execute_conditional(
+        &self,
+        wallet: &WalletKeypair,
+        intent: &ConditionalIntent,
+        poll_interval: Duration,
+    ) -> Result<Value> {
+        let (from_token, to_token, amount) = match &intent.goal {
+            Goal::Swap { from_token, to_token, amount } => {
+                (token_arg(from_token), token_arg(to_token), amount.0)
+            }
+            _ => anyhow::bail!("Conditional execution only supports swap goals"),
+        };
+        let base = token_arg(&intent.trigger.base_token);
+        let quote = token_arg(&intent.trigger.quote_token);
+        let slippage_bps = intent
+            .constraints
+            .max_slippage
+            .map(|pct| pct as u16 * 100)
+            .unwrap_or(50);
+
+        loop {
+            if let Some(deadline) = intent.constraints.deadline {
+                if Timestamp::now().0 > deadline.0 {
+                    anyhow::bail!("Conditional intent expired before trigger fired");
+                }
+            }
+            let price = self.get_price(&base, &quote).await?;
+            if intent.trigger.is_triggered(price) {
+                log::info!("Trigger fired at price {} — submitting swap", price);
+                return self.swap(wallet, &from_token, &to_token, amount, slippage_bps).await;
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
     /// Create a new liquidity pool.
     pub async fn create_pool(
         &self,
@@ -137,14 +794,32 @@ impl DexClient {
         amount_b: u128,
         lp_lock_duration: u64,
     ) -> Result<Value> {
+        let addr_a = bundle::parse_token(token_a)?;
+        let addr_b = bundle::parse_token(token_b)?;
+        let (first, second) = canonical_pair(&addr_a, &addr_b);
+        let (amount_first, amount_second) = if first == addr_a {
+            (amount_a, amount_b)
+        } else {
+            (amount_b, amount_a)
+        };
         let bundle = BundleBuilder::new(&wallet.hex_address())?
-            .create_pool(token_a, token_b, amount_a, amount_b, lp_lock_duration)?
-            .sign(wallet);
+            .create_pool(
+                &token_arg(&first),
+                &token_arg(&second),
+                amount_first,
+                amount_second,
+                lp_lock_duration,
+            )?
+            .sign(wallet)?;
 
         self.submit_bundle(&bundle).await
     }
 
     /// Add liquidity to an existing pool.
+    ///
+    /// Fetches the pool and sets `min_lp_tokens` to the LP expected at current
+    /// reserves less `slippage_bps` (see [`min_lp_tokens`]), so the deposit
+    /// reverts if the pool moves against it first.
     pub async fn add_liquidity(
         &self,
         wallet: &WalletKeypair,
@@ -154,18 +829,21 @@ impl DexClient {
         amount_b: u128,
         slippage_bps: u16,
     ) -> Result<Value> {
-        // min_lp_tokens = 0 for simplicity (could be calculated from pool state)
-        let min_lp = 0u128;
-        let _ = slippage_bps; // Reserved for future precision
+        let pool = self.get_pool(token_a, token_b).await?;
+        let min_lp = min_lp_tokens(&pool, amount_a, amount_b, slippage_bps)?;
 
         let bundle = BundleBuilder::new(&wallet.hex_address())?
             .add_liquidity(token_a, token_b, amount_a, amount_b, min_lp)?
-            .sign(wallet);
+            .sign(wallet)?;
 
         self.submit_bundle(&bundle).await
     }
 
     /// Remove liquidity from a pool.
+    ///
+    /// Fetches the pool and sets `min_amount_a`/`min_amount_b` to the
+    /// proportional payout at current reserves less `slippage_bps` (see
+    /// [`min_withdrawal`]).
     pub async fn remove_liquidity(
         &self,
         wallet: &WalletKeypair,
@@ -174,27 +852,919 @@ impl DexClient {
         lp_amount: u128,
         slippage_bps: u16,
     ) -> Result<Value> {
-        let _ = slippage_bps;
+        let pool = self.get_pool(token_a, token_b).await?;
+        let (min_a, min_b) = min_withdrawal(&pool, lp_amount, slippage_bps)?;
         let bundle = BundleBuilder::new(&wallet.hex_address())?
-            .remove_liquidity(token_a, token_b, lp_amount, 0, 0)?
-            .sign(wallet);
+            .remove_liquidity(token_a, token_b, lp_amount, min_a, min_b)?
+            .sign(wallet)?;
 
         self.submit_bundle(&bundle).await
     }
 
+    /// Remove enough liquidity to withdraw roughly `target_a` of token A.
+    ///
+    /// Looks up the pool reserves and converts the target into an LP amount via
+    /// [`lp_for_withdrawal`]. Token B is returned proportionally alongside.
+    pub async fn remove_liquidity_by_amount(
+        &self,
+        wallet: &WalletKeypair,
+        token_a: &str,
+        token_b: &str,
+        target_a: u128,
+        slippage_bps: u16,
+    ) -> Result<Value> {
+        let pool = self.get_pool(token_a, token_b).await?;
+        let reserve_a = pool.reserve_a_u128()?;
+        let lp_supply = pool.lp_total_supply_u128()?;
+        let lp_amount = lp_for_withdrawal(target_a, reserve_a, lp_supply);
+        if lp_amount == 0 {
+            anyhow::bail!("Pool has no liquidity to withdraw");
+        }
+        self.remove_liquidity(wallet, token_a, token_b, lp_amount, slippage_bps).await
+    }
+
+    /// Swap every dust balance into `target_token` in one bundle.
+    ///
+    /// Lists the wallet's token balances (`vex_getTokenBalances`), values each
+    /// non-zero balance in `target_token` at the pool's spot price, and swaps
+    /// those worth less than `min_value` (raw target units). Tokens without a
+    /// pool against the target are skipped; a pool whose reserves don't parse
+    /// is an error. Each swap accepts up to 1% below spot to cover the pool
+    /// fee.
+    ///
+    /// Returns `{ "submission", "swept": [{token, amount, value}],
+    /// "skipped": [{token, reason}] }`; `submission` is null when nothing
+    /// qualified.
+    pub async fn sweep_dust(
+        &self,
+        wallet: &WalletKeypair,
+        target_token: &str,
+        min_value: u128,
+    ) -> Result<Value> {
+        let target = bundle::parse_token(target_token)?;
+        let target_arg = token_arg(&target);
+        let balances = self
+            .rpc_call("vex_getTokenBalances", json!([wallet.hex_address()]))
+            .await?;
+
+        let mut builder = BundleBuilder::new(&wallet.hex_address())?;
+        let mut swept = Vec::new();
+        let mut skipped = Vec::new();
+        for entry in balances.as_array().cloned().unwrap_or_default() {
+            let Some(token) = entry["token"].as_str().and_then(|t| bundle::parse_token(t).ok())
+            else {
+                continue;
+            };
+            let amount = value_to_u128(&entry["balance"]).unwrap_or(0);
+            if token == target || amount == 0 {
+                continue;
+            }
+            let token_str = token_arg(&token);
+            let pool = match self.get_pool(&token_str, &target_arg).await {
+                Ok(pool) => pool,
+                Err(_) => {
+                    skipped.push(json!({ "token": token_str, "reason": "no pool" }));
+                    continue;
+                }
+            };
+            let reserve_in = pool.reserve_a_u128()?;
+            let reserve_out = pool.reserve_b_u128()?;
+            if reserve_in == 0 || reserve_out == 0 {
+                skipped.push(json!({ "token": token_str, "reason": "empty pool" }));
+                continue;
+            }
+            let value = amount.saturating_mul(reserve_out) / reserve_in;
+            if value >= min_value {
+                continue;
+            }
+            let min_out = min_amount_out(value, SWEEP_SLIPPAGE_BPS)?;
+            builder = builder.swap(&token_str, &target_arg, amount, min_out)?;
+            swept.push(json!({ "token": token_str, "amount": amount.to_string(), "value": value.to_string() }));
+        }
+
+        let submission = if swept.is_empty() {
+            Value::Null
+        } else {
+            let gas = builder.estimate_gas(None);
+            let bundle = builder.max_gas(gas).sign(wallet)?;
+            self.submit_bundle(&bundle).await?
+        };
+        Ok(json!({ "submission": submission, "swept": swept, "skipped": skipped }))
+    }
+
     async fn submit_bundle(&self, bundle: &vexidus_types::TransactionBundle) -> Result<Value> {
         let bundle_hex = hex::encode(borsh::to_vec(bundle)?);
-        self.rpc_call("vex_submitBundle", json!([bundle_hex])).await
+        // Whatever the bundle did, cached reserves may no longer hold
+        self.clear_cache();
+        self.rpc_write("vex_submitBundle", json!([bundle_hex])).await
     }
 }
 
+/// Average of `(timestamp, price)` observations, each weighted by the time
+/// until the next. `None` without observations; a window with no elapsed
+/// time averages its prices evenly.
+fn time_weighted_average(observations: &[(u64, f64)]) -> Option<f64> {
+    let ((start, _), (end, _)) = (observations.first()?, observations.last()?);
+    let elapsed = end.saturating_sub(*start);
+    if elapsed == 0 {
+        let sum: f64 = observations.iter().map(|(_, p)| p).sum();
+        return Some(sum / observations.len() as f64);
+    }
+    let weighted: f64 = observations
+        .windows(2)
+        .map(|w| w[0].1 * w[1].0.saturating_sub(w[0].0) as f64)
+        .sum();
+    Some(weighted / elapsed as f64)
+}
+
+/// Order a token pair the way the chain keys pools: ascending by raw address
+/// bytes (lexicographic over all 32 bytes). Native VXS (`Address::ZERO`)
+/// therefore always sorts first.
+pub fn canonical_pair(a: &Address, b: &Address) -> (Address, Address) {
+    if a.0 <= b.0 {
+        (*a, *b)
+    } else {
+        (*b, *a)
+    }
+}
+
+/// Parse one of the string amount fields of [`PoolInfo`] / [`SwapQuote`].
+fn parse_amount(value: &str, field: &str, pool: &str) -> Result<u128> {
+    value
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Malformed {} {:?} for pool {}: {}", field, value, pool, e))
+}
+
+/// Render a token address as an RPC argument ("VXS" for the native token).
+fn token_arg(token: &Address) -> String {
+    if *token == Address::ZERO {
+        "VXS".to_string()
+    } else {
+        format!("0x{}", hex::encode(token.0))
+    }
+}
+
+/// Minimum LP tokens to accept for depositing `amount_a`/`amount_b` into `pool`.
+///
+/// Expected LP is `min(amount_a * supply / reserve_a, amount_b * supply /
+/// reserve_b)` — the pool mints against the scarcer side — reduced by
+/// `slippage_bps`. Returns 0 (no protection) for an empty pool, where the
+/// first deposit sets the price. Errors on unreadable reserves, slippage
+/// over 100%, or a product that overflows u128.
+pub fn min_lp_tokens(pool: &PoolInfo, amount_a: u128, amount_b: u128, slippage_bps: u16) -> Result<u128> {
+    let reserve_a = pool.reserve_a_u128()?;
+    let reserve_b = pool.reserve_b_u128()?;
+    let lp_supply = pool.lp_total_supply_u128()?;
+    if reserve_a == 0 || reserve_b == 0 || lp_supply == 0 {
+        return Ok(0);
+    }
+    let expected = proportion(amount_a, lp_supply, reserve_a)?.min(proportion(amount_b, lp_supply, reserve_b)?);
+    min_amount_out(expected, slippage_bps)
+}
+
+/// Minimum `(token_a, token_b)` to accept for burning `lp_amount` of `pool`'s
+/// LP tokens: the proportional share of each reserve, reduced by
+/// `slippage_bps`. Errors like [`min_lp_tokens`].
+pub fn min_withdrawal(pool: &PoolInfo, lp_amount: u128, slippage_bps: u16) -> Result<(u128, u128)> {
+    let reserve_a = pool.reserve_a_u128()?;
+    let reserve_b = pool.reserve_b_u128()?;
+    let lp_supply = pool.lp_total_supply_u128()?;
+    if lp_supply == 0 {
+        return Ok((0, 0));
+    }
+    let lp_amount = lp_amount.min(lp_supply);
+    Ok((
+        min_amount_out(proportion(lp_amount, reserve_a, lp_supply)?, slippage_bps)?,
+        min_amount_out(proportion(lp_amount, reserve_b, lp_supply)?, slippage_bps)?,
+    ))
+}
+
+/// `amount * numerator / denominator`, rounded down; errors if the product
+/// overflows u128.
+fn proportion(amount: u128, numerator: u128, denominator: u128) -> Result<u128> {
+    amount
+        .checked_mul(numerator)
+        .map(|product| product / denominator)
+        .ok_or_else(|| anyhow::anyhow!("{} * {} overflows", amount, numerator))
+}
+
+/// `estimated_out` less `slippage_bps` basis points; errors if the slippage
+/// is over 100%.
+fn min_amount_out(estimated_out: u128, slippage_bps: u16) -> Result<u128> {
+    let keep_bps = 10_000u16
+        .checked_sub(slippage_bps)
+        .ok_or_else(|| anyhow::anyhow!("Slippage of {} bps exceeds 100%", slippage_bps))?;
+    let min_out = Amount(estimated_out)
+        .checked_mul_bps(keep_bps)
+        .expect("at most 100% of a u128 fits in a u128");
+    Ok(min_out.0)
+}
+
+/// LP tokens to burn so that roughly `target_token_a` of token A is withdrawn.
+///
+/// A withdrawal pays out `lp * reserve / lp_supply` of each token, so this is the
+/// inverse rounded up — the result withdraws at least the target (never more than
+/// one LP unit's worth over). Token B comes out proportionally and is not
+/// controlled here. Targets above the reserve are capped at the full LP supply.
+/// Returns 0 for an empty pool.
+pub fn lp_for_withdrawal(target_token_a: u128, reserve_a: u128, lp_supply: u128) -> u128 {
+    if reserve_a == 0 || lp_supply == 0 {
+        return 0;
+    }
+    if target_token_a >= reserve_a {
+        return lp_supply;
+    }
+    match target_token_a.checked_mul(lp_supply) {
+        Some(num) => num.div_ceil(reserve_a),
+        // Product overflows u128 only for absurd pools — fall back to float math
+        None => ((target_token_a as f64 * lp_supply as f64 / reserve_a as f64).ceil() as u128)
+            .min(lp_supply),
+    }
+}
+
+/// Smallest input that buys at least `amount_out` from a constant-product
+/// pool: the inverse of [`SwapQuote::calculate`].
+///
+/// `None` if the pool is empty, can't pay `amount_out` (it must stay below
+/// `reserve_out`), or the fee is 100%.
+pub fn amount_in_for_output(reserve_in: u128, reserve_out: u128, amount_out: u128, fee_bps: u16) -> Option<u128> {
+    if reserve_in == 0 || amount_out >= reserve_out || fee_bps >= 10_000 {
+        return None;
+    }
+    if amount_out == 0 {
+        return Some(0);
+    }
+    // out = net * reserve_out / (reserve_in * 10000 + net), net = in * (10000 - fee)
+    let net = amount_out
+        .checked_mul(reserve_in)?
+        .checked_mul(10_000)?
+        .div_ceil(reserve_out - amount_out);
+    let mut amount_in = net.div_ceil(10_000 - fee_bps as u128);
+    // Rounding in the forward formula can leave the estimate a unit short
+    let quoted_out = |amount_in| SwapQuote::calculate(reserve_in, reserve_out, amount_in, fee_bps).amount_out.parse::<u128>();
+    while quoted_out(amount_in).ok()? < amount_out {
+        amount_in = amount_in.checked_add(1)?;
+    }
+    Some(amount_in)
+}
+
+/// Input needed to push a constant-product pool's spot price down by
+/// `target_impact_bps` (e.g. 500 = "how much must be sold to drop the price 5%").
+///
+/// Selling `x` (after fees) moves the spot price `reserve_out / reserve_in` by a
+/// factor of `(reserve_in / (reserve_in + x))^2`, so
+/// `x = reserve_in * (1 / sqrt(1 - impact) - 1)`, grossed up by the fee and
+/// rounded up. A 100% target (draining the pool) or a 100% fee returns
+/// `u128::MAX`; an empty pool or a zero target returns 0.
+pub fn amount_to_move_price(
+    reserve_in: u128,
+    reserve_out: u128,
+    target_impact_bps: u16,
+    fee_bps: u16,
+) -> u128 {
+    if reserve_in == 0 || reserve_out == 0 || target_impact_bps == 0 {
+        return 0;
+    }
+    if target_impact_bps >= 10_000 || fee_bps >= 10_000 {
+        return u128::MAX;
+    }
+    let impact = target_impact_bps as f64 / 10_000.0;
+    let net_in = reserve_in as f64 * (1.0 / (1.0 - impact).sqrt() - 1.0);
+    let gross_in = net_in / (1.0 - fee_bps as f64 / 10_000.0);
+    // `as` saturates, so absurd results clamp to u128::MAX
+    gross_in.ceil() as u128
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const FEE_BPS: u16 = 30;
+
     #[test]
     fn test_dex_client_creation() {
         let client = DexClient::new("http://localhost:9933");
         assert_eq!(client.rpc_url, "http://localhost:9933");
     }
+
+    fn quote(amount_out: &str) -> SwapQuote {
+        SwapQuote {
+            amount_in: "1000000".into(),
+            amount_out: amount_out.into(),
+            price_impact_percent: "0.5".into(),
+            fee: "3000".into(),
+            pool_address: "0xpool".into(),
+            reserve_in: "1000000000".into(),
+            reserve_out: "2000000000".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execution_report() {
+        let receipt = json!({
+            "status": "success",
+            "logs": [
+                { "event": "Transfer", "data": {} },
+                { "event": "SwapExecuted", "data": { "amount_in": "1000000", "amount_out": "990000", "fee": "3000" } }
+            ]
+        });
+        let mock = MockTransport::new().with_result("vex_getTransactionReceipt", receipt);
+        let dex = DexClient::with_mock(mock);
+
+        let report = dex.execution_report("0xabc", &quote("1000000")).await.unwrap();
+        assert_eq!(report.quoted_out, 1_000_000);
+        assert_eq!(report.actual_out, 990_000);
+        assert_eq!(report.slippage_realized_bps, 100); // 1% worse than quoted
+        assert_eq!(report.fee_paid, 3_000);
+
+        // Better than quoted → negative slippage
+        let receipt = json!({ "logs": [{ "event": "SwapExecuted", "data": { "amount_out": 1_010_000 } }] });
+        let report = ExecutionReport::from_receipt(&receipt, &quote("1000000")).unwrap();
+        assert_eq!(report.slippage_realized_bps, -100);
+        assert_eq!(report.fee_paid, 3_000); // falls back to quoted fee
+
+        assert!(ExecutionReport::from_receipt(&json!({ "logs": [] }), &quote("1")).is_err());
+    }
+
+    #[test]
+    fn test_canonical_pair() {
+        let x = Address([0xab; 32]);
+        let y = Address([0x01; 32]);
+        assert_eq!(canonical_pair(&x, &y), canonical_pair(&y, &x));
+        assert_eq!(canonical_pair(&x, &y), (y, x));
+
+        // Bridged mints, hashed and sorted outside the SDK (blake3 of
+        // "<chain>_<contract>", ordered by hex string): WBTC < SOL < USDC < ETH < USDT
+        let mint = |symbol: &str, hex: &str| {
+            let mint = crate::address_utils::bridged_mint_from_symbol(symbol).unwrap();
+            assert_eq!(hex::encode(mint.0), hex, "{}", symbol);
+            mint
+        };
+        let wbtc = mint("WBTC", "09101ad2840f514e5674342bae4ac7dec60368e5d10b17b216bdeac428299fb9");
+        let sol = mint("SOL", "4bc15c8f077e59284ae68faaaa9f86d2979322d53ab33d9385f7c594ac93bdd4");
+        let usdc = mint("USDC", "579b84767a8dfd4aabe7b1e25f1294a1ae08bd255a85115e2a9d3d2f7a3e9c72");
+        let eth = mint("ETH", "7b753769718deeaf29d1bd49c8a3c80c4531cdda161eb09f669d2cc5fe0668d5");
+        let usdt = mint("USDT", "96b6a6960a0db915d815320103726f2c3bcecfc11ad1b49f95f2da0924c3a98f");
+        assert_eq!(canonical_pair(&usdt, &usdc), (usdc, usdt));
+        assert_eq!(canonical_pair(&usdc, &sol), (sol, usdc));
+        assert_eq!(canonical_pair(&eth, &wbtc), (wbtc, eth));
+        assert_eq!(canonical_pair(&usdt, &eth), (eth, usdt));
+        assert_eq!(canonical_pair(&usdc, &Address::ZERO), (Address::ZERO, usdc));
+
+        // Only the first differing byte decides
+        let mut lo = [0xff; 32];
+        lo[0] = 0x00;
+        let mut hi = [0x00; 32];
+        hi[0] = 0x01;
+        assert_eq!(canonical_pair(&Address(hi), &Address(lo)), (Address(lo), Address(hi)));
+    }
+
+    #[test]
+    fn test_lp_for_withdrawal() {
+        let reserve_a = 1_000_000_000_000u128; // 1000 VXS
+        let lp_supply = 316_227_766_016u128;
+        let target = 500_000_000_000u128; // 500 VXS
+
+        let lp = lp_for_withdrawal(target, reserve_a, lp_supply);
+        let withdrawn = lp * reserve_a / lp_supply;
+        assert!(withdrawn >= target);
+        // At most one LP unit's worth of token A over the target
+        assert!(withdrawn - target <= reserve_a / lp_supply + 1);
+
+        assert_eq!(lp_for_withdrawal(target, 0, lp_supply), 0);
+        assert_eq!(lp_for_withdrawal(reserve_a * 2, reserve_a, lp_supply), lp_supply);
+    }
+
+    #[test]
+    fn test_amount_to_move_price() {
+        // 1000 VXS / 2000 USDC pool, 0.3% fee. By hand:
+        // 1000e9 * (1/sqrt(0.95) - 1) = 25_978_352_085.2, / 0.997 = 26_056_521_650.1
+        let reserve_in = 1_000_000_000_000u128;
+        let reserve_out = 2_000_000_000_000u128;
+        let amount = amount_to_move_price(reserve_in, reserve_out, 500, 30);
+        assert!(amount.abs_diff(26_056_521_651) <= 1, "got {}", amount);
+
+        // Selling that amount really does move the spot price by ~5%
+        let net = amount * 9_970 / 10_000;
+        let out = reserve_out * net / (reserve_in + net);
+        let before = reserve_out as f64 / reserve_in as f64;
+        let after = (reserve_out - out) as f64 / (reserve_in + net) as f64;
+        assert!(((1.0 - after / before) - 0.05).abs() < 1e-6);
+
+        assert_eq!(amount_to_move_price(reserve_in, reserve_out, 10_000, 30), u128::MAX);
+        assert_eq!(amount_to_move_price(0, reserve_out, 500, 30), 0);
+        assert_eq!(amount_to_move_price(reserve_in, reserve_out, 0, 30), 0);
+    }
+
+    #[test]
+    fn test_swap_quote_calculate() {
+        // Matches the Uniswap v2 getAmountOut reference values
+        let quote = SwapQuote::calculate(1_000_000, 1_000_000, 1_000, 30);
+        assert_eq!(quote.amount_out, "996");
+        assert_eq!(quote.fee, "3");
+        assert_eq!(quote.price_impact_percent, "0.10");
+
+        // 100 VXS into a 1000 VXS / 2000 USDC pool
+        let quote = SwapQuote::calculate(1_000_000_000_000, 2_000_000_000, 100_000_000_000, 30);
+        assert_eq!(quote.amount_out, "181322178");
+        assert_eq!(quote.fee, "300000000");
+        assert_eq!(quote.price_impact_percent, "9.07");
+
+        // No fee, trade equal to the reserve: half the output side, 50% impact
+        let quote = SwapQuote::calculate(5_000, 10_000, 5_000, 0);
+        assert_eq!(quote.amount_out, "5000");
+        assert_eq!(quote.price_impact_percent, "50.00");
+
+        assert_eq!(SwapQuote::calculate(0, 10_000, 5_000, 30).amount_out, "0");
+        // Overflowing intermediates still produce a sane answer
+        let huge = SwapQuote::calculate(u128::MAX / 2, u128::MAX / 2, u128::MAX / 2, 30);
+        assert!(huge.amount_out.parse::<u128>().unwrap() < u128::MAX / 2);
+    }
+
+    #[test]
+    fn test_quote_swap_local() {
+        let client = DexClient::new("http://localhost:9933");
+        let pool: PoolInfo = serde_json::from_value(json!({
+            "address": "0xpool", "token_a": "VXS", "token_b": "0xusdc",
+            "reserve_a": "1000000", "reserve_b": "1000000", "lp_total_supply": "1000000",
+            "lp_locked": false, "creator": "0x01", "created_at": 0
+        }))
+        .unwrap();
+        let quote = client.quote_swap_local(&pool, 1_000, 30).unwrap();
+        assert_eq!(quote.amount_out, "996");
+        assert_eq!(quote.pool_address, "0xpool");
+
+        // The fee reads back off a large enough quote, not off a small one
+        assert_eq!(SwapQuote::calculate(1_000, 1_000, FEE_PROBE_AMOUNT, 30).fee_bps().unwrap(), 30);
+        assert_eq!(SwapQuote::calculate(1_000, 1_000, 300, 30).fee_bps().unwrap(), 0);
+        assert!(SwapQuote::calculate(1_000, 1_000, 0, 30).fee_bps().is_err());
+    }
+
+    fn pool(token_a: &Address, token_b: &Address, reserve_a: u128, reserve_b: u128) -> Value {
+        json!({
+            "address": "0xpool",
+            "token_a": token_arg(token_a),
+            "token_b": token_arg(token_b),
+            "reserve_a": reserve_a.to_string(),
+            "reserve_b": reserve_b.to_string(),
+            "lp_total_supply": "1000",
+            "lp_locked": false,
+            "creator": "0x00",
+            "created_at": 0
+        })
+    }
+
+    /// The first bundle `mock` received on `vex_submitBundle`.
+    fn submitted_bundle(mock: &MockTransport) -> vexidus_types::TransactionBundle {
+        let (_, params) = mock.calls().into_iter().find(|(m, _)| m == "vex_submitBundle").unwrap();
+        let bytes = hex::decode(params[0].as_str().unwrap()).unwrap();
+        borsh::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_best_pool_prefers_depth_for_large_trades() {
+        let usdc = Address([5u8; 32]);
+        let mut shallow = pool(&Address::ZERO, &usdc, 10_000, 40_000);
+        shallow["address"] = json!("0xshallow");
+        // Listed in reverse order; best_pool reorients it to the caller's pair
+        let mut deep = pool(&usdc, &Address::ZERO, 3_900_000, 1_000_000);
+        deep["address"] = json!("0xdeep");
+        // Would be the deepest, but its reserves don't parse
+        let mut unreadable = pool(&Address::ZERO, &usdc, 1_000_000_000, 4_000_000_000);
+        unreadable["address"] = json!("0xunreadable");
+        unreadable["reserve_b"] = json!("4e9");
+        let other = pool(&Address::ZERO, &Address([6u8; 32]), 1, 1);
+
+        // Two paged listings, then an unpaged node answering with the whole list
+        let mock = MockTransport::new()
+            .with_result("vex_listPools", json!({ "items": [shallow, other], "next_cursor": "c1" }))
+            .with_result("vex_listPools", json!({ "items": [deep, unreadable], "next_cursor": null }))
+            .with_result("vex_listPools", json!({ "items": [shallow, other], "next_cursor": "c1" }))
+            .with_result("vex_listPools", json!({ "items": [deep, unreadable], "next_cursor": null }))
+            .with_result("vex_listPools", json!([shallow, deep, other]))
+            // Fee probe, one per best_pool call: 0.3%
+            .with_result("vex_quoteSwap", fee_probe(30));
+        let client = DexClient::with_mock(mock.clone());
+        let usdc_arg = token_arg(&usdc);
+
+        // A small trade does better in the shallow pool's slightly better price
+        let best = client.best_pool("VXS", &usdc_arg, 10).await.unwrap();
+        assert_eq!(best.address, "0xshallow");
+        let best = client.best_pool("VXS", &usdc_arg, 5_000).await.unwrap();
+        assert_eq!(best.address, "0xdeep");
+        assert_eq!(best.reserve_a, "1000000");
+        let probes: Vec<_> = mock.calls().into_iter().filter(|(m, _)| m == "vex_quoteSwap").collect();
+        assert_eq!(probes[0].1[2], FEE_PROBE_AMOUNT.to_string());
+
+        // No candidate pool: no fee probe either
+        let quotes = mock.call_count("vex_quoteSwap");
+        let err = client.best_pool("VXS", &token_arg(&Address([7u8; 32])), 10).await.unwrap_err();
+        assert!(err.to_string().contains("No pool exists"));
+        assert_eq!(mock.call_count("vex_quoteSwap"), quotes);
+    }
+
+    #[tokio::test]
+    async fn test_swap_min_amount_out_from_node_quote() {
+        let usdc = Address([5u8; 32]);
+        let mock = MockTransport::new()
+            .with_result("vex_quoteSwap", node_quote(5_000, 1_200))
+            .with_result("vex_submitBundle", json!("0xabc"));
+        let client = DexClient::with_mock(mock.clone());
+        let usdc_arg = token_arg(&usdc);
+
+        client.swap(&WalletKeypair::generate(), "VXS", &usdc_arg, 5_000, 50).await.unwrap();
+        // The node's quoted 1200, less 0.5%
+        match &submitted_bundle(&mock).operations[0] {
+            vexidus_types::Operation::Swap { amount_in, min_amount_out, .. } => {
+                assert_eq!((*amount_in, *min_amount_out), (5_000, 1_194));
+            }
+            other => panic!("Expected Swap, got {:?}", other),
+        }
+        assert_eq!(mock.calls()[0].1, json!(["VXS", usdc_arg, "5000"]));
+    }
+
+    #[tokio::test]
+    async fn test_swap_rejects_slippage_over_100_percent() {
+        let mock = MockTransport::new().with_result("vex_quoteSwap", node_quote(5_000, 1_200));
+        let client = DexClient::with_mock(mock.clone());
+        let usdc_arg = token_arg(&Address([5u8; 32]));
+
+        let err = client.swap(&WalletKeypair::generate(), "VXS", &usdc_arg, 5_000, 10_001).await.unwrap_err();
+        assert!(err.to_string().contains("exceeds 100%"), "{}", err);
+        assert_eq!(mock.call_count("vex_submitBundle"), 0);
+
+        assert_eq!(min_amount_out(u128::MAX, 0).unwrap(), u128::MAX);
+        assert_eq!(min_amount_out(1_000, 50).unwrap(), 995);
+        assert_eq!(min_amount_out(1_000, 10_000).unwrap(), 0);
+        assert!(min_amount_out(1_000, 10_001).is_err());
+    }
+
+    fn fee_probe(fee_bps: u128) -> Value {
+        let mut quote = node_quote(FEE_PROBE_AMOUNT, 1);
+        quote["fee"] = json!((FEE_PROBE_AMOUNT * fee_bps / 10_000).to_string());
+        quote
+    }
+
+    fn node_quote(amount_in: u128, amount_out: u128) -> Value {
+        json!({
+            "amount_in": amount_in.to_string(),
+            "amount_out": amount_out.to_string(),
+            "price_impact_percent": "1.20",
+            "fee": "0",
+            "pool_address": "0xpool",
+            "reserve_in": "50000000",
+            "reserve_out": "100000000"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_swap_to_target_balance() {
+        let usdc = Address([5u8; 32]);
+        let usdc_arg = token_arg(&usdc);
+        // 600_000 short; with 1% slippage aim for ceil(600_000 / 0.99) = 606_061
+        let estimate = amount_in_for_output(50_000_000, 100_000_000, 606_061, 0).unwrap();
+        let mock = MockTransport::new()
+            .with_result("vex_getBalance", json!("400000"))
+            .with_result("vex_getBalance", json!("1500000"))
+            .with_result("vex_getPool", pool(&Address::ZERO, &usdc, 50_000_000, 100_000_000))
+            // The node prices the local estimate short, so it's scaled up and re-quoted
+            .with_result("vex_quoteSwap", node_quote(estimate, 600_000))
+            .with_result("vex_quoteSwap", node_quote(estimate * 2, 610_000))
+            .with_result("vex_submitBundle", json!("0xtopup"));
+        let dex = DexClient::with_mock(mock.clone());
+        let wallet = WalletKeypair::generate();
+
+        let report = dex.swap_to_target_balance(&wallet, "VXS", &usdc_arg, 1_000_000, 100).await.unwrap();
+        assert_eq!(report["submission"], "0xtopup");
+        assert_eq!(report["amount_out"], "610000");
+        assert_eq!(report["min_amount_out"], "603900");
+        let quotes: Vec<_> = mock.calls().into_iter().filter(|(m, _)| m == "vex_quoteSwap").collect();
+        assert_eq!(quotes[0].1[2], estimate.to_string());
+        let rescaled = (estimate * 606_061).div_ceil(600_000);
+        assert_eq!(quotes[1].1[2], rescaled.to_string());
+        match &submitted_bundle(&mock).operations[0] {
+            vexidus_types::Operation::Swap { amount_in, min_amount_out, .. } => {
+                // The quote's own input, and a floor that still covers the shortfall
+                assert_eq!(*amount_in, estimate * 2);
+                assert_eq!(*min_amount_out, 603_900);
+                assert!(*min_amount_out >= 600_000);
+            }
+            other => panic!("Expected Swap, got {:?}", other),
+        }
+
+        // Already above target: nothing submitted
+        let report = dex.swap_to_target_balance(&wallet, "VXS", &usdc_arg, 1_000_000, 100).await.unwrap();
+        assert!(report["submission"].is_null());
+        assert_eq!(mock.call_count("vex_submitBundle"), 1);
+
+        assert!(dex.swap_to_target_balance(&wallet, "VXS", &usdc_arg, 2_000_000, 10_000).await.is_err());
+        assert_eq!(amount_in_for_output(1_000, 1_000, 1_000, FEE_BPS), None);
+    }
+
+    #[tokio::test]
+    async fn test_swap_to_target_balance_gives_up_on_short_quotes() {
+        let usdc = Address([5u8; 32]);
+        let mock = MockTransport::new()
+            .with_result("vex_getBalance", json!("0"))
+            .with_result("vex_getPool", pool(&Address::ZERO, &usdc, 50_000_000, 100_000_000))
+            .with_result("vex_quoteSwap", node_quote(1, 0));
+        let dex = DexClient::with_mock(mock.clone());
+        let err = dex
+            .swap_to_target_balance(&WalletKeypair::generate(), "VXS", &token_arg(&usdc), 1_000, 50)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("won't quote"), "{}", err);
+        assert_eq!(mock.call_count("vex_submitBundle"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_list_pools_paged() {
+        let usdc = Address([5u8; 32]);
+        let mut second = pool(&Address::ZERO, &Address([6u8; 32]), 1, 1);
+        second["address"] = json!("0xpool2");
+        let mock = MockTransport::new()
+            .with_result("vex_listPools", json!({ "items": [pool(&Address::ZERO, &usdc, 1, 1)], "next_cursor": "c1" }))
+            .with_result("vex_listPools", json!({ "items": [second], "next_cursor": null }));
+        let dex = DexClient::with_mock(mock.clone());
+
+        let pools = dex.list_all_pools(1).await.unwrap();
+        assert_eq!(pools.iter().map(|p| p.address.as_str()).collect::<Vec<_>>(), ["0xpool", "0xpool2"]);
+        let params: Vec<Value> = mock.calls().into_iter().map(|(_, params)| params).collect();
+        assert_eq!(params, [json!([1, null]), json!([1, "c1"])]);
+
+        // Unpaged nodes answer with the whole list
+        let mock = MockTransport::new().with_result("vex_listPools", json!([pool(&Address::ZERO, &usdc, 1, 1)]));
+        let (pools, next) = DexClient::with_mock(mock).list_pools_paged(None, 10).await.unwrap();
+        assert_eq!((pools.len(), next), (1, None));
+    }
+
+    #[tokio::test]
+    async fn test_pool_cache() {
+        let usdc = Address([5u8; 32]);
+        let usdc_arg = token_arg(&usdc);
+        let mock = MockTransport::new()
+            .with_result("vex_getPool", pool(&Address::ZERO, &usdc, 1_000, 2_000))
+            .with_result("vex_getPool", pool(&Address::ZERO, &usdc, 1_000, 3_000))
+            .with_result("vex_listPools", json!([pool(&Address::ZERO, &usdc, 1_000, 2_000)]));
+        let dex = DexClient::with_mock(mock.clone()).with_quote_cache(Duration::from_secs(60));
+
+        assert_eq!(dex.get_price("VXS", &usdc_arg).await.unwrap(), 2.0);
+        // Either order hits the same entry
+        let reversed = dex.get_pool(&usdc_arg, "VXS").await.unwrap();
+        assert_eq!(reversed.reserve_a, "2000");
+        assert_eq!(mock.call_count("vex_getPool"), 1);
+
+        dex.list_pools(10).await.unwrap();
+        dex.list_pools(10).await.unwrap();
+        assert_eq!(mock.call_count("vex_listPools"), 1);
+
+        dex.invalidate(&usdc_arg, "VXS");
+        assert_eq!(dex.get_price("VXS", &usdc_arg).await.unwrap(), 3.0);
+        assert_eq!(mock.call_count("vex_getPool"), 2);
+        dex.list_pools(10).await.unwrap();
+        assert_eq!(mock.call_count("vex_listPools"), 2);
+
+        dex.clear_cache();
+        dex.get_pool("VXS", &usdc_arg).await.unwrap();
+        assert_eq!(mock.call_count("vex_getPool"), 3);
+
+        // Uncached by default
+        let dex = DexClient::with_mock(mock.clone());
+        dex.get_pool("VXS", &usdc_arg).await.unwrap();
+        dex.get_pool("VXS", &usdc_arg).await.unwrap();
+        assert_eq!(mock.call_count("vex_getPool"), 5);
+    }
+
+    #[tokio::test]
+    async fn test_typed_amounts_and_get_price() {
+        let usdc = Address([5u8; 32]);
+        let usdc_arg = token_arg(&usdc);
+        let mut malformed = pool(&Address::ZERO, &usdc, 0, 2_000);
+        malformed["reserve_a"] = json!("12abc");
+        let mock = MockTransport::new()
+            .with_result("vex_getPool", pool(&Address::ZERO, &usdc, 1_000, 2_500))
+            .with_result("vex_getPool", malformed.clone());
+        let dex = DexClient::with_mock(mock);
+        assert_eq!(dex.get_price("VXS", &usdc_arg).await.unwrap(), 2.5);
+        let err = dex.get_price("VXS", &usdc_arg).await.unwrap_err();
+        assert!(err.to_string().contains("reserve_a"), "{}", err);
+
+        let info: PoolInfo = serde_json::from_value(malformed).unwrap();
+        assert!(info.reserve_a_u128().is_err());
+        assert_eq!(info.reserve_b_u128().unwrap(), 2_000);
+        assert_eq!(info.lp_total_supply_u128().unwrap(), 1_000);
+
+        let q = quote("1980");
+        assert_eq!(q.amount_out_u128().unwrap(), 1_980);
+        assert_eq!(q.amount_in_u128().unwrap(), 1_000_000);
+        assert_eq!(q.fee_u128().unwrap(), 3_000);
+        assert!(quote("").amount_out_u128().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_price_trigger_uses_token_decimals() {
+        // 1000 VXS (9 decimals) / 950 USDC (6 decimals): 0.95 USDC per VXS,
+        // though the raw reserve ratio is 0.00095
+        let usdc = crate::address_utils::bridged_mint_from_symbol("USDC").unwrap();
+        let usdc_arg = token_arg(&usdc);
+        let mock = MockTransport::new()
+            .with_result("vex_getPool", pool(&Address::ZERO, &usdc, 1_000_000_000_000, 950_000_000));
+        let dex = DexClient::with_mock(mock.clone());
+        assert!((dex.get_price("VXS", &usdc_arg).await.unwrap() - 0.95).abs() < 1e-12);
+        assert!((dex.get_price(&usdc_arg, "VXS").await.unwrap() - 1.0 / 0.95).abs() < 1e-12);
+
+        // A 0.90 stop-loss keeps waiting rather than firing on the raw ratio
+        let intent = crate::IntentBuilder::new()
+            .stop_loss(Address::ZERO, usdc, Amount::from_vxd(1), 0.90, true)
+            .build_conditional()
+            .unwrap();
+        let wallet = WalletKeypair::generate();
+        let keeper = dex.poll_and_execute_conditional(&wallet, &intent, Duration::from_millis(5));
+        assert!(tokio::time::timeout(Duration::from_millis(50), keeper).await.is_err());
+        assert!(mock.call_count("vex_getPool") > 1);
+        assert_eq!(mock.call_count("vex_quoteSwap"), 0);
+
+        // Unregistered tokens count as DEFAULT_DECIMALS
+        let dex = DexClient::with_mock(mock).with_registry(TokenRegistry::empty());
+        assert!((dex.get_price("VXS", &usdc_arg).await.unwrap() - 0.00095).abs() < 1e-15);
+    }
+
+    #[tokio::test]
+    async fn test_exact_out_quote_and_swap() {
+        // The inverse quote's input, fed forward, buys at least the target
+        for (reserve_in, reserve_out, target) in
+            [(50_000_000u128, 100_000_000u128, 600_000u128), (1_000_000_007, 3_000_000_019, 123_456_789), (10, 1_000, 1)]
+        {
+            let quote = SwapQuote::calculate_exact_out(reserve_in, reserve_out, target, FEE_BPS).unwrap();
+            let amount_in: u128 = quote.amount_in.parse().unwrap();
+            let forward = SwapQuote::calculate(reserve_in, reserve_out, amount_in, FEE_BPS);
+            assert_eq!(forward.amount_out, quote.amount_out);
+            assert!(quote.amount_out.parse::<u128>().unwrap() >= target);
+            let short = SwapQuote::calculate(reserve_in, reserve_out, amount_in - 1, FEE_BPS);
+            assert!(short.amount_out.parse::<u128>().unwrap() < target);
+        }
+        assert!(SwapQuote::calculate_exact_out(1_000, 1_000, 1_000, FEE_BPS).is_none());
+
+        let usdc = Address([5u8; 32]);
+        let usdc_arg = token_arg(&usdc);
+        let estimate = amount_in_for_output(50_000_000, 100_000_000, 600_000, 0).unwrap();
+        let mock = MockTransport::new()
+            .with_result("vex_getPool", pool(&Address::ZERO, &usdc, 50_000_000, 100_000_000))
+            .with_result("vex_quoteSwap", node_quote(estimate, 600_001));
+        let dex = DexClient::with_mock(mock.clone());
+        let quote = dex.quote_swap_exact_out("VXS", &usdc_arg, 600_000).await.unwrap();
+        assert_eq!(quote.pool_address, "0xpool");
+        assert_eq!(quote.amount_in_u128().unwrap(), estimate);
+        assert_eq!(mock.calls().last().unwrap().1[2], estimate.to_string());
+
+        // Spends the quoted input, not the whole limit
+        let wallet = WalletKeypair::generate();
+        let mock = mock.with_result("vex_submitBundle", json!("0xinvoice"));
+        let dex = DexClient::with_mock(mock.clone());
+        assert_eq!(dex.swap_exact_out(&wallet, "VXS", &usdc_arg, 600_000, estimate * 2).await.unwrap(), "0xinvoice");
+        match &submitted_bundle(&mock).operations[0] {
+            vexidus_types::Operation::Swap { amount_in, min_amount_out, .. } => {
+                assert_eq!(*min_amount_out, 600_000);
+                assert_eq!(*amount_in, estimate);
+            }
+            other => panic!("Expected Swap, got {:?}", other),
+        }
+
+        // Over the limit: nothing submitted
+        let err = dex.swap_exact_out(&wallet, "VXS", &usdc_arg, 600_000, estimate - 1).await.unwrap_err();
+        assert!(err.to_string().contains("over the"), "{}", err);
+        assert_eq!(mock.call_count("vex_submitBundle"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_twap_weights_by_time() {
+        let usdc = Address([5u8; 32]);
+        let usdc_arg = token_arg(&usdc);
+        // 2.0 for 10s, 4.0 for 30s, then a closing observation
+        let history = json!([
+            { "block_height": 100, "timestamp": 1_000, "price": 2.0 },
+            { "block_height": 105, "timestamp": 1_010, "price": "4.0" },
+            { "block_height": 120, "timestamp": 1_040, "price": 8.0 }
+        ]);
+        let mock = MockTransport::new().with_result("vex_getPriceHistory", history);
+        let client = DexClient::with_mock(mock.clone());
+
+        let expected = (2.0 * 10.0 + 4.0 * 30.0) / 40.0;
+        assert!((client.twap("VXS", &usdc_arg, 20).await.unwrap() - expected).abs() < 1e-12);
+        // Reversed pair averages the inverted samples (not 1 / the average)
+        let inverse = client.twap(&usdc_arg, "VXS", 20).await.unwrap();
+        let expected_inverse = (0.5 * 10.0 + 0.25 * 30.0) / 40.0;
+        assert!((inverse - expected_inverse).abs() < 1e-12, "{}", inverse);
+        assert!((inverse - 1.0 / expected).abs() > 1e-3);
+        assert_eq!(mock.calls()[0].1, json!(["VXS", usdc_arg, 20]));
+
+        // Without history, the spot price is used
+        let mock = MockTransport::new()
+            .with_result("vex_getPriceHistory", json!([]))
+            .with_result("vex_getPool", pool(&Address::ZERO, &usdc, 1_000, 5_000));
+        let client = DexClient::with_mock(mock);
+        assert_eq!(client.twap("VXS", &usdc_arg, 20).await.unwrap(), 5.0);
+
+        // RPC failures aren't papered over with the spot price
+        let mock = MockTransport::new()
+            .with_error("vex_getPriceHistory", -32601, "Method not found")
+            .with_result("vex_getPool", pool(&Address::ZERO, &usdc, 1_000, 5_000));
+        let client = DexClient::with_mock(mock.clone());
+        assert!(client.twap("VXS", &usdc_arg, 20).await.is_err());
+        assert_eq!(mock.call_count("vex_getPool"), 0);
+    }
+
+    #[test]
+    fn test_liquidity_minimums_shrink_with_slippage() {
+        // 1000 LP over 10_000 A / 40_000 B
+        let info: PoolInfo =
+            serde_json::from_value(pool(&Address::ZERO, &Address([5u8; 32]), 10_000, 40_000)).unwrap();
+
+        // Depositing 1% of each side mints 1% of the supply
+        assert_eq!(min_lp_tokens(&info, 100, 400, 0).unwrap(), 10);
+        // Unbalanced deposits are priced by the scarcer side
+        assert_eq!(min_lp_tokens(&info, 1_000, 400, 0).unwrap(), 10);
+        let lp: Vec<u128> = [0, 50, 500, 5_000]
+            .iter()
+            .map(|bps| min_lp_tokens(&info, 10_000, 40_000, *bps).unwrap())
+            .collect();
+        assert_eq!(lp, vec![1_000, 995, 950, 500]);
+        assert!(min_lp_tokens(&info, 10_000, 40_000, 10_001).is_err());
+        assert!(min_lp_tokens(&info, u128::MAX, 40_000, 50).is_err());
+
+        assert_eq!(min_withdrawal(&info, 100, 0).unwrap(), (1_000, 4_000));
+        let (a_tight, b_tight) = min_withdrawal(&info, 100, 50).unwrap();
+        let (a_loose, b_loose) = min_withdrawal(&info, 100, 500).unwrap();
+        assert_eq!((a_tight, b_tight), (995, 3_980));
+        assert!(a_loose < a_tight && b_loose < b_tight);
+
+        let empty: PoolInfo =
+            serde_json::from_value(pool(&Address::ZERO, &Address([5u8; 32]), 0, 0)).unwrap();
+        assert_eq!(min_lp_tokens(&empty, 100, 400, 50).unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_add_liquidity_sets_min_lp() {
+        let token_b = Address([5u8; 32]);
+        let mock = MockTransport::new()
+            .with_result("vex_getPool", pool(&Address::ZERO, &token_b, 10_000, 40_000))
+            .with_result("vex_submitBundle", json!("0xabc"));
+        let client = DexClient::with_mock(mock.clone());
+        let wallet = WalletKeypair::generate();
+        client
+            .add_liquidity(&wallet, "VXS", &token_arg(&token_b), 100, 400, 100)
+            .await
+            .unwrap();
+
+        match &submitted_bundle(&mock).operations[0] {
+            vexidus_types::Operation::AddLiquidity { min_lp_tokens, .. } => assert_eq!(*min_lp_tokens, 9),
+            other => panic!("Expected AddLiquidity, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sweep_dust() {
+        // Canonical order puts VXS (ZERO) first in every pool below
+        let dust = Address([3u8; 32]);
+        let whale = Address([4u8; 32]);
+        let orphan = Address([5u8; 32]);
+        let mock = MockTransport::new()
+            .with_result(
+                "vex_getTokenBalances",
+                json!([
+                    { "token": token_arg(&dust), "balance": "1000" },
+                    { "token": token_arg(&whale), "balance": "5000000" },
+                    { "token": token_arg(&orphan), "balance": "10" },
+                    { "token": "VXS", "balance": "999" },
+                ]),
+            )
+            // dust: 1000 * 2 = 2000 VXS value; whale: 10_000_000 VXS value
+            .with_result("vex_getPool", pool(&Address::ZERO, &dust, 2_000_000, 1_000_000))
+            .with_result("vex_getPool", pool(&Address::ZERO, &whale, 2_000_000, 1_000_000))
+            .with_error("vex_getPool", -32000, "Pool not found")
+            .with_result("vex_submitBundle", json!("0xsweep"));
+        let dex = DexClient::with_mock(mock.clone());
+        let wallet = WalletKeypair::generate();
+
+        let report = dex.sweep_dust(&wallet, "VXS", 10_000).await.unwrap();
+        assert_eq!(report["submission"], "0xsweep");
+        let swept = report["swept"].as_array().unwrap();
+        assert_eq!(swept.len(), 1);
+        assert_eq!(swept[0]["token"], token_arg(&dust));
+        assert_eq!(swept[0]["value"], "2000");
+        let skipped = report["skipped"].as_array().unwrap();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0]["token"], token_arg(&orphan));
+        assert_eq!(mock.call_count("vex_submitBundle"), 1);
+
+        // Unreadable reserves fail the sweep rather than valuing the token at 0
+        let mut malformed = pool(&Address::ZERO, &dust, 2_000_000, 1_000_000);
+        malformed["reserve_b"] = json!("1e6");
+        let mock = MockTransport::new()
+            .with_result("vex_getTokenBalances", json!([{ "token": token_arg(&dust), "balance": "1000" }]))
+            .with_result("vex_getPool", malformed);
+        let err = DexClient::with_mock(mock.clone()).sweep_dust(&wallet, "VXS", 10_000).await.unwrap_err();
+        assert!(err.to_string().contains("reserve"), "{}", err);
+        assert_eq!(mock.call_count("vex_submitBundle"), 0);
+    }
 }
@@ -13,10 +13,15 @@
 //!     .unwrap();
 //! ```
 
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use vexidus_types::bridge::BridgeProofType;
 use vexidus_types::intent::{Goal, Constraints, RoutePreference};
-use vexidus_types::primitives::{Address, Amount, Timestamp};
+use vexidus_types::primitives::{Address, Amount, PublicKey, Timestamp};
 use thiserror::Error;
 
+use crate::wallet::{verify_with_pubkey, WalletKeypair};
+
 #[derive(Debug, Error)]
 pub enum IntentError {
     #[error("No goal specified — call swap(), stake(), or transfer() first")]
@@ -29,6 +34,169 @@ pub enum IntentError {
     SerializationError(String),
     #[error("Parse error: {0}")]
     ParseError(String),
+    #[error("Intent has a price trigger — call build_conditional() instead")]
+    ConditionalIntent,
+    #[error("Intent has a schedule — call build_recurring() instead")]
+    RecurringIntent,
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+    #[error("Invalid trigger price: {0}")]
+    InvalidTriggerPrice(f64),
+    #[error("Unsatisfiable constraints: {0}")]
+    UnsatisfiableConstraints(String),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Token 0x{} is not registered on-chain", hex::encode(.0 .0))]
+    UnknownToken(Address),
+    #[error("No balance available for token 0x{}", hex::encode(.0 .0))]
+    MissingBalance(Address),
+    #[error("Name {0} does not resolve to an address")]
+    UnresolvedName(String),
+    #[error("Transfer has no recipient (Address::ZERO) — resolve its .vex name first")]
+    MissingRecipient,
+    #[error("Goal amount is zero — resolve balance-relative amounts first")]
+    ZeroAmount,
+    #[error("Client error: {0}")]
+    Client(#[from] crate::wallet_client::WalletClientError),
+}
+
+/// A swap's `min_output` may be at most this many times its input amount
+/// (raw units) before it's treated as a typo rather than a real expectation.
+pub const MAX_OUTPUT_RATIO: u128 = 1_000_000;
+
+/// Price condition that arms a [`ConditionalIntent`].
+///
+/// The watched price is `base_token` quoted in `quote_token`, in whole tokens
+/// rather than raw units (same as [`DexClient::get_price`](crate::DexClient::get_price)):
+/// a VXS/USDC trigger of 0.90 means 0.90 USDC per VXS, whatever the two
+/// tokens' decimals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceTrigger {
+    pub base_token: Address,
+    pub quote_token: Address,
+    pub price: f64,
+    /// Fire when the price falls below `price` (stop-loss); otherwise when it rises above.
+    pub below: bool,
+}
+
+impl PriceTrigger {
+    /// Whether `current_price` satisfies this trigger.
+    pub fn is_triggered(&self, current_price: f64) -> bool {
+        if self.below {
+            current_price < self.price
+        } else {
+            current_price > self.price
+        }
+    }
+}
+
+/// An intent held back until its price trigger fires.
+///
+/// The trigger lives only in this SDK-side struct, next to the goal: `Goal`
+/// has no conditional variant, so the node never sees `trigger` and can't
+/// enforce it. It executes whatever `goal` it's sent, unconditionally, so
+/// submit `goal` only once the trigger has fired. Nothing on-chain watches the
+/// price either — execution needs a running keeper, e.g.
+/// [`DexClient::poll_and_execute_conditional`](crate::DexClient::poll_and_execute_conditional).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConditionalIntent {
+    pub trigger: PriceTrigger,
+    pub goal: Goal,
+    pub constraints: Constraints,
+}
+
+/// How often, and how many times, a [`RecurringIntent`] runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecurringSchedule {
+    /// Seconds between executions.
+    pub interval_secs: u64,
+    /// Total number of executions.
+    pub count: u32,
+}
+
+impl RecurringSchedule {
+    /// When execution `index` (0-based) is due, for a schedule starting at `start`.
+    /// `None` past the last execution.
+    pub fn due_at(&self, start: Timestamp, index: u32) -> Option<Timestamp> {
+        if index >= self.count {
+            return None;
+        }
+        let offset = self.interval_secs.checked_mul(index as u64)?;
+        start.0.checked_add(offset).map(Timestamp)
+    }
+}
+
+/// An intent repeated on a fixed schedule, e.g. a subscription payment.
+///
+/// The chain doesn't run schedules by itself: each execution has to be
+/// submitted by a keeper (or by the node's scheduled-transaction support,
+/// where enabled) once [`RecurringSchedule::due_at`] has passed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurringIntent {
+    pub schedule: RecurringSchedule,
+    pub goal: Goal,
+    pub constraints: Constraints,
+}
+
+/// Wire format version written by [`SignedIntentEnvelope`].
+pub const ENVELOPE_VERSION: u8 = 1;
+
+/// Domain separator for the envelope signing hash.
+const ENVELOPE_DOMAIN: &[u8] = b"vexidus-intent-v1";
+
+/// A signed intent as passed from a wallet to a solver.
+///
+/// Produced by [`IntentBuilder::sign`]; sent as [`to_bytes`](Self::to_bytes)
+/// (Borsh). [`verify`](Self::verify) only checks that `pubkey` signed the
+/// envelope — whether `pubkey` may act for `sender` is up to the account's key
+/// set on-chain.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct SignedIntentEnvelope {
+    pub version: u8,
+    pub goal: Goal,
+    pub constraints: Constraints,
+    pub sender: Address,
+    /// Ed25519 signature over [`signing_hash`](Self::signing_hash).
+    pub signature: Vec<u8>,
+    pub pubkey: PublicKey,
+    pub nonce: u64,
+}
+
+impl SignedIntentEnvelope {
+    /// Blake3 hash of `"vexidus-intent-v1"` followed by the Borsh encoding of
+    /// `(version, goal, constraints, sender, pubkey, nonce)`.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(ENVELOPE_DOMAIN);
+        borsh::to_writer(
+            &mut hasher,
+            &(self.version, &self.goal, &self.constraints, &self.sender, &self.pubkey, self.nonce),
+        )
+        .expect("hashing into memory cannot fail");
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Whether `signature` is `pubkey`'s signature over the envelope.
+    pub fn verify(&self) -> bool {
+        verify_with_pubkey(&self.pubkey.0, &self.signing_hash(), &self.signature)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, IntentError> {
+        borsh::to_vec(self).map_err(|e| IntentError::SerializationError(e.to_string()))
+    }
+
+    /// Decode an envelope, rejecting versions this SDK doesn't know.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, IntentError> {
+        let envelope: Self = borsh::from_slice(bytes)
+            .map_err(|e| IntentError::ParseError(format!("Invalid intent envelope: {}", e)))?;
+        if envelope.version != ENVELOPE_VERSION {
+            return Err(IntentError::ParseError(format!(
+                "Unsupported intent envelope version {}",
+                envelope.version
+            )));
+        }
+        Ok(envelope)
+    }
 }
 
 /// Fluent builder for constructing intents.
@@ -36,6 +204,8 @@ pub struct IntentBuilder {
     goal: Option<Goal>,
     constraints: Constraints,
     from: Option<Address>,
+    trigger: Option<PriceTrigger>,
+    schedule: Option<RecurringSchedule>,
 }
 
 impl IntentBuilder {
@@ -44,6 +214,19 @@ impl IntentBuilder {
             goal: None,
             constraints: Constraints::default(),
             from: None,
+            trigger: None,
+            schedule: None,
+        }
+    }
+
+    /// Start from an existing goal and constraints (e.g. a parsed or stored intent).
+    pub fn from_goal(goal: Goal, constraints: Constraints) -> Self {
+        Self {
+            goal: Some(goal),
+            constraints,
+            from: None,
+            trigger: None,
+            schedule: None,
         }
     }
 
@@ -59,6 +242,40 @@ impl IntentBuilder {
         self
     }
 
+    /// Swap an already-scaled raw amount (smallest units of `from_token`).
+    ///
+    /// Identical to [`swap`](Self::swap); named for callers holding raw
+    /// integers for tokens whose decimals the registry doesn't know, so no
+    /// decimals inference is implied.
+    pub fn swap_raw(self, from_token: Address, to_token: Address, amount_raw: u128) -> Self {
+        self.swap(from_token, to_token, Amount(amount_raw))
+    }
+
+    /// Stop-loss: swap `amount` of `from_token` into `to_token` once the price of
+    /// `from_token` (in whole `to_token`s, see [`PriceTrigger`]) crosses
+    /// `trigger_price`.
+    ///
+    /// `below = true` fires when the price drops under the trigger (classic
+    /// stop-loss), `false` when it rises above (take-profit). Finish with
+    /// [`build_conditional`](Self::build_conditional).
+    pub fn stop_loss(
+        mut self,
+        from_token: Address,
+        to_token: Address,
+        amount: Amount,
+        trigger_price: f64,
+        below: bool,
+    ) -> Self {
+        self.goal = Some(Goal::Swap { from_token, to_token, amount });
+        self.trigger = Some(PriceTrigger {
+            base_token: from_token,
+            quote_token: to_token,
+            price: trigger_price,
+            below,
+        });
+        self
+    }
+
     /// Stake tokens (optionally to a specific validator).
     pub fn stake(mut self, amount: Amount, validator: Option<Address>) -> Self {
         self.goal = Some(Goal::Stake {
@@ -81,6 +298,52 @@ impl IntentBuilder {
         self
     }
 
+    /// Unstake VXS from the sender's own validator stake.
+    pub fn unstake(mut self, amount: Amount) -> Self {
+        self.goal = Some(Goal::Unstake { amount });
+        self
+    }
+
+    /// Withdraw a delegation from `validator`.
+    pub fn undelegate(mut self, validator: Address, amount: Amount) -> Self {
+        self.goal = Some(Goal::Undelegate { validator, amount });
+        self
+    }
+
+    /// Claim accumulated staking rewards.
+    pub fn claim_rewards(mut self) -> Self {
+        self.goal = Some(Goal::ClaimRewards);
+        self
+    }
+
+    /// Send tokens to another account.
+    pub fn transfer(mut self, to: Address, token: Address, amount: Amount) -> Self {
+        self.goal = Some(Goal::Transfer { to, token, amount });
+        self
+    }
+
+    /// Pay `amount` of `token` to `to` every `interval_secs`, `count` times.
+    ///
+    /// Finish with [`build_recurring`](Self::build_recurring); see
+    /// [`RecurringIntent`] for how the payments get executed.
+    pub fn recurring(mut self, to: Address, token: Address, amount: Amount, interval_secs: u64, count: u32) -> Self {
+        self.goal = Some(Goal::Transfer { to, token, amount });
+        self.schedule = Some(RecurringSchedule { interval_secs, count });
+        self
+    }
+
+    /// Bridge tokens in from another chain, backed by a burn proof.
+    pub fn bridge(
+        mut self,
+        source_chain: String,
+        token_symbol: String,
+        amount: Amount,
+        proof: BridgeProofType,
+    ) -> Self {
+        self.goal = Some(Goal::Bridge { source_chain, token_symbol, amount, proof });
+        self
+    }
+
     /// Set a custom natural language goal (for NL parser or future LLM integration).
     pub fn custom(mut self, description: String) -> Self {
         self.goal = Some(Goal::Custom(description));
@@ -124,8 +387,20 @@ impl IntentBuilder {
     }
 
     /// Build the intent, returning (Goal, Constraints).
+    ///
+    /// Rejects the placeholders [`parse_intent`](crate::parse_intent) leaves
+    /// for later resolution: a transfer to `Address::ZERO` (an unresolved
+    /// `.vex` name) and a swap, stake, or transfer of zero (an unresolved
+    /// balance-relative amount).
     pub fn build(self) -> Result<(Goal, Constraints), IntentError> {
+        if self.trigger.is_some() {
+            return Err(IntentError::ConditionalIntent);
+        }
+        if self.schedule.is_some() {
+            return Err(IntentError::RecurringIntent);
+        }
         let goal = self.goal.ok_or(IntentError::NoGoal)?;
+        check_placeholders(&goal)?;
         if let Some(s) = self.constraints.max_slippage {
             if s > 100 {
                 return Err(IntentError::InvalidSlippage(s));
@@ -134,9 +409,53 @@ impl IntentBuilder {
         Ok((goal, self.constraints))
     }
 
+    /// Build an intent that carries a price trigger (see [`stop_loss`](Self::stop_loss)).
+    pub fn build_conditional(mut self) -> Result<ConditionalIntent, IntentError> {
+        let trigger = self.trigger.take().ok_or(IntentError::NoGoal)?;
+        if !trigger.price.is_finite() || trigger.price <= 0.0 {
+            return Err(IntentError::InvalidTriggerPrice(trigger.price));
+        }
+        let (goal, constraints) = self.build()?;
+        Ok(ConditionalIntent { trigger, goal, constraints })
+    }
+
+    /// Build an intent that carries a schedule (see [`recurring`](Self::recurring)).
+    pub fn build_recurring(mut self) -> Result<RecurringIntent, IntentError> {
+        let schedule = self.schedule.take().ok_or(IntentError::NoGoal)?;
+        if schedule.interval_secs == 0 {
+            return Err(IntentError::InvalidSchedule("interval must be nonzero".into()));
+        }
+        if schedule.count == 0 {
+            return Err(IntentError::InvalidSchedule("count must be nonzero".into()));
+        }
+        let (goal, constraints) = self.build()?;
+        Ok(RecurringIntent { schedule, goal, constraints })
+    }
+
+    /// Build the intent and sign it into a [`SignedIntentEnvelope`] for a solver.
+    ///
+    /// Needs a sender ([`from_account`](Self::from_account)). `nonce` should be
+    /// unique per sender so solvers can drop replays.
+    pub fn sign(self, wallet: &WalletKeypair, nonce: u64) -> Result<SignedIntentEnvelope, IntentError> {
+        let sender = self.from.ok_or(IntentError::NoSender)?;
+        let (goal, constraints) = self.build()?;
+        let mut envelope = SignedIntentEnvelope {
+            version: ENVELOPE_VERSION,
+            goal,
+            constraints,
+            sender,
+            signature: Vec::new(),
+            pubkey: wallet.public_key(),
+            nonce,
+        };
+        envelope.signature = wallet.sign(&envelope.signing_hash());
+        Ok(envelope)
+    }
+
     /// Build and serialize to JSON (for RPC submission).
     pub fn to_json(&self) -> Result<String, IntentError> {
         let goal = self.goal.clone().ok_or(IntentError::NoGoal)?;
+        check_placeholders(&goal)?;
         let json = serde_json::json!({
             "goal": goal,
             "constraints": self.constraints,
@@ -145,12 +464,164 @@ impl IntentBuilder {
             .map_err(|e| IntentError::SerializationError(e.to_string()))
     }
 
+    /// Rebuild a builder from [`to_json`](Self::to_json) output; see
+    /// [`parse_intent_json`].
+    pub fn from_json(json: &str) -> Result<IntentBuilder, IntentError> {
+        let (goal, constraints) = parse_intent_json(json)?;
+        Ok(IntentBuilder::from_goal(goal, constraints))
+    }
+
     /// Get the sender address (if set).
     pub fn sender(&self) -> Option<&Address> {
         self.from.as_ref()
     }
 }
 
+/// Reject the zero recipient and zero amounts [`ParsedIntent`](crate::ParsedIntent)
+/// uses until its name or amount is resolved.
+fn check_placeholders(goal: &Goal) -> Result<(), IntentError> {
+    match goal {
+        Goal::Transfer { to, .. } if *to == Address::ZERO => Err(IntentError::MissingRecipient),
+        Goal::Swap { amount, .. } | Goal::Stake { amount, .. } | Goal::Transfer { amount, .. }
+            if amount.0 == 0 =>
+        {
+            Err(IntentError::ZeroAmount)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether a goal must go through a solver rather than straight into a bundle.
+///
+/// Swaps, liquidity provision, transfers, unstakes, undelegations, reward
+/// claims, and stakes naming a validator map 1:1 onto bundle operations (see
+/// [`bundle::from_goal`](crate::bundle::from_goal)). Composite, Custom, and
+/// Bridge goals need a solver, as does a stake with no validator (the solver
+/// picks one). A [`ConditionalIntent`] or [`RecurringIntent`] always needs a
+/// keeper regardless of its inner goal.
+pub fn requires_solver(goal: &Goal) -> bool {
+    match goal {
+        Goal::Swap { .. }
+        | Goal::ProvideLiquidity { .. }
+        | Goal::Transfer { .. }
+        | Goal::Unstake { .. }
+        | Goal::Undelegate { .. }
+        | Goal::ClaimRewards => false,
+        Goal::Stake { validator, .. } => validator.is_none(),
+        Goal::Bridge { .. } | Goal::Composite(_) | Goal::Custom(_) => true,
+    }
+}
+
+/// Wire shape written by [`IntentBuilder::to_json`].
+#[derive(Deserialize)]
+struct IntentJson {
+    goal: Goal,
+    #[serde(default)]
+    constraints: Constraints,
+}
+
+/// Parse `{"goal": .., "constraints": ..}` as written by
+/// [`IntentBuilder::to_json`].
+///
+/// Missing `constraints` default to [`Constraints::default`]. An unknown goal
+/// variant, a missing goal, or a malformed amount is a
+/// [`ParseError`](IntentError::ParseError) naming the offending field.
+pub fn parse_intent_json(json: &str) -> Result<(Goal, Constraints), IntentError> {
+    let parsed: IntentJson = serde_json::from_str(json)
+        .map_err(|e| IntentError::ParseError(format!("Invalid intent JSON: {}", e)))?;
+    Ok((parsed.goal, parsed.constraints))
+}
+
+/// Every non-native token mint `goal` references, in first-seen order.
+///
+/// Bridge goals name their token by symbol and contribute nothing.
+pub fn goal_tokens(goal: &Goal) -> Vec<Address> {
+    fn collect(goal: &Goal, out: &mut Vec<Address>) {
+        let tokens: Vec<Address> = match goal {
+            Goal::Swap { from_token, to_token, .. } => vec![*from_token, *to_token],
+            Goal::Stake { token, .. } | Goal::Transfer { token, .. } => vec![*token],
+            Goal::ProvideLiquidity { token_a, token_b, .. } => vec![*token_a, *token_b],
+            Goal::Composite(goals) => {
+                goals.iter().for_each(|g| collect(g, out));
+                Vec::new()
+            }
+            _ => Vec::new(),
+        };
+        for token in tokens {
+            if token != Address::ZERO && !out.contains(&token) {
+                out.push(token);
+            }
+        }
+    }
+    let mut out = Vec::new();
+    collect(goal, &mut out);
+    out
+}
+
+/// Check that `constraints` can possibly be met for `goal`.
+///
+/// Catches intents that would sit unexecuted forever: slippage over 100%, a
+/// deadline already in the past, a swap `min_output` more than
+/// [`MAX_OUTPUT_RATIO`] times the input, and a DEX/pool route on a goal that
+/// doesn't trade (or an `ExactPool` pointing at one of the goal's own tokens).
+pub fn validate_constraints(goal: &Goal, constraints: &Constraints) -> Result<(), IntentError> {
+    if let Some(s) = constraints.max_slippage {
+        if s > 100 {
+            return Err(IntentError::InvalidSlippage(s));
+        }
+    }
+
+    if let Some(deadline) = constraints.deadline {
+        if deadline.0 <= Timestamp::now().0 {
+            return Err(IntentError::UnsatisfiableConstraints(format!(
+                "deadline {} has already passed",
+                deadline.0
+            )));
+        }
+    }
+
+    if let (Goal::Swap { amount, .. }, Some(min_output)) = (goal, constraints.min_output) {
+        if min_output.0 > amount.0.saturating_mul(MAX_OUTPUT_RATIO) {
+            return Err(IntentError::UnsatisfiableConstraints(format!(
+                "min_output {} is implausible for an input of {}",
+                min_output.0, amount.0
+            )));
+        }
+    }
+
+    let route = match &constraints.preferred_route {
+        RoutePreference::PreferDex(addr) => Some(("PreferDex", addr, false)),
+        RoutePreference::ExactPool(addr) => Some(("ExactPool", addr, true)),
+        _ => None,
+    };
+    if let Some((kind, route_addr, is_pool)) = route {
+        let tokens = traded_tokens(goal);
+        if tokens.is_empty() {
+            return Err(IntentError::UnsatisfiableConstraints(format!(
+                "{} route set on a goal that doesn't trade",
+                kind
+            )));
+        }
+        if is_pool && tokens.contains(route_addr) {
+            return Err(IntentError::UnsatisfiableConstraints(
+                "ExactPool route points at a token, not a pool".into(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Tokens a goal trades through a pool (empty for non-trading goals).
+fn traded_tokens(goal: &Goal) -> Vec<Address> {
+    match goal {
+        Goal::Swap { from_token, to_token, .. } => vec![*from_token, *to_token],
+        Goal::ProvideLiquidity { token_a, token_b, .. } => vec![*token_a, *token_b],
+        Goal::Composite(goals) => goals.iter().flat_map(traded_tokens).collect(),
+        _ => Vec::new(),
+    }
+}
+
 impl Default for IntentBuilder {
     fn default() -> Self {
         Self::new()
@@ -176,6 +647,19 @@ mod tests {
         assert_eq!(constraints.max_slippage, Some(2));
     }
 
+    #[test]
+    fn test_build_swap_raw_unscaled() {
+        // 6-decimal token: 12.345678 units, already scaled by the caller
+        let (goal, _) = IntentBuilder::new()
+            .swap_raw(Address([2u8; 32]), Address::ZERO, 12_345_678)
+            .build()
+            .unwrap();
+        match goal {
+            Goal::Swap { amount, .. } => assert_eq!(amount, Amount(12_345_678)),
+            _ => panic!("Expected Swap goal"),
+        }
+    }
+
     #[test]
     fn test_build_stake_intent() {
         let (goal, _) = IntentBuilder::new()
@@ -192,6 +676,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_build_recurring_payment() {
+        let to = Address([2u8; 32]);
+        let month = 30 * 24 * 3600;
+        let intent = IntentBuilder::new()
+            .recurring(to, Address::ZERO, Amount::from_vxd(10), month, 12)
+            .build_recurring()
+            .unwrap();
+        assert_eq!(intent.schedule, RecurringSchedule { interval_secs: month, count: 12 });
+        assert!(matches!(intent.goal, Goal::Transfer { to: t, .. } if t == to));
+        assert_eq!(intent.schedule.due_at(Timestamp(1_000), 0), Some(Timestamp(1_000)));
+        assert_eq!(intent.schedule.due_at(Timestamp(1_000), 11), Some(Timestamp(1_000 + 11 * month)));
+        assert_eq!(intent.schedule.due_at(Timestamp(1_000), 12), None);
+
+        let result = IntentBuilder::new().recurring(to, Address::ZERO, Amount::from_vxd(10), month, 12).build();
+        assert!(matches!(result, Err(IntentError::RecurringIntent)));
+        for (interval, count) in [(0, 12), (month, 0)] {
+            let result = IntentBuilder::new()
+                .recurring(to, Address::ZERO, Amount::from_vxd(10), interval, count)
+                .build_recurring();
+            assert!(matches!(result, Err(IntentError::InvalidSchedule(_))));
+        }
+    }
+
+    #[test]
+    fn test_build_staking_transfer_and_bridge_goals() {
+        let validator = Address([2u8; 32]);
+        let (goal, _) = IntentBuilder::new().unstake(Amount::from_vxd(5)).build().unwrap();
+        assert!(matches!(goal, Goal::Unstake { amount } if amount == Amount::from_vxd(5)));
+
+        let (goal, _) = IntentBuilder::new()
+            .undelegate(validator, Amount::from_vxd(3))
+            .build()
+            .unwrap();
+        assert!(matches!(goal, Goal::Undelegate { validator: v, .. } if v == validator));
+
+        let (goal, _) = IntentBuilder::new().claim_rewards().build().unwrap();
+        assert!(matches!(goal, Goal::ClaimRewards));
+
+        let (goal, _) = IntentBuilder::new()
+            .transfer(Address([3u8; 32]), Address::ZERO, Amount::from_vxd(1))
+            .build()
+            .unwrap();
+        assert!(matches!(goal, Goal::Transfer { to, .. } if to == Address([3u8; 32])));
+
+        let (goal, _) = IntentBuilder::new()
+            .bridge("ethereum".into(), "USDC".into(), Amount(1_000_000), BridgeProofType::Legacy)
+            .build()
+            .unwrap();
+        assert!(matches!(goal, Goal::Bridge { ref source_chain, .. } if source_chain == "ethereum"));
+
+        // Slippage is still validated for non-swap goals
+        assert!(IntentBuilder::new().claim_rewards().with_slippage(150).build().is_err());
+    }
+
     #[test]
     fn test_no_goal_errors() {
         let result = IntentBuilder::new().build();
@@ -217,6 +756,133 @@ mod tests {
         assert!(json.contains("max_slippage"));
     }
 
+    #[test]
+    fn test_stop_loss_intent() {
+        let usdc = Address([1u8; 32]);
+        let conditional = IntentBuilder::new()
+            .stop_loss(Address::ZERO, usdc, Amount::from_vxd(100), 0.90, true)
+            .with_slippage(1)
+            .build_conditional()
+            .unwrap();
+
+        assert!(conditional.trigger.below);
+        assert_eq!(conditional.trigger.price, 0.90);
+        assert_eq!(conditional.trigger.base_token, Address::ZERO);
+        assert_eq!(conditional.trigger.quote_token, usdc);
+        assert!(conditional.trigger.is_triggered(0.89));
+        assert!(!conditional.trigger.is_triggered(0.95));
+        match conditional.goal {
+            Goal::Swap { amount, .. } => assert_eq!(amount, Amount::from_vxd(100)),
+            _ => panic!("Expected Swap goal"),
+        }
+
+        // Plain build() refuses to drop the trigger
+        let result = IntentBuilder::new()
+            .stop_loss(Address::ZERO, usdc, Amount::from_vxd(1), 0.90, true)
+            .build();
+        assert!(matches!(result, Err(IntentError::ConditionalIntent)));
+    }
+
+    #[test]
+    fn test_requires_solver() {
+        let swap = Goal::Swap {
+            from_token: Address::ZERO,
+            to_token: Address([1u8; 32]),
+            amount: Amount(100),
+        };
+        assert!(!requires_solver(&swap));
+
+        assert!(!requires_solver(&Goal::ProvideLiquidity {
+            token_a: Address::ZERO,
+            token_b: Address([1u8; 32]),
+            amount_a: Amount(100),
+            amount_b: Amount(200),
+        }));
+        assert!(!requires_solver(&Goal::Stake {
+            token: Address::ZERO,
+            amount: Amount(100),
+            validator: Some(Address([3u8; 32])),
+        }));
+        assert!(requires_solver(&Goal::Stake {
+            token: Address::ZERO,
+            amount: Amount(100),
+            validator: None,
+        }));
+        assert!(!requires_solver(&Goal::Transfer { to: Address([2u8; 32]), token: Address::ZERO, amount: Amount(1) }));
+        assert!(!requires_solver(&Goal::Unstake { amount: Amount(1) }));
+        assert!(!requires_solver(&Goal::Undelegate { validator: Address([3u8; 32]), amount: Amount(1) }));
+        assert!(!requires_solver(&Goal::ClaimRewards));
+        assert!(requires_solver(&Goal::Custom("do something".into())));
+        assert!(requires_solver(&Goal::Composite(vec![swap])));
+        assert!(requires_solver(&Goal::Bridge {
+            source_chain: "solana".into(),
+            token_symbol: "SOL".into(),
+            amount: Amount(100),
+            proof: vexidus_types::bridge::BridgeProofType::Legacy,
+        }));
+    }
+
+    fn swap_goal(amount: u128) -> Goal {
+        Goal::Swap { from_token: Address::ZERO, to_token: Address([1u8; 32]), amount: Amount(amount) }
+    }
+
+    #[test]
+    fn test_validate_constraints_ok() {
+        let constraints = Constraints {
+            max_slippage: Some(2),
+            deadline: Some(Timestamp::now() + 600),
+            min_output: Some(Amount(90)),
+            preferred_route: RoutePreference::PreferDex(Address([9u8; 32])),
+            ..Default::default()
+        };
+        assert!(validate_constraints(&swap_goal(100), &constraints).is_ok());
+    }
+
+    #[test]
+    fn test_validate_constraints_inconsistencies() {
+        let goal = swap_goal(100);
+
+        let slippage = Constraints { max_slippage: Some(150), ..Default::default() };
+        assert!(matches!(
+            validate_constraints(&goal, &slippage),
+            Err(IntentError::InvalidSlippage(150))
+        ));
+
+        let expired = Constraints { deadline: Some(Timestamp(1)), ..Default::default() };
+        assert!(matches!(
+            validate_constraints(&goal, &expired),
+            Err(IntentError::UnsatisfiableConstraints(_))
+        ));
+
+        let greedy = Constraints {
+            min_output: Some(Amount(100 * MAX_OUTPUT_RATIO + 1)),
+            ..Default::default()
+        };
+        assert!(matches!(
+            validate_constraints(&goal, &greedy),
+            Err(IntentError::UnsatisfiableConstraints(_))
+        ));
+
+        let stake = Goal::Stake { token: Address::ZERO, amount: Amount(100), validator: None };
+        let dex_route = Constraints {
+            preferred_route: RoutePreference::PreferDex(Address([9u8; 32])),
+            ..Default::default()
+        };
+        assert!(matches!(
+            validate_constraints(&stake, &dex_route),
+            Err(IntentError::UnsatisfiableConstraints(_))
+        ));
+
+        let pool_is_token = Constraints {
+            preferred_route: RoutePreference::ExactPool(Address([1u8; 32])),
+            ..Default::default()
+        };
+        assert!(matches!(
+            validate_constraints(&goal, &pool_is_token),
+            Err(IntentError::UnsatisfiableConstraints(_))
+        ));
+    }
+
     #[test]
     fn test_composite_goal() {
         let goals = vec![
@@ -241,4 +907,76 @@ mod tests {
             _ => panic!("Expected Composite"),
         }
     }
+
+    #[test]
+    fn test_signed_envelope_roundtrip() {
+        let wallet = WalletKeypair::generate();
+        let sender = Address([1u8; 32]);
+        let envelope = IntentBuilder::new()
+            .from_account(sender)
+            .swap(Address::ZERO, Address([2u8; 32]), Amount::from_vxd(10))
+            .with_slippage(1)
+            .sign(&wallet, 7)
+            .unwrap();
+        assert!(envelope.verify());
+
+        let decoded = SignedIntentEnvelope::from_bytes(&envelope.to_bytes().unwrap()).unwrap();
+        assert!(decoded.verify());
+        assert_eq!(decoded.sender, sender);
+        assert_eq!(decoded.nonce, 7);
+        assert_eq!(decoded.constraints.max_slippage, Some(1));
+
+        // Any change to the signed fields breaks the signature
+        let mut tampered = decoded.clone();
+        tampered.goal = Goal::Swap {
+            from_token: Address::ZERO,
+            to_token: Address([2u8; 32]),
+            amount: Amount::from_vxd(1_000),
+        };
+        assert!(!tampered.verify());
+        let mut tampered = decoded.clone();
+        tampered.nonce = 8;
+        assert!(!tampered.verify());
+
+        let mut bytes = envelope.to_bytes().unwrap();
+        bytes[0] = 9;
+        assert!(matches!(SignedIntentEnvelope::from_bytes(&bytes), Err(IntentError::ParseError(_))));
+        assert!(matches!(
+            IntentBuilder::new().claim_rewards().sign(&wallet, 0),
+            Err(IntentError::NoSender)
+        ));
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let builder = IntentBuilder::new()
+            .swap(Address::ZERO, Address([1u8; 32]), Amount::from_vxd(100))
+            .with_slippage(2)
+            .with_min_output(Amount(5));
+        let json = builder.to_json().unwrap();
+        let (goal, constraints) = builder.build().unwrap();
+
+        let (goal2, constraints2) = IntentBuilder::from_json(&json).unwrap().build().unwrap();
+        assert_eq!(serde_json::to_value(&goal).unwrap(), serde_json::to_value(&goal2).unwrap());
+        assert_eq!(
+            serde_json::to_value(&constraints).unwrap(),
+            serde_json::to_value(&constraints2).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_intent_json_errors() {
+        // Constraints are optional
+        let goal_json = serde_json::to_string(&Goal::ClaimRewards).unwrap();
+        let (goal, constraints) = parse_intent_json(&format!(r#"{{"goal":{}}}"#, goal_json)).unwrap();
+        assert!(matches!(goal, Goal::ClaimRewards));
+        assert!(constraints.max_slippage.is_none());
+
+        let err = parse_intent_json(r#"{"goal":"Teleport"}"#).unwrap_err();
+        assert!(err.to_string().contains("unknown variant"), "{}", err);
+
+        let err = parse_intent_json(r#"{"goal":{"Unstake":{"amount":"lots"}}}"#).unwrap_err();
+        assert!(matches!(err, IntentError::ParseError(_)));
+        assert!(parse_intent_json(r#"{"constraints":{}}"#).is_err());
+    }
 }
@@ -0,0 +1,138 @@
+//! Recent-intent history for undo and one-tap replay.
+//!
+//! ```ignore
+//! let mut history = IntentHistory::new(20);
+//! history.push(parse_intent("swap 100 VXS for USDC")?);
+//! history.save("intents.json")?;
+//!
+//! let history = IntentHistory::load("intents.json")?;
+//! let (goal, constraints) = history.replay(0).unwrap().build()?;
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+
+use crate::intent::{IntentBuilder, IntentError};
+use crate::intent_parser::ParsedIntent;
+
+/// Bounded list of the most recent parsed intents, oldest first.
+///
+/// Once `capacity` is reached, pushing drops the oldest entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentHistory {
+    capacity: usize,
+    entries: VecDeque<ParsedIntent>,
+}
+
+impl IntentHistory {
+    /// Create an empty history holding at most `capacity` intents.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Record an intent, evicting the oldest one if the history is full.
+    pub fn push(&mut self, intent: ParsedIntent) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(intent);
+    }
+
+    /// The last `n` intents (fewer if the history is shorter), oldest first.
+    pub fn recent(&self, n: usize) -> impl DoubleEndedIterator<Item = &ParsedIntent> + ExactSizeIterator {
+        let start = self.entries.len().saturating_sub(n);
+        self.entries.range(start..)
+    }
+
+    /// Number of stored intents.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the history is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Rebuild the intent at `index` (0 = oldest) so it can be run again.
+    pub fn replay(&self, index: usize) -> Option<IntentBuilder> {
+        self.entries
+            .get(index)
+            .map(|p| IntentBuilder::from_goal(p.goal.clone(), p.constraints.clone()))
+    }
+
+    /// Save the history as JSON.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), IntentError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| IntentError::SerializationError(e.to_string()))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Load a history previously written by [`save`](Self::save).
+    ///
+    /// A file holding more entries than its `capacity` (e.g. edited by hand)
+    /// keeps only the newest `capacity` of them.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, IntentError> {
+        let contents = std::fs::read_to_string(path)?;
+        let mut history: Self = serde_json::from_str(&contents)
+            .map_err(|e| IntentError::SerializationError(e.to_string()))?;
+        let excess = history.entries.len().saturating_sub(history.capacity);
+        history.entries.drain(..excess);
+        Ok(history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intent_parser::parse_intent;
+    use vexidus_types::intent::Goal;
+    use vexidus_types::primitives::Address;
+
+    #[test]
+    fn test_save_load_replay() {
+        let mut history = IntentHistory::new(2);
+        history.push(parse_intent("stake 1000 VXS").unwrap());
+        history.push(parse_intent("swap 100 VXS for USDC with 2% slippage").unwrap());
+        history.push(parse_intent("stake 5 VXS").unwrap());
+
+        // Capacity 2: the first stake was evicted
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.recent(1).len(), 1);
+        assert_eq!(history.recent(10).len(), 2);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("intents.json");
+        history.save(&path).unwrap();
+        let loaded = IntentHistory::load(&path).unwrap();
+        assert_eq!(loaded.len(), 2);
+
+        let (goal, constraints) = loaded.replay(0).unwrap().build().unwrap();
+        match goal {
+            Goal::Swap { from_token, amount, .. } => {
+                assert_eq!(from_token, Address::ZERO);
+                assert_eq!(amount.0, 100_000_000_000);
+            }
+            _ => panic!("Expected Swap goal"),
+        }
+        assert_eq!(constraints.max_slippage, Some(2));
+        assert!(loaded.replay(2).is_none());
+
+        // An over-full file is cut back to its capacity, keeping the newest
+        let mut json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        json["capacity"] = 1.into();
+        std::fs::write(&path, json.to_string()).unwrap();
+        let loaded = IntentHistory::load(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        let (goal, _) = loaded.replay(0).unwrap().build().unwrap();
+        assert!(matches!(goal, Goal::Stake { .. }));
+    }
+}
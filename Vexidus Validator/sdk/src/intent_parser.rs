@@ -5,132 +5,678 @@
 //! - "swap 100 VXS for USDC"
 //! - "stake 1000 VXS"
 //! - "stake 500 VXS with validator Vx1abc..."
+//! - "send 50 VXS to Vx0abc..." / "transfer 10 USDC to chris.vex"
+//! - "pay 10 VXS to Vx0abc... every 30 days for 12 months"
 //!
-//! For LLM-based parsing (Grok, Claude), see the developer guide.
+//! Anything else becomes `Goal::Custom`. To hand those to an LLM (Grok,
+//! Claude) or another service, implement [`IntentResolver`] and call
+//! [`parse_intent_with_fallback`]; see the developer guide.
 
 use regex::Regex;
-use vexidus_types::intent::{Goal, Constraints};
-use vexidus_types::primitives::{Address, Amount};
-use crate::intent::IntentError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::BufRead;
+use std::path::Path;
+use vexidus_types::intent::{Goal, Constraints, RoutePreference};
+use vexidus_types::primitives::{Address, Amount, Timestamp};
+use crate::address_utils;
+use crate::intent::{self, IntentError, RecurringSchedule};
+use crate::wallet_client::WalletClient;
 
 /// Result of parsing a natural language intent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedIntent {
     pub goal: Goal,
     pub constraints: Constraints,
+    /// `.vex` name a transfer was addressed to. Until
+    /// [`resolve_recipient`](Self::resolve_recipient) fills it in, the
+    /// transfer's `to` is `Address::ZERO`, which
+    /// [`IntentBuilder::build`](crate::IntentBuilder::build) rejects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient_name: Option<String>,
+    /// Balance-relative amount ("half", "all", "25%") the goal was stated
+    /// in. Until [`resolve_amounts`](Self::resolve_amounts) converts it, the
+    /// goal's amount is zero, which [`IntentBuilder::build`](crate::IntentBuilder::build)
+    /// and [`bundle::from_goal`](crate::bundle::from_goal) reject.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_spec: Option<AmountSpec>,
+    /// Schedule of a recurring payment ("pay 10 VXS to Vx0... every month");
+    /// build it with [`IntentBuilder::recurring`](crate::IntentBuilder::recurring).
+    /// A recurring payment to a `.vex` name needs
+    /// [`resolve_recipient`](Self::resolve_recipient) first, like a one-off
+    /// transfer: its `to` is the same `Address::ZERO` placeholder.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<RecurringSchedule>,
 }
 
-/// Known token symbols → mint addresses.
-/// VXS is Address::ZERO (native token). Others are bridged token Blake3 hashes.
-fn resolve_token(symbol: &str) -> Option<Address> {
-    match symbol.to_uppercase().as_str() {
-        "VXS" | "VEXIDUS" => Some(Address::ZERO),
-        // Bridged tokens use deterministic Blake3 hash of "{chain}_{contract}"
-        // These are placeholder entries — real addresses computed at bridge time
-        "USDC" => Some(blake3_mint("ethereum_USDC")),
-        "USDT" => Some(blake3_mint("ethereum_USDT")),
-        "SOL" => Some(blake3_mint("solana_SOL")),
-        "ETH" | "WETH" => Some(blake3_mint("ethereum_ETH")),
-        "BTC" | "WBTC" => Some(blake3_mint("ethereum_WBTC")),
-        // Vexidus testnet tokens
-        "VXUSD" => Some(blake3_mint("vexidus_VXUSD")),
-        "VXBTC" => Some(blake3_mint("vexidus_VXBTC")),
-        "VXETH" => Some(blake3_mint("vexidus_VXETH")),
-        "VXAI" => Some(blake3_mint("vexidus_VXAI")),
-        "VMEME" => Some(blake3_mint("vexidus_VMEME")),
-        _ => None,
+/// How much of a token an intent moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AmountSpec {
+    Absolute(Amount),
+    /// Percentage (1-100) of the sender's balance.
+    Percent(u8),
+    /// The sender's whole balance.
+    All,
+}
+
+impl AmountSpec {
+    /// The raw amount this spec selects from `balance`.
+    pub fn resolve(&self, balance: u128) -> u128 {
+        match self {
+            AmountSpec::Absolute(amount) => amount.0,
+            AmountSpec::Percent(pct) => {
+                let pct = *pct as u128;
+                balance / 100 * pct + balance % 100 * pct / 100
+            }
+            AmountSpec::All => balance,
+        }
+    }
+}
+
+impl ParsedIntent {
+    /// Serialize to JSON, e.g. for logging or handing to another process.
+    pub fn to_json(&self) -> Result<String, IntentError> {
+        serde_json::to_string(self).map_err(|e| IntentError::SerializationError(e.to_string()))
+    }
+
+    /// Read back [`to_json`](Self::to_json) output.
+    pub fn from_json(json: &str) -> Result<ParsedIntent, IntentError> {
+        serde_json::from_str(json).map_err(|e| IntentError::SerializationError(e.to_string()))
+    }
+
+    /// Confirm every token mint the goal references is registered on-chain.
+    ///
+    /// The parser maps symbols to deterministic bridged mints that may not
+    /// exist on the connected network. Each mint (native VXS excluded) is
+    /// looked up with [`WalletClient::get_token_info`]. A null result or a
+    /// [not-found](crate::wallet_client::WalletClientError::is_not_found) error
+    /// yields [`IntentError::UnknownToken`]; any other failure comes back as
+    /// [`IntentError::Client`].
+    pub async fn verify_tokens(&self, client: &WalletClient) -> Result<(), IntentError> {
+        for token in intent::goal_tokens(&self.goal) {
+            let mint = format!("0x{}", hex::encode(token.0));
+            match client.get_token_info(&mint).await {
+                Ok(info) if !info.is_null() => {}
+                Ok(_) => return Err(IntentError::UnknownToken(token)),
+                Err(e) if e.is_not_found() => return Err(IntentError::UnknownToken(token)),
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve a `.vex` transfer recipient and write it into the goal.
+    ///
+    /// A no-op for intents without a [`recipient_name`](Self::recipient_name).
+    /// A name that doesn't resolve is [`IntentError::UnresolvedName`].
+    pub async fn resolve_recipient(&mut self, client: &WalletClient) -> Result<(), IntentError> {
+        let Some(name) = &self.recipient_name else {
+            return Ok(());
+        };
+        let resolved = client
+            .resolve_name(name)
+            .await?
+            .ok_or_else(|| IntentError::UnresolvedName(name.clone()))?;
+        let address = address_utils::parse_address(&resolved)
+            .map_err(|e| IntentError::ParseError(format!("{} resolved to {}: {}", name, resolved, e)))?;
+        if let Goal::Transfer { to, .. } = &mut self.goal {
+            *to = address;
+        }
+        self.recipient_name = None;
+        Ok(())
+    }
+
+    /// Convert a balance-relative [`amount_spec`](Self::amount_spec) into an
+    /// absolute goal amount.
+    ///
+    /// `balance_lookup` returns the sender's raw balance of a token (native
+    /// VXS is `Address::ZERO`); fetch balances first, e.g. with
+    /// [`WalletClient::get_balance`]. A no-op for intents with absolute amounts.
+    pub fn resolve_amounts<F>(&mut self, balance_lookup: F) -> Result<(), IntentError>
+    where
+        F: Fn(&Address) -> Option<u128>,
+    {
+        let Some(spec) = self.amount_spec else {
+            return Ok(());
+        };
+        let (token, amount) = match &mut self.goal {
+            Goal::Swap { from_token, amount, .. } => (*from_token, amount),
+            Goal::Stake { token, amount, .. } | Goal::Transfer { token, amount, .. } => (*token, amount),
+            _ => return Ok(()),
+        };
+        let balance = balance_lookup(&token).ok_or(IntentError::MissingBalance(token))?;
+        *amount = Amount(spec.resolve(balance));
+        self.amount_spec = None;
+        Ok(())
+    }
+}
+
+/// Structured intent input for form-based UIs (dropdowns instead of free text).
+///
+/// Token fields take the same symbols the text parser understands ("VXS",
+/// "USDC", ...). Amounts are human-readable decimal strings as typed, e.g.
+/// `"100.5"`, and are scaled exactly like parsed text.
+#[derive(Debug, Clone)]
+pub enum IntentForm {
+    Swap { from: String, to: String, amount: String, slippage: Option<u8> },
+    Stake { amount: String, validator: Option<String> },
+    ProvideLiquidity { token_a: String, amount_a: String, token_b: String, amount_b: String },
+    Bridge { token: String, amount: String, source_chain: String },
+    Register { name: String },
+}
+
+/// Decimals of native VXS, and of any token registered without its own.
+pub const DEFAULT_DECIMALS: u8 = 9;
+
+/// Symbols in [`TokenRegistry::default`] besides VXS: `(symbol, chain,
+/// contract, decimals)`, minted with [`address_utils::bridged_mint`]`(chain,
+/// contract)`. Bridged tokens keep their source chain's decimals; the Vexidus
+/// testnet tokens use VXS's 9. Also the table behind
+/// [`address_utils::bridged_mint_from_symbol`].
+pub(crate) const BUILTIN_TOKENS: &[(&str, &str, &str, u8)] = &[
+    ("USDC", "ethereum", "USDC", 6),
+    ("USDT", "ethereum", "USDT", 6),
+    ("SOL", "solana", "SOL", 9),
+    ("ETH", "ethereum", "ETH", 18),
+    ("WETH", "ethereum", "ETH", 18),
+    ("BTC", "ethereum", "WBTC", 8),
+    ("WBTC", "ethereum", "WBTC", 8),
+    // Vexidus testnet tokens
+    ("VXUSD", "vexidus", "VXUSD", 9),
+    ("VXBTC", "vexidus", "VXBTC", 9),
+    ("VXETH", "vexidus", "VXETH", 9),
+    ("VXAI", "vexidus", "VXAI", 9),
+    ("VMEME", "vexidus", "VMEME", 9),
+];
+
+/// Token symbol → mint address and decimals, used by the parser.
+///
+/// Symbols are case-insensitive. The default registry knows VXS (native,
+/// `Address::ZERO`, 9 decimals) and the bridged/testnet mints (USDC and USDT
+/// 6, WBTC 8, ETH 18); register more, e.g. from the node's token list at
+/// startup:
+///
+/// ```ignore
+/// let mut registry = TokenRegistry::default();
+/// for token in client.list_tokens(500).await?.as_array().into_iter().flatten() {
+///     if let (Some(symbol), Some(mint)) = (token["symbol"].as_str(), token["address"].as_str()) {
+///         let decimals = token["decimals"].as_u64().unwrap_or(9) as u8;
+///         registry.register_with_decimals(symbol, address_utils::parse_address(mint)?, decimals);
+///     }
+/// }
+/// let parsed = parse_intent_with_registry("swap 10 PEPE for VXS", &registry)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct TokenRegistry {
+    tokens: HashMap<String, (Address, u8)>,
+}
+
+impl TokenRegistry {
+    /// A registry with no symbols, not even VXS.
+    pub fn empty() -> Self {
+        Self { tokens: HashMap::new() }
+    }
+
+    /// Add or replace `symbol`, with [`DEFAULT_DECIMALS`].
+    pub fn register(&mut self, symbol: &str, address: Address) {
+        self.register_with_decimals(symbol, address, DEFAULT_DECIMALS);
+    }
+
+    /// Add or replace `symbol` with its decimals.
+    pub fn register_with_decimals(&mut self, symbol: &str, address: Address, decimals: u8) {
+        self.tokens.insert(symbol.to_uppercase(), (address, decimals));
+    }
+
+    pub fn resolve(&self, symbol: &str) -> Option<Address> {
+        self.tokens.get(&symbol.to_uppercase()).map(|(address, _)| *address)
+    }
+
+    /// Decimals of `symbol`, if registered.
+    pub fn decimals(&self, symbol: &str) -> Option<u8> {
+        self.tokens.get(&symbol.to_uppercase()).map(|(_, decimals)| *decimals)
+    }
+
+    /// Decimals of the mint at `address`, if any symbol registers it.
+    pub fn decimals_of(&self, address: &Address) -> Option<u8> {
+        self.tokens
+            .values()
+            .find(|(mint, _)| mint == address)
+            .map(|(_, decimals)| *decimals)
+    }
+}
+
+impl Default for TokenRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register("VXS", Address::ZERO);
+        registry.register("VEXIDUS", Address::ZERO);
+        for (symbol, chain, contract, decimals) in BUILTIN_TOKENS {
+            registry.register_with_decimals(symbol, address_utils::bridged_mint(chain, contract), *decimals);
+        }
+        registry
+    }
+}
+
+/// Convert a human-readable amount to raw units of a token with `decimals`
+/// places, exactly (see [`parse_amount`] for the accepted forms).
+///
+/// More fractional digits than the token has is an error, not a rounding.
+pub fn to_raw_amount(human: &str, decimals: u8) -> Result<u128, IntentError> {
+    scale_amount(human, decimals)
+        .ok_or_else(|| IntentError::ParseError(format!("Invalid amount for {} decimals: {}", decimals, human)))
+}
+
+/// Format a raw amount of a token with `decimals` places for display, without
+/// trailing zeros: `from_raw_amount(1_500_000, 6)` is `"1.5"`.
+pub fn from_raw_amount(raw: u128, decimals: u8) -> String {
+    let Some(scale) = 10u128.checked_pow(decimals as u32) else {
+        // More decimals than u128 has digits: all fraction
+        return format!("0.{:0>width$}", raw, width = decimals as usize)
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string();
+    };
+    let (int_part, frac_part) = (raw / scale, raw % scale);
+    if frac_part == 0 {
+        return int_part.to_string();
+    }
+    let frac = format!("{:0>width$}", frac_part, width = decimals as usize);
+    format!("{}.{}", int_part, frac.trim_end_matches('0'))
+}
+
+/// Convert a human-readable amount to raw units with `decimals` places.
+///
+/// Exact: the integer and fractional digits are combined as integers, never
+/// through `f64`. `None` on overflow, on malformed input, and when the amount
+/// has more fractional digits than `decimals` (after any suffix), rather than
+/// rounding them away.
+fn scale_amount(amount_str: &str, decimals: u8) -> Option<u128> {
+    let cleaned = strip_separators(amount_str);
+    let (number, suffix_exp) = split_suffix(&cleaned);
+    let places = decimals as u32 + suffix_exp;
+
+    let (int_part, frac_part) = number.split_once('.').unwrap_or((number, ""));
+    if (int_part.is_empty() && frac_part.is_empty())
+        || !int_part.chars().chain(frac_part.chars()).all(|c| c.is_ascii_digit())
+        || frac_part.len() > places as usize
+    {
+        return None;
+    }
+    let int_value: u128 = if int_part.is_empty() { 0 } else { int_part.parse().ok()? };
+    let frac_value: u128 = if frac_part.is_empty() { 0 } else { frac_part.parse().ok()? };
+    let frac_scale = 10u128.checked_pow(places - frac_part.len() as u32)?;
+    int_value
+        .checked_mul(10u128.checked_pow(places)?)?
+        .checked_add(frac_value.checked_mul(frac_scale)?)
+}
+
+fn strip_separators(s: &str) -> String {
+    s.trim().chars().filter(|c| *c != ',' && *c != '_').collect()
+}
+
+/// Split a `k`/`m`/`b`/`t` magnitude suffix off `s`, returning the power of ten
+/// it stands for (0 without a suffix).
+fn split_suffix(s: &str) -> (&str, u32) {
+    let exp = match s.chars().last().map(|c| c.to_ascii_lowercase()) {
+        Some('k') => 3,
+        Some('m') => 6,
+        Some('b') => 9,
+        Some('t') => 12,
+        _ => return (s, 0),
+    };
+    (&s[..s.len() - 1], exp)
+}
+
+/// Regex fragment for an amount as users type it: digits with optional `,`/`_`
+/// separators and decimals, plus an optional magnitude suffix. The suffix must
+/// touch the number, so in "5 kas" the `k` stays part of the token symbol.
+const AMOUNT_PATTERN: &str = r"(\d[\d,_]*\.?\d*(?i:[kmbt])?)";
+
+/// Compile `pattern` with each `<amount>` replaced by [`AMOUNT_PATTERN`].
+fn amount_regex(pattern: &str) -> Option<Regex> {
+    Regex::new(&pattern.replace("<amount>", AMOUNT_PATTERN)).ok()
+}
+
+/// Parse a human-readable amount: `"1,000"`, `"1_000_000"`, `"2.5"`, and
+/// case-insensitive `k`/`m`/`b`/`t` suffixes (`"1.5k"` = 1500).
+///
+/// `None` for anything else, including negative or non-finite values. For
+/// display and estimates only: raw amounts are scaled exactly, without `f64`.
+pub fn parse_amount(s: &str) -> Option<f64> {
+    let cleaned = strip_separators(s);
+    let (number, exp) = split_suffix(&cleaned);
+    let amount = number.parse::<f64>().ok()? * 10f64.powi(exp as i32);
+    if !amount.is_finite() || amount < 0.0 {
+        return None;
     }
+    Some(amount)
 }
 
-fn blake3_mint(unique_id: &str) -> Address {
-    let hash = blake3::hash(unique_id.as_bytes());
-    Address(*hash.as_bytes())
+/// Token knowledge for [`parse_intent_with`] when the built-in registry is
+/// incomplete.
+///
+/// Symbols are case-insensitive. Amounts are scaled by the decimals override
+/// for a symbol if set, else the registry's decimals, else 9.
+///
+/// ```ignore
+/// let opts = ParseOptions::new().with_token("PEPE", pepe_mint, 18);
+/// let parsed = parse_intent_with("swap 1.5 PEPE for VXS", &opts)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    registry: TokenRegistry,
+    decimals: HashMap<String, u8>,
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve symbols against `registry` instead of the default one.
+    pub fn with_registry(mut self, registry: TokenRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Teach the parser a symbol it doesn't know, with its mint and decimals.
+    pub fn with_token(mut self, symbol: &str, mint: Address, decimals: u8) -> Self {
+        self.registry.register(symbol, mint);
+        self.decimals.insert(symbol.to_uppercase(), decimals);
+        self
+    }
+
+    /// Override the decimals used to scale amounts of `symbol`.
+    pub fn with_decimals(mut self, symbol: &str, decimals: u8) -> Self {
+        self.decimals.insert(symbol.to_uppercase(), decimals);
+        self
+    }
+
+    fn resolve(&self, symbol: &str) -> Option<Address> {
+        self.registry.resolve(symbol)
+    }
+
+    fn decimals(&self, symbol: &str) -> u8 {
+        self.decimals
+            .get(&symbol.to_uppercase())
+            .copied()
+            .or_else(|| self.registry.decimals(symbol))
+            .unwrap_or(DEFAULT_DECIMALS)
+    }
+
+    fn raw_amount(&self, symbol: &str, amount_str: &str) -> Option<u128> {
+        scale_amount(amount_str, self.decimals(symbol))
+    }
 }
 
 /// Parse a natural language intent string into a Goal + Constraints.
 ///
 /// Supported patterns:
-/// - `swap <amount> <TOKEN_A> for <TOKEN_B>` (optional: `with <N>% slippage`)
+/// - `swap <amount> <TOKEN_A> for <TOKEN_B>` (optional, any order: slippage,
+///   deadline, pool/DEX route, and minimum output clauses)
 /// - `stake <amount> <TOKEN>` (optional: `with validator <address>`)
+/// - `send|transfer <amount> <TOKEN> to <address or name.vex>` (names are
+///   resolved later with [`ParsedIntent::resolve_recipient`])
 ///
 /// Returns `Err` if the string doesn't match any known pattern.
 pub fn parse_intent(text: &str) -> Result<ParsedIntent, IntentError> {
-    let text = text.trim().to_lowercase();
+    parse_intent_with(text, &ParseOptions::default())
+}
+
+/// [`parse_intent`] resolving token symbols against `registry`.
+pub fn parse_intent_with_registry(text: &str, registry: &TokenRegistry) -> Result<ParsedIntent, IntentError> {
+    parse_intent_with(text, &ParseOptions::new().with_registry(registry.clone()))
+}
 
-    // Try swap pattern: "swap 100 VXS for USDC" or "swap 100 vxs for usdc with 2% slippage"
-    if let Some(parsed) = try_parse_swap(&text) {
+/// [`parse_intent`] with extra token symbols and decimals overrides.
+pub fn parse_intent_with(text: &str, opts: &ParseOptions) -> Result<ParsedIntent, IntentError> {
+    if let Some(parsed) = parse_known(text, opts)? {
         return Ok(parsed);
     }
 
-    // Try liquidity pattern: "add 100 VXS and 500 USDC liquidity"
-    if let Some(parsed) = try_parse_liquidity(&text) {
-        return Ok(parsed);
+    // Fallback: wrap as Custom goal for future LLM processing
+    Ok(custom_intent(text))
+}
+
+fn custom_intent(text: &str) -> ParsedIntent {
+    ParsedIntent {
+        goal: Goal::Custom(text.trim().to_lowercase()),
+        constraints: Constraints::default(),
+        recipient_name: None,
+        amount_spec: None,
+        schedule: None,
     }
+}
 
-    // Try stake pattern: "stake 1000 VXS" or "stake 1000 VXS with validator Vx1..."
-    if let Some(parsed) = try_parse_stake(&text) {
-        return Ok(parsed);
+/// Parses intents the built-in patterns don't recognise, for
+/// [`parse_intent_with_fallback`]. Implement it over an LLM or any other
+/// service; write `async fn resolve` in the impl.
+pub trait IntentResolver {
+    fn resolve(&self, text: &str) -> impl Future<Output = Result<ParsedIntent, IntentError>> + Send;
+}
+
+/// Resolver that leaves unrecognised text as `Goal::Custom`, the same as
+/// [`parse_intent`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopResolver;
+
+impl IntentResolver for NoopResolver {
+    async fn resolve(&self, text: &str) -> Result<ParsedIntent, IntentError> {
+        Ok(custom_intent(text))
     }
+}
 
-    // Try bridge pattern: "bridge 10 SOL from solana"
-    if let Some(parsed) = try_parse_bridge(&text) {
-        return Ok(parsed);
+/// [`parse_intent`], but text that matches no built-in pattern goes to
+/// `resolver` instead of becoming `Goal::Custom`. The resolver is never called
+/// for recognised intents.
+pub async fn parse_intent_with_fallback<R: IntentResolver>(
+    text: &str,
+    resolver: &R,
+) -> Result<ParsedIntent, IntentError> {
+    match parse_known(text, &ParseOptions::default())? {
+        Some(parsed) => Ok(parsed),
+        None => resolver.resolve(text).await,
     }
+}
 
-    // Try bridge+action pattern: "bridge 10 SOL from solana and swap to VXS"
-    if let Some(parsed) = try_parse_bridge_and_action(&text) {
-        return Ok(parsed);
+/// Try each known pattern in turn; `Ok(None)` if nothing matches, `Err` if a
+/// pattern matched but one of its clauses is invalid.
+fn parse_known(text: &str, opts: &ParseOptions) -> Result<Option<ParsedIntent>, IntentError> {
+    // Balance-relative amounts: "swap half my VXS for USDC", "stake 50% of my VXS"
+    match try_parse_relative(text.trim(), opts) {
+        Some(parsed) => parsed.map(Some),
+        None => parse_absolute(text, opts),
     }
+}
 
-    // Try register pattern: "register chris.vex" or "register chris"
-    if let Some(parsed) = try_parse_register(&text) {
-        return Ok(parsed);
+fn parse_absolute(text: &str, opts: &ParseOptions) -> Result<Option<ParsedIntent>, IntentError> {
+    let original = text.trim();
+    let text = original.to_lowercase();
+
+    // Try swap pattern: "swap 100 VXS for USDC" plus optional modifiers in any
+    // order ("with 2% slippage", "expiring in 5 minutes", "using pool Vx1...")
+    if let Some(parsed) = try_parse_swap(original, opts) {
+        return parsed.map(Some);
     }
 
-    // Fallback: wrap as Custom goal for future LLM processing
-    Ok(ParsedIntent {
-        goal: Goal::Custom(text),
-        constraints: Constraints::default(),
-    })
+    // Try liquidity pattern: "add 100 VXS and 500 USDC liquidity"
+    if let Some(parsed) = try_parse_liquidity(&text, opts)
+        // Try transfer pattern: "send 50 VXS to Vx0abc...", "transfer 10 USDC to chris.vex",
+        // or recurring "pay 10 VXS to Vx0abc... every month"
+        .or_else(|| try_parse_transfer(original, opts))
+    {
+        return Ok(Some(parsed));
+    }
+
+    // Try stake pattern: "stake 1000 VXS" or "stake 1000 VXS with validator Vx1..."
+    if let Some(parsed) = try_parse_stake(&text, opts) {
+        return parsed.map(Some);
+    }
+
+    // Try bridge pattern: "bridge 10 SOL from solana"
+    Ok(try_parse_bridge(&text, opts)
+        // Try bridge+action pattern: "bridge 10 SOL from solana and swap to VXS"
+        .or_else(|| try_parse_bridge_and_action(&text, opts))
+        // Try register pattern: "register chris.vex" or "register chris"
+        .or_else(|| try_parse_register(&text)))
 }
 
-fn try_parse_swap(text: &str) -> Option<ParsedIntent> {
+/// Swap, stake, and transfer intents stated as `half`, `all`, or `N%` of a
+/// balance. The rest of the sentence is parsed as if the amount were 0, and the
+/// spec is recorded for [`ParsedIntent::resolve_amounts`].
+fn try_parse_relative(text: &str, opts: &ParseOptions) -> Option<Result<ParsedIntent, IntentError>> {
     let re = Regex::new(
-        r"swap\s+(\d+\.?\d*)\s+(\w+)\s+for\s+(\w+)(?:\s+with\s+(\d+)%?\s*slippage)?"
-    ).ok()?;
+        r"(?i)^(swap|stake|send|transfer)\s+(half|all|\d{1,3}\s*%)\s+(?:of\s+)?(?:my\s+)?(\w+)(.*)$",
+    )
+    .ok()?;
+    let caps = re.captures(text)?;
+    let spec = match caps.get(2)?.as_str().to_lowercase().as_str() {
+        "half" => AmountSpec::Percent(50),
+        "all" => AmountSpec::All,
+        pct => match pct.trim_end_matches('%').trim().parse::<u8>().ok()? {
+            p @ 1..=100 => AmountSpec::Percent(p),
+            _ => return None,
+        },
+    };
+
+    let rewritten = format!("{} 0 {}{}", &caps[1], &caps[3], &caps[4]);
+    let mut parsed = match parse_absolute(&rewritten, opts) {
+        Ok(parsed) => parsed?,
+        Err(e) => return Some(Err(e)),
+    };
+    if !matches!(parsed.goal, Goal::Swap { .. } | Goal::Stake { .. } | Goal::Transfer { .. }) {
+        return None;
+    }
+    parsed.amount_spec = Some(spec);
+    Some(Ok(parsed))
+}
+
+/// `None` if the text isn't a swap; `Some(Err)` if it is but a modifier
+/// clause is invalid.
+fn try_parse_swap(text: &str, opts: &ParseOptions) -> Option<Result<ParsedIntent, IntentError>> {
+    // Core clause only; modifiers are picked out of whatever follows it.
+    // Case-insensitive on the original text so pool/DEX addresses keep their case.
+    let re = amount_regex(r"(?i)swap\s+<amount>\s+(\w+)\s+for\s+(\w+)")?;
 
     let caps = re.captures(text)?;
     let amount_str = caps.get(1)?.as_str();
     let from_symbol = caps.get(2)?.as_str();
     let to_symbol = caps.get(3)?.as_str();
-    let slippage = caps.get(4).and_then(|m| m.as_str().parse::<u8>().ok());
 
-    let amount: f64 = amount_str.parse().ok()?;
-    let from_token = resolve_token(from_symbol)?;
-    let to_token = resolve_token(to_symbol)?;
+    let from_token = opts.resolve(from_symbol)?;
+    let to_token = opts.resolve(to_symbol)?;
+    if from_token == to_token {
+        // e.g. ETH for WETH: same mint, nothing to swap
+        return None;
+    }
 
-    // Convert to raw amount (9 decimals for VXS)
-    let raw_amount = (amount * 1_000_000_000.0) as u128;
+    // Convert to raw amount in the input token's decimals
+    let raw_amount = opts.raw_amount(from_symbol, amount_str)?;
 
     let mut constraints = Constraints::default();
-    if let Some(s) = slippage {
-        constraints.max_slippage = Some(s);
+    let rest = &text[caps.get(0)?.end()..];
+    if let Err(e) = apply_swap_modifiers(rest, opts.decimals(to_symbol), &mut constraints) {
+        return Some(Err(e));
     }
 
-    Some(ParsedIntent {
+    Some(Ok(ParsedIntent {
         goal: Goal::Swap {
             from_token,
             to_token,
             amount: Amount(raw_amount),
         },
         constraints,
-    })
+        recipient_name: None,
+        amount_spec: None,
+        schedule: None,
+    }))
+}
+
+/// Scan the text after a swap's core clause for modifier clauses, in any order:
+///
+/// - slippage: `with 2% slippage`, `slippage 2%`
+/// - deadline: `expiring in 5 minutes`, `within 1 hour`, `deadline 30s`
+/// - route: `using pool <address>`, `via dex <address>`
+/// - minimum output: `min output 190`, `at least 190 USDC`
+///
+/// Each recognized clause is consumed so its numbers can't be re-read by
+/// another clause; unrecognized text is ignored, but a recognized clause with
+/// a bad value (an address that doesn't parse, a minimum output the output
+/// token can't hold) is an error rather than being dropped. The minimum output
+/// is scaled by `out_decimals`, the output token's. Slippage is a whole
+/// percent up to 100, so "0.5% slippage" and "150% slippage" are errors too.
+fn apply_swap_modifiers(rest: &str, out_decimals: u8, constraints: &mut Constraints) -> Result<(), IntentError> {
+    let mut rest = rest.to_string();
+    let mut take = |pattern: &str| -> Option<Vec<String>> {
+        let re = Regex::new(pattern).ok()?;
+        let caps = re.captures(&rest)?;
+        let groups = caps
+            .iter()
+            .skip(1)
+            .map(|m| m.map(|m| m.as_str().to_string()).unwrap_or_default())
+            .collect();
+        let range = caps.get(0)?.range();
+        rest.replace_range(range.clone(), &" ".repeat(range.len()));
+        Some(groups)
+    };
+
+    // Addresses first — they can contain digits the other clauses would grab
+    let address = |kind: &str, s: &str| {
+        address_utils::parse_address(s)
+            .map_err(|e| IntentError::ParseError(format!("Invalid {} address {}: {}", kind, s, e)))
+    };
+    if let Some(g) = take(r"(?i)(?:using|via|through|on)\s+pool\s+(\S+)") {
+        constraints.preferred_route = RoutePreference::ExactPool(address("pool", &g[0])?);
+    }
+    if let Some(g) = take(r"(?i)(?:using|via|through|on)\s+dex\s+(\S+)") {
+        constraints.preferred_route = RoutePreference::PreferDex(address("dex", &g[0])?);
+    }
+
+    if let Some(g) = take(
+        r"(?i)(?:expir(?:ing|es|y)\s+in|within|deadline(?:\s+of|\s+in)?)\s+(\d+)\s*(seconds?|secs?|s|minutes?|mins?|m|hours?|hrs?|h|days?|d)\b",
+    ) {
+        if let Ok(n) = g[0].parse::<u64>() {
+            let unit = match g[1].to_lowercase().chars().next() {
+                Some('m') => 60,
+                Some('h') => 3_600,
+                Some('d') => 86_400,
+                _ => 1,
+            };
+            constraints.deadline = Some(Timestamp::now() + n.saturating_mul(unit));
+        }
+    }
+
+    if let Some(g) = take(
+        r"(?i)(?:with\s+)?(\d+(?:\.\d+)?)\s*%?\s*slippage|slippage\s+(?:of\s+)?(\d+(?:\.\d+)?)\s*%?",
+    ) {
+        let value = if g[0].is_empty() { &g[1] } else { &g[0] };
+        let slippage = match value.parse::<u8>() {
+            Ok(s) if s <= 100 => s,
+            Ok(s) => return Err(IntentError::InvalidSlippage(s)),
+            Err(_) if value.contains('.') => {
+                return Err(IntentError::ParseError(format!("Slippage must be a whole percent, got {}%", value)))
+            }
+            Err(_) => return Err(IntentError::ParseError(format!("Slippage must be at most 100%, got {}%", value))),
+        };
+        constraints.max_slippage = Some(slippage);
+    }
+
+    let min_output = r"(?i)(?:min(?:imum)?(?:\s+output|\s+out)?|at\s+least)\s+<amount>";
+    if let Some(g) = take(&min_output.replace("<amount>", AMOUNT_PATTERN)) {
+        let raw = scale_amount(&g[0], out_decimals).ok_or_else(|| {
+            IntentError::ParseError(format!("Invalid minimum output for {} decimals: {}", out_decimals, g[0]))
+        })?;
+        constraints.min_output = Some(Amount(raw));
+    }
+    Ok(())
 }
 
-fn try_parse_liquidity(text: &str) -> Option<ParsedIntent> {
+fn try_parse_liquidity(text: &str, opts: &ParseOptions) -> Option<ParsedIntent> {
     // "add 100 VXS and 500 USDC liquidity" or "provide 100 vxs and 500 usdc liquidity"
-    let re = Regex::new(
-        r"(?:add|provide)\s+(\d+\.?\d*)\s+(\w+)\s+and\s+(\d+\.?\d*)\s+(\w+)\s+liquidity"
-    ).ok()?;
+    let re = amount_regex(
+        r"(?:add|provide)\s+<amount>\s+(\w+)\s+and\s+<amount>\s+(\w+)\s+liquidity"
+    )?;
 
     let caps = re.captures(text)?;
     let amount_a_str = caps.get(1)?.as_str();
@@ -138,13 +684,11 @@ fn try_parse_liquidity(text: &str) -> Option<ParsedIntent> {
     let amount_b_str = caps.get(3)?.as_str();
     let symbol_b = caps.get(4)?.as_str();
 
-    let amount_a: f64 = amount_a_str.parse().ok()?;
-    let amount_b: f64 = amount_b_str.parse().ok()?;
-    let token_a = resolve_token(symbol_a)?;
-    let token_b = resolve_token(symbol_b)?;
+    let token_a = opts.resolve(symbol_a)?;
+    let token_b = opts.resolve(symbol_b)?;
 
-    let raw_a = (amount_a * 1_000_000_000.0) as u128;
-    let raw_b = (amount_b * 1_000_000_000.0) as u128;
+    let raw_a = opts.raw_amount(symbol_a, amount_a_str)?;
+    let raw_b = opts.raw_amount(symbol_b, amount_b_str)?;
 
     Some(ParsedIntent {
         goal: Goal::ProvideLiquidity {
@@ -154,36 +698,131 @@ fn try_parse_liquidity(text: &str) -> Option<ParsedIntent> {
             amount_b: Amount(raw_b),
         },
         constraints: Constraints::default(),
+        recipient_name: None,
+        amount_spec: None,
+        schedule: None,
     })
 }
 
-fn try_parse_stake(text: &str) -> Option<ParsedIntent> {
+fn try_parse_transfer(text: &str, opts: &ParseOptions) -> Option<ParsedIntent> {
+    // Matched on the original text: base58 recipients are case-sensitive
+    let re = amount_regex(r"(?i)(?:send|transfer|pay)\s+<amount>\s+(\w+)\s+to\s+(\S+)")?;
+
+    let caps = re.captures(text)?;
+    let amount_str = caps.get(1)?.as_str();
+    let symbol = caps.get(2)?.as_str();
+    let recipient = caps.get(3)?.as_str();
+
+    let token = opts.resolve(symbol)?;
+    let raw_amount = opts.raw_amount(symbol, amount_str)?;
+
+    // `.vex` names need an RPC lookup; leave that to `resolve_recipient`
+    let (to, recipient_name) = if recipient.to_lowercase().ends_with(".vex") {
+        let lower = recipient.to_lowercase();
+        let name = normalize_vns_name(&lower)?;
+        (Address::ZERO, Some(format!("{}.vex", name)))
+    } else {
+        (address_utils::parse_address(recipient).ok()?, None)
+    };
+
+    // "every month", "every 30 days for 12 months"
+    let rest = &text[caps.get(0)?.end()..];
+    let schedule = if Regex::new(r"(?i)\bevery\b").ok()?.is_match(rest) {
+        Some(parse_schedule(rest)?)
+    } else {
+        None
+    };
+
+    Some(ParsedIntent {
+        goal: Goal::Transfer { to, token, amount: Amount(raw_amount) },
+        constraints: Constraints::default(),
+        recipient_name,
+        amount_spec: None,
+        schedule,
+    })
+}
+
+/// Parse "every [N] <unit> [for N times|<unit>s]". Months are 30 days and
+/// years 365. Without a `for` clause the count is `u32::MAX`, i.e. until
+/// cancelled.
+fn parse_schedule(text: &str) -> Option<RecurringSchedule> {
     let re = Regex::new(
-        r"stake\s+(\d+\.?\d*)\s+(\w+)(?:\s+with\s+validator\s+(\S+))?"
+        r"(?i)\bevery\s+(?:(\d+)\s+)?(second|minute|hour|day|week|month|year)s?\b(?:\s+for\s+(\d+)\s+(times|payments|(?:second|minute|hour|day|week|month|year)s?)\b)?"
     ).ok()?;
+    let caps = re.captures(text)?;
+    let every: u64 = caps.get(1).map_or(Some(1), |m| m.as_str().parse().ok())?;
+    let interval_secs = every.checked_mul(unit_secs(caps.get(2)?.as_str())?)?;
+    if interval_secs == 0 {
+        return None;
+    }
+
+    let count = match (caps.get(3), caps.get(4)) {
+        (Some(n), Some(unit)) => {
+            let n: u64 = n.as_str().parse().ok()?;
+            let unit = unit.as_str().to_lowercase();
+            if unit == "times" || unit == "payments" {
+                n
+            } else {
+                n.checked_mul(unit_secs(unit.trim_end_matches('s'))?)? / interval_secs
+            }
+        }
+        _ => u32::MAX as u64,
+    };
+    if count == 0 {
+        return None;
+    }
+    Some(RecurringSchedule { interval_secs, count: u32::try_from(count).ok()? })
+}
+
+fn unit_secs(unit: &str) -> Option<u64> {
+    match unit.to_lowercase().as_str() {
+        "second" => Some(1),
+        "minute" => Some(60),
+        "hour" => Some(3_600),
+        "day" => Some(86_400),
+        "week" => Some(604_800),
+        "month" => Some(30 * 86_400),
+        "year" => Some(365 * 86_400),
+        _ => None,
+    }
+}
+
+/// `None` if the text isn't a stake of a known token; `Some(Err)` if it stakes
+/// anything but VXS, the only token the chain stakes.
+fn try_parse_stake(text: &str, opts: &ParseOptions) -> Option<Result<ParsedIntent, IntentError>> {
+    let re = amount_regex(
+        r"stake\s+<amount>\s+(\w+)(?:\s+with\s+validator\s+(\S+))?"
+    )?;
 
     let caps = re.captures(text)?;
     let amount_str = caps.get(1)?.as_str();
     let token_symbol = caps.get(2)?.as_str();
     let validator_str = caps.get(3).map(|m| m.as_str());
 
-    let amount: f64 = amount_str.parse().ok()?;
-    let _token = resolve_token(token_symbol)?; // Validate token exists
+    if opts.resolve(token_symbol)? != Address::ZERO {
+        return Some(Err(IntentError::ParseError(format!(
+            "Can't stake {}: only VXS can be staked",
+            token_symbol.to_uppercase()
+        ))));
+    }
 
-    let raw_amount = (amount * 1_000_000_000.0) as u128;
+    let raw_amount = opts.raw_amount("VXS", amount_str)?;
 
     let validator = validator_str.and_then(|v| {
-        crate::address_utils::parse_address(v).ok()
+        address_utils::parse_address(v).ok()
     });
 
-    Some(ParsedIntent {
+    Some(Ok(ParsedIntent {
         goal: Goal::Stake {
             token: Address::ZERO, // Staking is always VXS
             amount: Amount(raw_amount),
             validator,
         },
         constraints: Constraints::default(),
-    })
+        recipient_name: None,
+        amount_spec: None,
+        schedule: None,
+    }))
 }
 
 /// Known chain name aliases → canonical chain names
@@ -200,11 +839,11 @@ fn resolve_chain(name: &str) -> Option<String> {
     }
 }
 
-fn try_parse_bridge(text: &str) -> Option<ParsedIntent> {
+fn try_parse_bridge(text: &str, opts: &ParseOptions) -> Option<ParsedIntent> {
     // "bridge 10 SOL from solana"
-    let re = Regex::new(
-        r"bridge\s+(\d+\.?\d*)\s+(\w+)\s+from\s+(\w+)"
-    ).ok()?;
+    let re = amount_regex(
+        r"bridge\s+<amount>\s+(\w+)\s+from\s+(\w+)"
+    )?;
 
     let caps = re.captures(text)?;
     let amount_str = caps.get(1)?.as_str();
@@ -216,9 +855,8 @@ fn try_parse_bridge(text: &str) -> Option<ParsedIntent> {
         return None;
     }
 
-    let amount: f64 = amount_str.parse().ok()?;
     let chain = resolve_chain(chain_name)?;
-    let raw_amount = (amount * 1_000_000_000.0) as u128;
+    let raw_amount = opts.raw_amount(&token_symbol, amount_str)?;
 
     Some(ParsedIntent {
         goal: Goal::Bridge {
@@ -228,14 +866,17 @@ fn try_parse_bridge(text: &str) -> Option<ParsedIntent> {
             proof: vexidus_types::bridge::BridgeProofType::Legacy,
         },
         constraints: Constraints::default(),
+        recipient_name: None,
+        amount_spec: None,
+        schedule: None,
     })
 }
 
-fn try_parse_bridge_and_action(text: &str) -> Option<ParsedIntent> {
+fn try_parse_bridge_and_action(text: &str, opts: &ParseOptions) -> Option<ParsedIntent> {
     // "bridge 10 SOL from solana and swap to VXS"
-    let re = Regex::new(
-        r"bridge\s+(\d+\.?\d*)\s+(\w+)\s+from\s+(\w+)\s+(?:and|then)\s+swap\s+(?:to|for)\s+(\w+)"
-    ).ok()?;
+    let re = amount_regex(
+        r"bridge\s+<amount>\s+(\w+)\s+from\s+(\w+)\s+(?:and|then)\s+swap\s+(?:to|for)\s+(\w+)"
+    )?;
 
     let caps = re.captures(text)?;
     let amount_str = caps.get(1)?.as_str();
@@ -243,13 +884,12 @@ fn try_parse_bridge_and_action(text: &str) -> Option<ParsedIntent> {
     let chain_name = caps.get(3)?.as_str();
     let to_symbol = caps.get(4)?.as_str();
 
-    let amount: f64 = amount_str.parse().ok()?;
     let chain = resolve_chain(chain_name)?;
-    let raw_amount = (amount * 1_000_000_000.0) as u128;
+    let raw_amount = opts.raw_amount(&token_symbol, amount_str)?;
 
     // Resolve the bridge token's mint address for swap
-    let from_token = resolve_token(&token_symbol)?;
-    let to_token = resolve_token(to_symbol)?;
+    let from_token = opts.resolve(&token_symbol)?;
+    let to_token = opts.resolve(to_symbol)?;
 
     Some(ParsedIntent {
         goal: Goal::Composite(vec![
@@ -266,6 +906,9 @@ fn try_parse_bridge_and_action(text: &str) -> Option<ParsedIntent> {
             },
         ]),
         constraints: Constraints::default(),
+        recipient_name: None,
+        amount_spec: None,
+        schedule: None,
     })
 }
 
@@ -273,21 +916,129 @@ fn try_parse_register(text: &str) -> Option<ParsedIntent> {
     // "register chris.vex" or "register chris" or "register my-name"
     let re = Regex::new(r"register\s+(\S+)").ok()?;
     let caps = re.captures(text)?;
-    let name = caps.get(1)?.as_str();
-    // Strip .vex suffix if present, normalize
-    let normalized = name.strip_suffix(".vex").unwrap_or(name);
-
-    // Validate: 3-64 chars, alphanumeric + hyphen
-    if normalized.len() < 3 || normalized.len() > 64 { return None; }
-    if !normalized.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') { return None; }
-    if normalized.starts_with('-') || normalized.ends_with('-') { return None; }
+    let normalized = normalize_vns_name(caps.get(1)?.as_str())?;
 
     Some(ParsedIntent {
         goal: Goal::Custom(format!("register_vns:{}", normalized)),
         constraints: Constraints::default(),
+        recipient_name: None,
+        amount_spec: None,
+        schedule: None,
     })
 }
 
+/// Strip a `.vex` suffix and validate a VNS name: 3-64 chars, alphanumeric +
+/// hyphen, no leading/trailing hyphen.
+fn normalize_vns_name(name: &str) -> Option<&str> {
+    let normalized = name.strip_suffix(".vex").unwrap_or(name);
+    if normalized.len() < 3 || normalized.len() > 64 { return None; }
+    if !normalized.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') { return None; }
+    if normalized.starts_with('-') || normalized.ends_with('-') { return None; }
+    Some(normalized)
+}
+
+/// Build a validated intent from structured form input.
+///
+/// Applies the same token resolution, amount scaling, and name rules as
+/// [`parse_intent`], without going through regex. Unlike the text parser it
+/// never falls back to `Goal::Custom` — bad input is an error.
+pub fn from_form(form: IntentForm) -> Result<ParsedIntent, IntentError> {
+    let registry = TokenRegistry::default();
+    let token = |symbol: &str| {
+        registry.resolve(symbol).ok_or_else(|| IntentError::ParseError(format!("Unknown token: {}", symbol)))
+    };
+    let amount = |value: &str, symbol: &str| {
+        let decimals = registry.decimals(symbol).unwrap_or(DEFAULT_DECIMALS);
+        scale_amount(value, decimals).ok_or_else(|| IntentError::ParseError(format!("Invalid amount: {}", value)))
+    };
+
+    let mut constraints = Constraints::default();
+    let goal = match form {
+        IntentForm::Swap { from, to, amount: value, slippage } => {
+            if let Some(s) = slippage {
+                if s > 100 {
+                    return Err(IntentError::InvalidSlippage(s));
+                }
+            }
+            constraints.max_slippage = slippage;
+            let (from_token, to_token) = (token(&from)?, token(&to)?);
+            if from_token == to_token {
+                return Err(IntentError::ParseError(format!("Can't swap {} for {}: same token", from, to)));
+            }
+            Goal::Swap { from_token, to_token, amount: Amount(amount(&value, &from)?) }
+        }
+        IntentForm::Stake { amount: value, validator } => {
+            let validator = validator
+                .map(|v| {
+                    address_utils::parse_address(&v)
+                        .map_err(|e| IntentError::ParseError(e.to_string()))
+                })
+                .transpose()?;
+            Goal::Stake {
+                token: Address::ZERO, // Staking is always VXS
+                amount: Amount(amount(&value, "VXS")?),
+                validator,
+            }
+        }
+        IntentForm::ProvideLiquidity { token_a, amount_a, token_b, amount_b } => {
+            Goal::ProvideLiquidity {
+                token_a: token(&token_a)?,
+                token_b: token(&token_b)?,
+                amount_a: Amount(amount(&amount_a, &token_a)?),
+                amount_b: Amount(amount(&amount_b, &token_b)?),
+            }
+        }
+        IntentForm::Bridge { token: symbol, amount: value, source_chain } => {
+            let chain = resolve_chain(&source_chain)
+                .ok_or_else(|| IntentError::ParseError(format!("Unknown chain: {}", source_chain)))?;
+            Goal::Bridge {
+                source_chain: chain,
+                token_symbol: symbol.to_uppercase(),
+                amount: Amount(amount(&value, &symbol)?),
+                proof: vexidus_types::bridge::BridgeProofType::Legacy,
+            }
+        }
+        IntentForm::Register { name } => {
+            let lower = name.to_lowercase();
+            let normalized = normalize_vns_name(&lower)
+                .ok_or_else(|| IntentError::ParseError(format!("Invalid VNS name: {}", name)))?;
+            Goal::Custom(format!("register_vns:{}", normalized))
+        }
+    };
+
+    Ok(ParsedIntent { goal, constraints, recipient_name: None, amount_spec: None, schedule: None })
+}
+
+/// Parse a file of one intent per line; see [`parse_reader`].
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<BatchResults, IntentError> {
+    let file = std::fs::File::open(path)?;
+    parse_reader(std::io::BufReader::new(file))
+}
+
+/// Each non-empty, non-comment line paired with its parse result.
+pub type BatchResults = Vec<(String, Result<ParsedIntent, IntentError>)>;
+
+/// Parse one intent per line, in order, pairing each line with its result.
+///
+/// Blank lines and `#` comments are skipped. Unlike [`parse_intent`], a line
+/// matching no known pattern is an error rather than a `Goal::Custom`, since a
+/// batch can't execute it. Only I/O errors abort the whole read.
+pub fn parse_reader<R: BufRead>(reader: R) -> Result<BatchResults, IntentError> {
+    let mut results = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let text = line.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let parsed = parse_known(text, &ParseOptions::default()).and_then(|parsed| {
+            parsed.ok_or_else(|| IntentError::ParseError(format!("Unrecognized intent: {}", text)))
+        });
+        results.push((text.to_string(), parsed));
+    }
+    Ok(results)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,16 +1056,284 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_swap_same_token() {
+        assert!(matches!(parse_intent("swap 1 VXS for VXS").unwrap().goal, Goal::Custom(_)));
+        // Different symbols, same bridged mint
+        assert!(matches!(parse_intent("swap 1 ETH for WETH").unwrap().goal, Goal::Custom(_)));
+        let form = IntentForm::Swap { from: "BTC".into(), to: "WBTC".into(), amount: "1".into(), slippage: None };
+        assert!(from_form(form).is_err());
+    }
+
     #[test]
     fn test_parse_swap_with_slippage() {
         let result = parse_intent("swap 50 ETH for VXS with 3% slippage").unwrap();
         match result.goal {
             Goal::Swap { amount, .. } => {
-                assert_eq!(amount, Amount(50_000_000_000));
+                assert_eq!(amount, Amount(50_000_000_000_000_000_000));
             }
             _ => panic!("Expected Swap"),
         }
         assert_eq!(result.constraints.max_slippage, Some(3));
+
+        // max_slippage is a whole percent: fractions are rejected, not dropped
+        for input in ["swap 50 ETH for VXS with 0.5% slippage", "swap 50 ETH for VXS slippage of 0.5%"] {
+            match parse_intent(input) {
+                Err(IntentError::ParseError(msg)) => assert!(msg.contains("0.5"), "{}", msg),
+                other => panic!("Expected ParseError for {}, got {:?}", input, other),
+            }
+        }
+        assert!(parse_intent("swap half my VXS for USDC with 0.5% slippage").is_err());
+        assert!(parse_intent("swap 50 ETH for VXS with 300% slippage").is_err());
+        assert!(matches!(
+            parse_intent("swap 50 ETH for VXS with 150% slippage"),
+            Err(IntentError::InvalidSlippage(150))
+        ));
+        assert_eq!(parse_intent("swap 50 ETH for VXS with 100% slippage").unwrap().constraints.max_slippage, Some(100));
+    }
+
+    #[test]
+    fn test_parse_swap_modifiers_any_order() {
+        let pool = address_utils::vx0_from_pubkey(&[7u8; 32]);
+        let pool_addr = address_utils::parse_address(&pool).unwrap();
+        let before = Timestamp::now().0;
+
+        let inputs = [
+            format!("swap 100 VXS for USDC with 2% slippage expiring in 5 minutes using pool {} min output 190", pool),
+            format!("swap 100 VXS for USDC using pool {} min output 190 expiring in 5 minutes with 2% slippage", pool),
+            format!("swap 100 VXS for USDC at least 190 USDC within 5 min slippage 2% via pool {}", pool),
+        ];
+        for input in &inputs {
+            let result = parse_intent(input).unwrap();
+            assert!(matches!(result.goal, Goal::Swap { .. }), "{}", input);
+            let c = result.constraints;
+            assert_eq!(c.max_slippage, Some(2), "{}", input);
+            assert_eq!(c.min_output, Some(Amount(190_000_000)), "{}", input); // USDC has 6 decimals
+            let deadline = c.deadline.expect("deadline").0;
+            assert!(deadline >= before + 300 && deadline <= Timestamp::now().0 + 300, "{}", input);
+            match c.preferred_route {
+                RoutePreference::ExactPool(addr) => assert_eq!(addr, pool_addr),
+                _ => panic!("Expected ExactPool for {}", input),
+            }
+        }
+
+        // Recognized clauses with bad values are reported, not skipped
+        for input in [
+            "swap 100 VXS for USDC using pool not-an-address",
+            "swap 100 VXS for USDC via dex 0x1234",
+            "swap 100 VXS for USDC min output 1.1234567", // USDC has 6 decimals
+        ] {
+            assert!(matches!(parse_intent(input), Err(IntentError::ParseError(_))), "{}", input);
+        }
+
+        // Leftover text is ignored
+        let result = parse_intent("swap 1 VXS for USDC please, quickly").unwrap();
+        assert_eq!(result.constraints.max_slippage, None);
+        assert!(result.constraints.deadline.is_none());
+    }
+
+    #[test]
+    fn test_parse_with_decimals_override() {
+        let pepe = Address([8u8; 32]);
+        // Unknown without options
+        assert!(matches!(parse_intent("swap 1.5 PEPE for VXS").unwrap().goal, Goal::Custom(_)));
+
+        let opts = ParseOptions::new()
+            .with_token("PEPE", pepe, 18)
+            .with_decimals("USDC", 6);
+        match parse_intent_with("swap 1.5 PEPE for VXS", &opts).unwrap().goal {
+            Goal::Swap { from_token, amount, .. } => {
+                assert_eq!(from_token, pepe);
+                assert_eq!(amount, Amount(1_500_000_000_000_000_000));
+            }
+            _ => panic!("Expected Swap"),
+        }
+        match parse_intent_with("add 100 VXS and 250 USDC liquidity", &opts).unwrap().goal {
+            Goal::ProvideLiquidity { amount_a, amount_b, .. } => {
+                assert_eq!(amount_a, Amount(100_000_000_000));
+                assert_eq!(amount_b, Amount(250_000_000));
+            }
+            _ => panic!("Expected ProvideLiquidity"),
+        }
+    }
+
+    #[test]
+    fn test_parse_amount_suffixes_and_separators() {
+        assert_eq!(parse_amount("1.5k"), Some(1_500.0));
+        assert_eq!(parse_amount("2M"), Some(2_000_000.0));
+        assert_eq!(parse_amount("1,000"), Some(1_000.0));
+        assert_eq!(parse_amount("1_000_000"), Some(1_000_000.0));
+        assert_eq!(parse_amount("3b"), Some(3e9));
+        assert_eq!(parse_amount("42"), Some(42.0));
+        for bad in ["", "k", "1.5x", "-1", "nan", "inf"] {
+            assert_eq!(parse_amount(bad), None, "{}", bad);
+        }
+
+        match parse_intent("swap 1.5k VXS for USDC").unwrap().goal {
+            Goal::Swap { amount, .. } => assert_eq!(amount, Amount(1_500_000_000_000)),
+            _ => panic!("Expected Swap"),
+        }
+        match parse_intent("stake 1,000,000 VXS").unwrap().goal {
+            Goal::Stake { amount, .. } => assert_eq!(amount, Amount(1_000_000_000_000_000)),
+            _ => panic!("Expected Stake"),
+        }
+        match parse_intent("add 2m VXS and 1_000_000 USDC liquidity").unwrap().goal {
+            Goal::ProvideLiquidity { amount_a, amount_b, .. } => {
+                assert_eq!(amount_a, Amount(2_000_000_000_000_000));
+                assert_eq!(amount_b, Amount(1_000_000_000_000));
+            }
+            _ => panic!("Expected ProvideLiquidity"),
+        }
+        match parse_intent("bridge 2.5K SOL from solana").unwrap().goal {
+            Goal::Bridge { amount, .. } => assert_eq!(amount, Amount(2_500_000_000_000)),
+            _ => panic!("Expected Bridge"),
+        }
+
+        // A symbol starting with a suffix letter isn't read as a suffix
+        let opts = ParseOptions::new().with_token("KAS", Address([9u8; 32]), 9);
+        match parse_intent_with("swap 2 KAS for VXS", &opts).unwrap().goal {
+            Goal::Swap { from_token, amount, .. } => {
+                assert_eq!(from_token, Address([9u8; 32]));
+                assert_eq!(amount, Amount(2_000_000_000));
+            }
+            _ => panic!("Expected Swap"),
+        }
+    }
+
+    #[test]
+    fn test_raw_amount_conversion_is_exact() {
+        assert_eq!(scale_amount("123456789.123456789", 9), Some(123_456_789_123_456_789));
+        assert_eq!(scale_amount("0.000000001", 9), Some(1));
+        assert_eq!(scale_amount("0.0000000001", 9), None);
+        assert_eq!(scale_amount(".5", 9), Some(500_000_000));
+        assert_eq!(scale_amount("7.", 9), Some(7_000_000_000));
+        assert_eq!(scale_amount("1.000000000001k", 9), Some(1_000_000_000_001));
+        for bad in ["", ".", "1.2.3", "-1", "1e5", "abc"] {
+            assert_eq!(scale_amount(bad, 9), None, "{}", bad);
+        }
+
+        // u128::MAX is 340282366920938463463374607431.768211455 at 9 decimals
+        assert_eq!(scale_amount("340282366920938463463374607431.768211455", 9), Some(u128::MAX));
+        assert_eq!(scale_amount("340282366920938463463374607431.768211456", 9), None);
+        assert_eq!(scale_amount("340282366920938463463374607432", 9), None);
+
+        match parse_intent("swap 123456789.123456789 VXS for USDC").unwrap().goal {
+            Goal::Swap { amount, .. } => assert_eq!(amount, Amount(123_456_789_123_456_789)),
+            _ => panic!("Expected Swap"),
+        }
+        // Too precise: falls through in text, errors in forms
+        assert!(matches!(parse_intent("stake 0.0000000001 VXS").unwrap().goal, Goal::Custom(_)));
+        assert!(from_form(IntentForm::Stake { amount: "0.0000000001".into(), validator: None }).is_err());
+    }
+
+    #[test]
+    fn test_per_token_decimals() {
+        let registry = TokenRegistry::default();
+        assert_eq!(registry.decimals("vxs"), Some(9));
+        assert_eq!(registry.decimals("USDC"), Some(6));
+        assert_eq!(registry.decimals("WBTC"), Some(8));
+        assert_eq!(registry.decimals("ETH"), Some(18));
+        assert_eq!(registry.decimals("PEPE"), None);
+
+        assert_eq!(to_raw_amount("1.5", 6).unwrap(), 1_500_000);
+        assert_eq!(to_raw_amount("0.00000001", 8).unwrap(), 1);
+        assert!(to_raw_amount("0.0000001", 6).is_err());
+        assert_eq!(from_raw_amount(1_500_000, 6), "1.5");
+        assert_eq!(from_raw_amount(2_000_000_000, 9), "2");
+        assert_eq!(from_raw_amount(1, 18), "0.000000000000000001");
+        assert_eq!(from_raw_amount(0, 6), "0");
+        assert_eq!(from_raw_amount(u128::MAX, 9), "340282366920938463463374607431.768211455");
+
+        match parse_intent("swap 2.5 USDC for WBTC min output 0.0001").unwrap() {
+            ParsedIntent { goal: Goal::Swap { amount, .. }, constraints, .. } => {
+                assert_eq!(amount, Amount(2_500_000));
+                assert_eq!(constraints.min_output, Some(Amount(10_000)));
+            }
+            _ => panic!("Expected Swap"),
+        }
+        // Minimum outputs take the same separators and suffixes as amounts
+        let min_output = |text| parse_intent(text).unwrap().constraints.min_output;
+        assert_eq!(min_output("swap 1 ETH for USDC at least 1,000 USDC"), Some(Amount(1_000_000_000)));
+        assert_eq!(min_output("swap 1 ETH for USDC min output 1.5k"), Some(Amount(1_500_000_000)));
+
+        let mut registry = TokenRegistry::empty();
+        registry.register("VXS", Address::ZERO);
+        registry.register_with_decimals("PEPE", Address([8u8; 32]), 2);
+        match parse_intent_with_registry("swap 1.25 PEPE for VXS", &registry).unwrap().goal {
+            Goal::Swap { amount, .. } => assert_eq!(amount, Amount(125)),
+            _ => panic!("Expected Swap"),
+        }
+    }
+
+    #[test]
+    fn test_parse_with_custom_registry() {
+        let pepe = Address([8u8; 32]);
+        assert!(matches!(parse_intent("swap 10 PEPE for VXS").unwrap().goal, Goal::Custom(_)));
+
+        let mut registry = TokenRegistry::default();
+        registry.register("pepe", pepe);
+        assert_eq!(registry.resolve("PEPE"), Some(pepe));
+        assert_eq!(registry.resolve("vxs"), Some(Address::ZERO));
+        match parse_intent_with_registry("swap 10 PEPE for VXS", &registry).unwrap().goal {
+            Goal::Swap { from_token, to_token, .. } => {
+                assert_eq!(from_token, pepe);
+                assert_eq!(to_token, Address::ZERO);
+            }
+            _ => panic!("Expected Swap"),
+        }
+
+        // Every pattern resolves through the registry, including VXS
+        let empty = TokenRegistry::empty();
+        assert!(matches!(
+            parse_intent_with_registry("stake 10 VXS", &empty).unwrap().goal,
+            Goal::Custom(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_balance_relative_amounts() {
+        let usdc = TokenRegistry::default().resolve("USDC").unwrap();
+        let balances = |token: &Address| (*token == Address::ZERO).then_some(1_000_000_001u128);
+
+        let mut half = parse_intent("swap half my VXS for USDC with 2% slippage").unwrap();
+        assert_eq!(half.amount_spec, Some(AmountSpec::Percent(50)));
+        assert_eq!(half.constraints.max_slippage, Some(2));
+        // The zero placeholder amount can't be built until resolved
+        let unresolved = crate::IntentBuilder::from_goal(half.goal.clone(), half.constraints.clone());
+        assert!(matches!(unresolved.build(), Err(IntentError::ZeroAmount)));
+        assert!(matches!(
+            crate::bundle::from_goal(&format!("0x{}", hex::encode([1u8; 32])), &half.goal, &half.constraints),
+            Err(crate::bundle::BundleError::ZeroAmount)
+        ));
+        half.resolve_amounts(balances).unwrap();
+        match half.goal {
+            Goal::Swap { to_token, amount, .. } => {
+                assert_eq!(to_token, usdc);
+                assert_eq!(amount, Amount(500_000_000));
+            }
+            _ => panic!("Expected Swap"),
+        }
+        assert!(half.amount_spec.is_none());
+
+        let mut all = parse_intent("stake all my VXS").unwrap();
+        assert_eq!(all.amount_spec, Some(AmountSpec::All));
+        all.resolve_amounts(balances).unwrap();
+        assert!(matches!(all.goal, Goal::Stake { amount, .. } if amount == Amount(1_000_000_001)));
+
+        let mut quarter = parse_intent("Stake 25% of my VXS").unwrap();
+        assert_eq!(quarter.amount_spec, Some(AmountSpec::Percent(25)));
+        quarter.resolve_amounts(balances).unwrap();
+        assert!(matches!(quarter.goal, Goal::Stake { amount, .. } if amount == Amount(250_000_000)));
+
+        // No balance for the input token
+        let mut usdc_swap = parse_intent("swap all my USDC for VXS").unwrap();
+        assert!(matches!(
+            usdc_swap.resolve_amounts(balances),
+            Err(IntentError::MissingBalance(token)) if token == usdc
+        ));
+
+        assert!(matches!(parse_intent("swap 150% of my VXS for USDC").unwrap().goal, Goal::Custom(_)));
+        assert_eq!(AmountSpec::Percent(100).resolve(u128::MAX), u128::MAX);
     }
 
     #[test]
@@ -327,6 +1346,121 @@ mod tests {
             }
             _ => panic!("Expected Stake"),
         }
+
+        // Staking is VXS-only, so any other token is an error, not a VXS stake
+        for text in ["stake 1000 USDC", "stake all my USDC"] {
+            match parse_intent(text) {
+                Err(IntentError::ParseError(msg)) => assert!(msg.contains("USDC"), "{}", msg),
+                other => panic!("Expected ParseError for {:?}, got {:?}", text, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_transfer_hex_and_vx0() {
+        let hex_to = format!("0x{}", hex::encode([4u8; 32]));
+        let result = parse_intent(&format!("send 50 VXS to {}", hex_to)).unwrap();
+        match result.goal {
+            Goal::Transfer { to, token, amount } => {
+                assert_eq!(to, Address([4u8; 32]));
+                assert_eq!(token, Address::ZERO);
+                assert_eq!(amount, Amount(50_000_000_000));
+            }
+            _ => panic!("Expected Transfer"),
+        }
+        assert!(result.recipient_name.is_none());
+
+        // Vx0 is base58, so the recipient's case must survive parsing
+        let vx0 = address_utils::vx0_from_pubkey(&[7u8; 32]);
+        let opts = ParseOptions::new().with_decimals("USDC", 6);
+        let result = parse_intent_with(&format!("Transfer 10 USDC to {}", vx0), &opts).unwrap();
+        match result.goal {
+            Goal::Transfer { to, token, amount } => {
+                assert_eq!(to, address_utils::parse_address(&vx0).unwrap());
+                assert_eq!(Some(token), TokenRegistry::default().resolve("USDC"));
+                assert_eq!(amount, Amount(10_000_000));
+            }
+            _ => panic!("Expected Transfer"),
+        }
+
+        // Unparseable recipient falls through to Custom
+        assert!(matches!(parse_intent("send 5 VXS to nowhere").unwrap().goal, Goal::Custom(_)));
+    }
+
+    #[test]
+    fn test_parse_recurring_payment() {
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+        let result = parse_intent(&format!("pay 10 VXS to {} every month", to)).unwrap();
+        assert!(matches!(result.goal, Goal::Transfer { amount, .. } if amount == Amount(10_000_000_000)));
+        assert_eq!(
+            result.schedule,
+            Some(RecurringSchedule { interval_secs: 30 * 86_400, count: u32::MAX })
+        );
+
+        let result = parse_intent(&format!("pay 10 VXS to {} every 30 days for 12 months", to)).unwrap();
+        assert_eq!(result.schedule, Some(RecurringSchedule { interval_secs: 30 * 86_400, count: 12 }));
+        let result = parse_intent(&format!("send 5 USDC to {} every 2 weeks for 6 payments", to)).unwrap();
+        assert_eq!(result.schedule, Some(RecurringSchedule { interval_secs: 1_209_600, count: 6 }));
+
+        // A one-off payment has no schedule; a malformed one isn't a transfer
+        assert!(parse_intent(&format!("pay 10 VXS to {}", to)).unwrap().schedule.is_none());
+        let result = parse_intent(&format!("pay 10 VXS to {} every fortnight", to)).unwrap();
+        assert!(matches!(result.goal, Goal::Custom(_)));
+        // "every" has to be its own word
+        let result = parse_intent(&format!("pay 10 VXS to {} for everything", to)).unwrap();
+        assert!(matches!(result.goal, Goal::Transfer { .. }));
+        assert!(result.schedule.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_recurring_payment_to_vns_name() {
+        use crate::transport::MockTransport;
+        use serde_json::json;
+
+        let mut result = parse_intent("pay 10 VXS to chris.vex every week").unwrap();
+        let recurring = |result: &ParsedIntent| {
+            let Goal::Transfer { to, token, amount } = result.goal.clone() else {
+                panic!("Expected Transfer, got {:?}", result.goal);
+            };
+            let schedule = result.schedule.unwrap();
+            crate::IntentBuilder::new()
+                .recurring(to, token, amount, schedule.interval_secs, schedule.count)
+                .build_recurring()
+        };
+        assert!(matches!(recurring(&result), Err(IntentError::MissingRecipient)));
+
+        let chris = format!("0x{}", hex::encode([6u8; 32]));
+        let client = WalletClient::with_mock(MockTransport::new().with_result("vex_resolveName", json!(chris)));
+        result.resolve_recipient(&client).await.unwrap();
+        let intent = recurring(&result).unwrap();
+        assert_eq!(intent.schedule, RecurringSchedule { interval_secs: 604_800, count: u32::MAX });
+    }
+
+    #[tokio::test]
+    async fn test_parse_transfer_to_vns_name() {
+        use crate::transport::MockTransport;
+        use serde_json::json;
+
+        let mut result = parse_intent("transfer 1 VXS to Chris.vex").unwrap();
+        assert_eq!(result.recipient_name.as_deref(), Some("chris.vex"));
+        assert!(matches!(result.goal, Goal::Transfer { to, .. } if to == Address::ZERO));
+        // The placeholder recipient can't be built or signed
+        let unresolved = crate::IntentBuilder::from_goal(result.goal.clone(), result.constraints.clone());
+        assert!(matches!(unresolved.build(), Err(IntentError::MissingRecipient)));
+
+        let chris = format!("0x{}", hex::encode([6u8; 32]));
+        let client = WalletClient::with_mock(MockTransport::new().with_result("vex_resolveName", json!(chris)));
+        result.resolve_recipient(&client).await.unwrap();
+        assert!(matches!(result.goal, Goal::Transfer { to, .. } if to == Address([6u8; 32])));
+        assert!(result.recipient_name.is_none());
+        assert!(crate::IntentBuilder::from_goal(result.goal, result.constraints).build().is_ok());
+
+        let mut result = parse_intent("send 1 VXS to ghost.vex").unwrap();
+        let client = WalletClient::with_mock(MockTransport::new().with_result("vex_resolveName", json!(null)));
+        assert!(matches!(
+            result.resolve_recipient(&client).await,
+            Err(IntentError::UnresolvedName(name)) if name == "ghost.vex"
+        ));
     }
 
     #[test]
@@ -355,7 +1489,7 @@ mod tests {
                 assert_eq!(token_a, Address::ZERO); // VXS
                 assert_ne!(token_b, Address::ZERO); // USDC
                 assert_eq!(amount_a, Amount(100_000_000_000));
-                assert_eq!(amount_b, Amount(500_000_000_000));
+                assert_eq!(amount_b, Amount(500_000_000));
             }
             _ => panic!("Expected ProvideLiquidity"),
         }
@@ -366,7 +1500,7 @@ mod tests {
         let result = parse_intent("provide 50 ETH and 1000 VXS liquidity").unwrap();
         match result.goal {
             Goal::ProvideLiquidity { amount_a, amount_b, .. } => {
-                assert_eq!(amount_a, Amount(50_000_000_000));
+                assert_eq!(amount_a, Amount(50_000_000_000_000_000_000));
                 assert_eq!(amount_b, Amount(1_000_000_000_000));
             }
             _ => panic!("Expected ProvideLiquidity"),
@@ -447,6 +1581,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_form_swap() {
+        let result = from_form(IntentForm::Swap {
+            from: "VXS".into(),
+            to: "USDC".into(),
+            amount: "100".into(),
+            slippage: Some(2),
+        })
+        .unwrap();
+        match result.goal {
+            Goal::Swap { from_token, to_token, amount } => {
+                assert_eq!(from_token, Address::ZERO);
+                assert_eq!(to_token, TokenRegistry::default().resolve("USDC").unwrap());
+                assert_eq!(amount, Amount(100_000_000_000));
+            }
+            _ => panic!("Expected Swap"),
+        }
+        assert_eq!(result.constraints.max_slippage, Some(2));
+    }
+
+    #[test]
+    fn test_from_form_stake_and_liquidity() {
+        let validator = format!("0x{}", hex::encode([3u8; 32]));
+        let result = from_form(IntentForm::Stake {
+            amount: "1000".into(),
+            validator: Some(validator),
+        })
+        .unwrap();
+        match result.goal {
+            Goal::Stake { amount, validator, .. } => {
+                assert_eq!(amount, Amount(1_000_000_000_000));
+                assert_eq!(validator, Some(Address([3u8; 32])));
+            }
+            _ => panic!("Expected Stake"),
+        }
+
+        let result = from_form(IntentForm::ProvideLiquidity {
+            token_a: "VXS".into(),
+            amount_a: "100".into(),
+            token_b: "USDC".into(),
+            amount_b: "0.5".into(),
+        })
+        .unwrap();
+        match result.goal {
+            Goal::ProvideLiquidity { amount_a, amount_b, .. } => {
+                assert_eq!(amount_a, Amount(100_000_000_000));
+                assert_eq!(amount_b, Amount(500_000));
+            }
+            _ => panic!("Expected ProvideLiquidity"),
+        }
+    }
+
+    #[test]
+    fn test_from_form_bridge_and_register() {
+        let result = from_form(IntentForm::Bridge {
+            token: "sol".into(),
+            amount: "10".into(),
+            source_chain: "Solana".into(),
+        })
+        .unwrap();
+        match result.goal {
+            Goal::Bridge { source_chain, token_symbol, amount, .. } => {
+                assert_eq!(source_chain, "solana");
+                assert_eq!(token_symbol, "SOL");
+                assert_eq!(amount, Amount(10_000_000_000));
+            }
+            _ => panic!("Expected Bridge"),
+        }
+
+        let result = from_form(IntentForm::Register { name: "Chris.vex".into() }).unwrap();
+        match result.goal {
+            Goal::Custom(text) => assert_eq!(text, "register_vns:chris"),
+            _ => panic!("Expected Custom for register"),
+        }
+    }
+
+    #[test]
+    fn test_from_form_rejects_bad_input() {
+        let unknown = from_form(IntentForm::Swap {
+            from: "DOGWIFHAT".into(),
+            to: "VXS".into(),
+            amount: "1".into(),
+            slippage: None,
+        });
+        assert!(matches!(unknown, Err(IntentError::ParseError(_))));
+
+        let bad_amount = from_form(IntentForm::Stake { amount: "-5".into(), validator: None });
+        assert!(bad_amount.is_err());
+
+        let bad_slippage = from_form(IntentForm::Swap {
+            from: "VXS".into(),
+            to: "USDC".into(),
+            amount: "1".into(),
+            slippage: Some(101),
+        });
+        assert!(matches!(bad_slippage, Err(IntentError::InvalidSlippage(101))));
+
+        assert!(from_form(IntentForm::Register { name: "ab".into() }).is_err());
+    }
+
     #[test]
     fn test_parse_bridge_unknown_chain_falls_to_custom() {
         let result = parse_intent("bridge 10 SOL from neptune").unwrap();
@@ -455,4 +1689,131 @@ mod tests {
             _ => panic!("Expected Custom for unknown chain"),
         }
     }
+
+    #[test]
+    fn test_parse_reader_batch() {
+        let input = "# nightly rebalance\nswap 100 VXS for USDC\n\n  stake 50 VXS  \nfly to the moon\n";
+        let results = parse_reader(input.as_bytes()).unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, "swap 100 VXS for USDC");
+        assert!(matches!(results[0].1.as_ref().unwrap().goal, Goal::Swap { .. }));
+        assert_eq!(results[1].0, "stake 50 VXS");
+        assert!(results[1].1.is_ok());
+        assert_eq!(results[2].0, "fly to the moon");
+        assert!(results[2].1.is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("intents.txt");
+        std::fs::write(&path, input).unwrap();
+        assert_eq!(parse_file(&path).unwrap().len(), 3);
+        assert!(parse_file(dir.path().join("missing.txt")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_tokens_reports_missing_mint() {
+        use crate::transport::MockTransport;
+        use crate::wallet_client::WalletClientError;
+        use serde_json::json;
+
+        let parsed = parse_intent("swap 100 VXS for USDC").unwrap();
+
+        let registered = MockTransport::new()
+            .with_result("vex_getTokenInfo", json!({ "symbol": "USDC", "decimals": 6 }));
+        let client = WalletClient::with_mock(registered.clone());
+        parsed.verify_tokens(&client).await.unwrap();
+        // VXS is native and never looked up
+        assert_eq!(registered.call_count("vex_getTokenInfo"), 1);
+
+        // USDC is registered, USDT is not
+        let usdt = TokenRegistry::default().resolve("USDT").unwrap();
+        let parsed = parse_intent("swap 100 USDC for USDT").unwrap();
+        let partial = MockTransport::new()
+            .with_result("vex_getTokenInfo", json!({ "symbol": "USDC", "decimals": 6 }))
+            .with_result("vex_getTokenInfo", serde_json::Value::Null);
+        let client = WalletClient::with_mock(partial);
+        match parsed.verify_tokens(&client).await {
+            Err(IntentError::UnknownToken(token)) => assert_eq!(token, usdt),
+            other => panic!("Expected UnknownToken, got {:?}", other),
+        }
+
+        // Only the node's not-found error means the mint is missing
+        let rejected = |message: &str| {
+            MockTransport::new()
+                .with_result("vex_getTokenInfo", json!({ "symbol": "USDC", "decimals": 6 }))
+                .with_error("vex_getTokenInfo", -32000, message)
+        };
+        let client = WalletClient::with_mock(rejected("Token not found"));
+        assert!(matches!(parsed.verify_tokens(&client).await, Err(IntentError::UnknownToken(_))));
+        let client = WalletClient::with_mock(rejected("state database busy"));
+        match parsed.verify_tokens(&client).await {
+            Err(IntentError::Client(WalletClientError::Rpc { message, .. })) => assert_eq!(message, "state database busy"),
+            other => panic!("Expected the RPC error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parsed_intent_json_roundtrip() {
+        let roundtrip = |text: &str| {
+            let parsed = parse_intent(text).unwrap();
+            let json = parsed.to_json().unwrap();
+            let back = ParsedIntent::from_json(&json).unwrap();
+            assert_eq!(serde_json::to_value(&back).unwrap(), serde_json::to_value(&parsed).unwrap());
+            back
+        };
+        match roundtrip("swap 100 VXS for USDC").goal {
+            Goal::Swap { from_token, amount, .. } => {
+                assert_eq!(from_token, Address::ZERO);
+                assert_eq!(amount, Amount(100_000_000_000));
+            }
+            other => panic!("Expected Swap, got {:?}", other),
+        }
+        match roundtrip("do something clever with my tokens").goal {
+            Goal::Custom(text) => assert_eq!(text, "do something clever with my tokens"),
+            other => panic!("Expected Custom, got {:?}", other),
+        }
+        assert!(roundtrip("swap half my VXS for USDC").amount_spec.is_some());
+        assert!(matches!(ParsedIntent::from_json("{}"), Err(IntentError::SerializationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_parse_intent_with_fallback() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Turns "buy me a coffee" into a USDC transfer, like an LLM might.
+        struct CoffeeResolver {
+            calls: AtomicUsize,
+        }
+
+        impl IntentResolver for CoffeeResolver {
+            async fn resolve(&self, text: &str) -> Result<ParsedIntent, IntentError> {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                if !text.contains("coffee") {
+                    return Err(IntentError::ParseError(format!("can't resolve {:?}", text)));
+                }
+                parse_intent(&format!("send 5 USDC to 0x{}", hex::encode([7u8; 32])))
+            }
+        }
+
+        let resolver = CoffeeResolver { calls: AtomicUsize::new(0) };
+        let parsed = parse_intent_with_fallback("swap 100 VXS for USDC", &resolver).await.unwrap();
+        assert!(matches!(parsed.goal, Goal::Swap { .. }));
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 0);
+
+        let parsed = parse_intent_with_fallback("buy me a coffee", &resolver).await.unwrap();
+        match parsed.goal {
+            Goal::Transfer { to, amount, .. } => {
+                assert_eq!(to, Address([7u8; 32]));
+                assert_eq!(amount, Amount(5_000_000));
+            }
+            other => panic!("Expected Transfer, got {:?}", other),
+        }
+        assert!(parse_intent_with_fallback("launch a rocket", &resolver).await.is_err());
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 2);
+
+        match parse_intent_with_fallback("Launch A Rocket", &NoopResolver).await.unwrap().goal {
+            Goal::Custom(text) => assert_eq!(text, "launch a rocket"),
+            other => panic!("Expected Custom, got {:?}", other),
+        }
+    }
 }
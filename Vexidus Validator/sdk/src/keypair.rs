@@ -1,6 +1,7 @@
 //! Validator keypair management — generate, save, load Ed25519 signing keys.
 
 use ed25519_dalek::{SigningKey, VerifyingKey, Signer, Signature};
+use rand_core::OsRng;
 use std::path::Path;
 use thiserror::Error;
 
@@ -23,13 +24,11 @@ pub struct ValidatorKeypair {
 }
 
 impl ValidatorKeypair {
-    /// Generate a new random keypair.
+    /// Generate a new random keypair from the OS CSPRNG.
     pub fn generate() -> Self {
-        let mut rng = rand_bytes();
-        let signing_key = SigningKey::from_bytes(&rng);
-        // Zero out stack buffer
-        rng.fill(0u8);
-        Self { signing_key }
+        Self {
+            signing_key: SigningKey::generate(&mut OsRng),
+        }
     }
 
     /// Load from a hex-encoded secret key file.
@@ -102,31 +101,6 @@ impl ValidatorKeypair {
     }
 }
 
-/// Generate 32 random bytes for key generation.
-fn rand_bytes() -> [u8; 32] {
-    use sha2::{Sha256, Digest};
-    // Use system randomness seeded with multiple entropy sources
-    let mut hasher = Sha256::new();
-    hasher.update(std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH).unwrap()
-        .as_nanos().to_le_bytes());
-    hasher.update(std::process::id().to_le_bytes());
-    // Read 32 bytes from /dev/urandom on Unix
-    #[cfg(unix)]
-    {
-        use std::io::Read;
-        if let Ok(mut f) = std::fs::File::open("/dev/urandom") {
-            let mut buf = [0u8; 32];
-            let _ = f.read_exact(&mut buf);
-            hasher.update(&buf);
-        }
-    }
-    let result = hasher.finalize();
-    let mut out = [0u8; 32];
-    out.copy_from_slice(&result);
-    out
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,6 +132,13 @@ mod tests {
         assert_eq!(kp1.public_key_bytes(), kp2.public_key_bytes());
     }
 
+    #[test]
+    fn test_generate_no_collisions() {
+        let keys: std::collections::HashSet<[u8; 32]> =
+            (0..10_000).map(|_| ValidatorKeypair::generate().public_key_bytes()).collect();
+        assert_eq!(keys.len(), 10_000);
+    }
+
     #[test]
     fn test_sign_vote() {
         let kp = ValidatorKeypair::generate();
@@ -11,34 +11,67 @@
 pub mod keypair;
 pub mod client;
 pub mod config;
+pub mod staking;
 
 // Wallet SDK
 pub mod address_utils;
+pub mod address_book;
+pub mod amount;
 pub mod wallet;
 pub mod bundle;
 pub mod wallet_client;
+pub mod session;
+pub mod airdrop;
+pub mod vns;
+#[cfg(feature = "multisig")]
+pub mod multisig;
 
 // Intent SDK
 pub mod intent;
 pub mod intent_parser;
+pub mod intent_history;
 
 // DEX SDK
 pub mod dex;
 
+// RPC transport
+pub mod transport;
+#[cfg(feature = "ws")]
+pub mod ws;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
 // Validator exports
 pub use keypair::ValidatorKeypair;
-pub use client::ValidatorClient;
-pub use config::ValidatorConfig;
+pub use client::{ValidatorClient, ValidatorApy, JailStatus, ValidatorInfo, ValidatorMetadata, Delegation, StakingInfo};
+pub use config::{ValidatorConfig, ConfigError};
+pub use staking::{reward_split, RewardSplit};
 
 // Wallet exports
-pub use wallet::{WalletKeypair, WalletError};
-pub use bundle::{BundleBuilder, BundleError};
-pub use wallet_client::WalletClient;
-pub use address_utils::AddressError;
+pub use wallet::{WalletKeypair, WalletError, WatchOnlyAccount, RateLimitedSigner, verify_with_pubkey};
+pub use wallet::hd::{VEXIDUS_COIN_TYPE, account_path};
+pub use bundle::{BundleBuilder, BundleError, attach_encoded_signature, decode_bundle, encode_bundle, FeeSchedule, DEFAULT_MAX_OPERATIONS, GAS_ESTIMATE_MARGIN_PERCENT, MAX_COMMISSION_BPS};
+pub use wallet_client::{WalletClient, WalletClientError, Congestion, CongestionLevel, DecodedEvent, FeeEstimate, FeeSource, NonceDiagnosis, PendingTx, TxStatus, TransactionInfo, BlockInfo, BalanceDelta, OperationOutcome, SimulationResult, ScheduledClaim, TokenTransfer, TransferDirection, TxReceipt};
+pub use address_utils::{AddressError, AddressKind};
+pub use address_book::{AddressBook, AddressBookEntry, AddressBookIssue};
+pub use amount::AmountExt;
+pub use airdrop::{build_merkle_tree, verify_proof, MerkleProof, MerkleTree};
+pub use vns::VnsClient;
+pub use session::{SessionKey, SessionScope, SessionAuthorization, SessionError, verify_session};
+#[cfg(feature = "multisig")]
+pub use multisig::{MultiSigAggregator, AggregateSignature, AggregateScheme, MultiSigError, ACTIVE_SCHEME, add_signature, bundle_signers, partial_signature};
 
 // Intent exports
-pub use intent::{IntentBuilder, IntentError};
-pub use intent_parser::{parse_intent, ParsedIntent};
+pub use intent::{IntentBuilder, IntentError, ConditionalIntent, PriceTrigger, RecurringIntent, RecurringSchedule, SignedIntentEnvelope, ENVELOPE_VERSION, goal_tokens, parse_intent_json, requires_solver, validate_constraints};
+pub use intent_parser::{AmountSpec, IntentResolver, NoopResolver, parse_amount, parse_intent, parse_intent_with, parse_intent_with_fallback, parse_intent_with_registry, parse_file, parse_reader, BatchResults, from_form, from_raw_amount, to_raw_amount, DEFAULT_DECIMALS, IntentForm, ParseOptions, ParsedIntent, TokenRegistry};
+pub use intent_history::IntentHistory;
 
 // DEX exports
-pub use dex::{DexClient, PoolInfo, SwapQuote};
+pub use dex::{DexClient, PoolInfo, SwapQuote, ExecutionReport, amount_in_for_output, min_lp_tokens, min_withdrawal};
+
+// Transport exports
+pub use transport::{MockTransport, RetryPolicy, DEFAULT_TIMEOUT};
+#[cfg(feature = "ws")]
+pub use ws::{BalanceUpdate, BlockHeader, subscribe_blocks_at};
+#[cfg(feature = "blocking")]
+pub use blocking::{BlockingWalletClient, BlockingDexClient};
@@ -0,0 +1,359 @@
+//! Threshold multisig signature aggregation.
+//!
+//! Vexidus accounts only sign with Ed25519 or secp256k1, neither of which
+//! aggregates, so [`MultiSigAggregator`] uses the
+//! [`AggregateScheme::Ed25519Concat`] form: a signer bitmap followed by the
+//! 64-byte partial signatures of the set bits, in signer order. The bitmap
+//! replaces a per-signature public key, which is what keeps large validator
+//! multisigs compact. [`ACTIVE_SCHEME`] names the scheme in use so callers can
+//! branch on it if the chain adds an aggregatable (BLS) scheme later.
+//!
+//! ```ignore
+//! let mut agg = MultiSigAggregator::new(signers, 2, &bundle)?;
+//! agg.add_partial(&alice.public_key(), &partial_signature(&alice, &bundle))?;
+//! agg.add_partial(&bob.public_key(), &partial_signature(&bob, &bundle))?;
+//! let aggregate = agg.aggregate()?;
+//! assert!(aggregate.verify(&signers, 2, bundle.hash().as_bytes()));
+//! ```
+//!
+//! ## Signature lists
+//!
+//! When the signer set isn't fixed up front — co-signers on separate machines
+//! adding their signatures in turn — [`BundleBuilder::sign_multi`] and
+//! [`add_signature`] store a signature list in the bundle's `signature`
+//! instead: one 96-byte entry per signer, `pubkey (32) || Ed25519 signature
+//! (64)`, in signing order, with no header. Every signer signs the same
+//! Blake3 bundle hash (which excludes `signature`), so an offline co-signer
+//! reproduces an entry by signing `bundle.hash()` and appending its public
+//! key and signature. A list is never 64 bytes long, so it can't be mistaken
+//! for a single signature. [`bundle_signers`] checks every entry.
+//!
+//! ## Node support
+//!
+//! Neither encoding is something a node is known to accept yet. The node
+//! checks a bundle's `signature` with `TransactionBundle::verify_signature`
+//! in `vexidus-types`. That takes one public key and expects a single 64-byte
+//! Ed25519 signature, so an aggregate or a signature list submitted as-is is
+//! rejected until that decoder learns the format. Until then these are for
+//! collecting and checking co-signatures off-chain. An on-chain multisig
+//! account goes through the `CreateMultiSig`, `ProposeMultiSigTx` and
+//! `ApproveMultiSigTx` operations
+//! ([`BundleBuilder::approve_multisig_tx`] and friends), where each signer
+//! submits an ordinary single-signature bundle.
+
+use thiserror::Error;
+use vexidus_types::{PublicKey, Signature, TransactionBundle};
+
+use crate::address_utils;
+use crate::bundle::{BundleBuilder, BundleError};
+use crate::wallet::{verify_with_pubkey, WalletKeypair};
+
+/// Length of one Ed25519 partial signature.
+const PARTIAL_SIG_LEN: usize = 64;
+
+/// Length of one signature-list entry: public key then signature.
+const LIST_ENTRY_LEN: usize = 32 + PARTIAL_SIG_LEN;
+
+#[derive(Error, Debug)]
+pub enum MultiSigError {
+    #[error("Threshold {threshold} is not achievable with {signers} signers")]
+    InvalidThreshold { threshold: u8, signers: usize },
+    #[error("Public key {0} is not in the signer set")]
+    UnknownSigner(String),
+    #[error("Partial signature from {0} does not verify")]
+    InvalidSignature(String),
+    #[error("Only {have} of {need} required signatures collected")]
+    BelowThreshold { have: usize, need: usize },
+    #[error("Malformed aggregate signature: {0}")]
+    Malformed(String),
+}
+
+/// Signature scheme an aggregate is encoded with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateScheme {
+    /// Signer bitmap followed by concatenated 64-byte Ed25519 signatures.
+    Ed25519Concat,
+}
+
+/// The scheme [`MultiSigAggregator`] produces.
+pub const ACTIVE_SCHEME: AggregateScheme = AggregateScheme::Ed25519Concat;
+
+/// Sign `bundle` as one member of a multisig, returning the partial signature.
+///
+/// Every signer signs the same Blake3 bundle hash, so partials can be
+/// collected in any order.
+pub fn partial_signature(wallet: &WalletKeypair, bundle: &TransactionBundle) -> Vec<u8> {
+    wallet.sign_bundle(bundle).0
+}
+
+/// Collected signer bitmap plus partial signatures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregateSignature {
+    /// Bit `i` (LSB-first within each byte) is set if signer `i` signed.
+    pub bitmap: Vec<u8>,
+    /// Partial signatures of the set bits, in signer order.
+    pub signatures: Vec<u8>,
+}
+
+impl AggregateSignature {
+    /// Wire encoding: `bitmap || signatures`, with the bitmap `ceil(n / 8)`
+    /// bytes long for `n` signers.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = self.bitmap.clone();
+        out.extend_from_slice(&self.signatures);
+        out
+    }
+
+    /// Decode the wire form for a multisig with `signer_count` signers.
+    pub fn from_bytes(bytes: &[u8], signer_count: usize) -> Result<Self, MultiSigError> {
+        let bitmap_len = bitmap_len(signer_count);
+        if bytes.len() < bitmap_len {
+            return Err(MultiSigError::Malformed(format!(
+                "{} bytes is shorter than the {}-byte bitmap",
+                bytes.len(),
+                bitmap_len
+            )));
+        }
+        let (bitmap, signatures) = bytes.split_at(bitmap_len);
+        let aggregate = Self { bitmap: bitmap.to_vec(), signatures: signatures.to_vec() };
+        if aggregate.signer_indices().iter().any(|&i| i >= signer_count) {
+            return Err(MultiSigError::Malformed("bitmap sets bits past the signer set".into()));
+        }
+        if signatures.len() != aggregate.signer_indices().len() * PARTIAL_SIG_LEN {
+            return Err(MultiSigError::Malformed(format!(
+                "expected {} signatures, got {} bytes",
+                aggregate.signer_indices().len(),
+                signatures.len()
+            )));
+        }
+        Ok(aggregate)
+    }
+
+    /// Indices of the signers whose bits are set, ascending.
+    pub fn signer_indices(&self) -> Vec<usize> {
+        (0..self.bitmap.len() * 8)
+            .filter(|i| self.bitmap[i / 8] & (1 << (i % 8)) != 0)
+            .collect()
+    }
+
+    /// Check that at least `threshold` distinct members of `signers` signed
+    /// `message` and that every included signature verifies. The bitmap must
+    /// be exactly `ceil(n / 8)` bytes for the `n` signers.
+    pub fn verify(&self, signers: &[PublicKey], threshold: u8, message: &[u8]) -> bool {
+        let indices = self.signer_indices();
+        if self.bitmap.len() != bitmap_len(signers.len())
+            || indices.len() < threshold as usize
+            || self.signatures.len() != indices.len() * PARTIAL_SIG_LEN
+            || indices.iter().any(|&i| i >= signers.len())
+        {
+            return false;
+        }
+        indices
+            .iter()
+            .zip(self.signatures.chunks(PARTIAL_SIG_LEN))
+            .all(|(&i, sig)| verify_with_pubkey(&signers[i].0, message, sig))
+    }
+}
+
+/// Collects partial signatures over one bundle until the threshold is met.
+pub struct MultiSigAggregator {
+    signers: Vec<PublicKey>,
+    threshold: u8,
+    message: [u8; 32],
+    partials: Vec<Option<Vec<u8>>>,
+}
+
+impl MultiSigAggregator {
+    /// Start collecting signatures over `bundle` from `signers`, in the same
+    /// order the multisig was created with.
+    pub fn new(
+        signers: Vec<PublicKey>,
+        threshold: u8,
+        bundle: &TransactionBundle,
+    ) -> Result<Self, MultiSigError> {
+        if threshold == 0 || threshold as usize > signers.len() {
+            return Err(MultiSigError::InvalidThreshold { threshold, signers: signers.len() });
+        }
+        let partials = vec![None; signers.len()];
+        Ok(Self { signers, threshold, message: *bundle.hash().as_bytes(), partials })
+    }
+
+    /// Record `signer`'s partial signature after checking it verifies.
+    ///
+    /// Adding a second signature for the same signer replaces the first.
+    pub fn add_partial(&mut self, signer: &PublicKey, signature: &[u8]) -> Result<(), MultiSigError> {
+        let index = self
+            .signers
+            .iter()
+            .position(|s| address_utils::ct_eq_bytes(&s.0, &signer.0))
+            .ok_or_else(|| MultiSigError::UnknownSigner(hex::encode(signer.0)))?;
+        if !verify_with_pubkey(&signer.0, &self.message, signature) {
+            return Err(MultiSigError::InvalidSignature(hex::encode(signer.0)));
+        }
+        self.partials[index] = Some(signature.to_vec());
+        Ok(())
+    }
+
+    /// Number of valid partials collected so far.
+    pub fn collected(&self) -> usize {
+        self.partials.iter().filter(|p| p.is_some()).count()
+    }
+
+    /// Whether enough partials have been collected to aggregate.
+    pub fn is_complete(&self) -> bool {
+        self.collected() >= self.threshold as usize
+    }
+
+    /// Combine the collected partials into an [`AggregateSignature`].
+    pub fn aggregate(&self) -> Result<AggregateSignature, MultiSigError> {
+        if !self.is_complete() {
+            return Err(MultiSigError::BelowThreshold {
+                have: self.collected(),
+                need: self.threshold as usize,
+            });
+        }
+        let mut bitmap = vec![0u8; bitmap_len(self.signers.len())];
+        let mut signatures = Vec::with_capacity(self.collected() * PARTIAL_SIG_LEN);
+        for (i, partial) in self.partials.iter().enumerate() {
+            if let Some(sig) = partial {
+                bitmap[i / 8] |= 1 << (i % 8);
+                signatures.extend_from_slice(sig);
+            }
+        }
+        Ok(AggregateSignature { bitmap, signatures })
+    }
+}
+
+impl BundleBuilder {
+    /// Check the bundle like [`sign`](Self::sign), then sign it with every
+    /// wallet into a [signature list](crate::multisig#signature-lists).
+    pub fn sign_multi(self, wallets: &[&WalletKeypair]) -> Result<TransactionBundle, BundleError> {
+        let mut bundle = self.try_build()?;
+        for wallet in wallets {
+            append_entry(&mut bundle, wallet);
+        }
+        Ok(bundle)
+    }
+}
+
+/// Add `wallet`'s entry to `bundle`'s [signature list](crate::multisig#signature-lists),
+/// starting one if the bundle is unsigned.
+///
+/// Signing again with a key already in the list is a no-op. A bundle holding
+/// a single (non-list) signature is [`Malformed`](MultiSigError::Malformed).
+pub fn add_signature(bundle: &mut TransactionBundle, wallet: &WalletKeypair) -> Result<(), MultiSigError> {
+    if !bundle.signature.0.len().is_multiple_of(LIST_ENTRY_LEN) {
+        return Err(MultiSigError::Malformed(format!(
+            "{}-byte signature is not a signature list",
+            bundle.signature.0.len()
+        )));
+    }
+    let pubkey = wallet.public_key_bytes();
+    let signed = bundle
+        .signature
+        .0
+        .chunks(LIST_ENTRY_LEN)
+        .any(|entry| address_utils::ct_eq_bytes(&entry[..32], &pubkey));
+    if !signed {
+        append_entry(bundle, wallet);
+    }
+    Ok(())
+}
+
+/// Public keys in `bundle`'s signature list, in signing order, after checking
+/// that every entry's signature verifies.
+pub fn bundle_signers(bundle: &TransactionBundle) -> Result<Vec<PublicKey>, MultiSigError> {
+    let list = &bundle.signature.0;
+    if list.is_empty() || !list.len().is_multiple_of(LIST_ENTRY_LEN) {
+        return Err(MultiSigError::Malformed(format!("{}-byte signature is not a signature list", list.len())));
+    }
+    let hash = bundle.hash();
+    list.chunks(LIST_ENTRY_LEN)
+        .map(|entry| {
+            let mut pubkey = [0u8; 32];
+            pubkey.copy_from_slice(&entry[..32]);
+            if !verify_with_pubkey(&pubkey, hash.as_bytes(), &entry[32..]) {
+                return Err(MultiSigError::InvalidSignature(hex::encode(pubkey)));
+            }
+            Ok(PublicKey(pubkey))
+        })
+        .collect()
+}
+
+fn append_entry(bundle: &mut TransactionBundle, wallet: &WalletKeypair) {
+    let Signature(sig) = wallet.sign_bundle(bundle);
+    bundle.signature.0.extend_from_slice(&wallet.public_key_bytes());
+    bundle.signature.0.extend_from_slice(&sig);
+}
+
+fn bitmap_len(signer_count: usize) -> usize {
+    signer_count.div_ceil(8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::BundleBuilder;
+
+    #[test]
+    fn test_aggregate_three_partials() {
+        let wallets: Vec<WalletKeypair> = (0..5).map(|_| WalletKeypair::generate()).collect();
+        let signers: Vec<PublicKey> = wallets.iter().map(|w| w.public_key()).collect();
+        let bundle = BundleBuilder::new(&wallets[0].hex_address()).unwrap().build();
+
+        let mut agg = MultiSigAggregator::new(signers.clone(), 3, &bundle).unwrap();
+        for i in [4, 0, 2] {
+            assert!(!agg.is_complete());
+            agg.add_partial(&signers[i], &partial_signature(&wallets[i], &bundle)).unwrap();
+        }
+        let aggregate = agg.aggregate().unwrap();
+        assert_eq!(aggregate.bitmap, vec![0b10101]);
+        assert_eq!(aggregate.signer_indices(), vec![0, 2, 4]);
+        assert!(aggregate.verify(&signers, 3, bundle.hash().as_bytes()));
+        assert!(!aggregate.verify(&signers, 4, bundle.hash().as_bytes()));
+
+        let decoded = AggregateSignature::from_bytes(&aggregate.to_bytes(), signers.len()).unwrap();
+        assert_eq!(decoded, aggregate);
+
+        // The bitmap must be sized for the signer set, even when padding is zero
+        let mut padded = aggregate.clone();
+        padded.bitmap.push(0);
+        assert!(!padded.verify(&signers, 3, bundle.hash().as_bytes()));
+
+        // A signature over a different message is rejected up front
+        let other = BundleBuilder::new(&wallets[1].hex_address()).unwrap().build();
+        assert!(matches!(
+            agg.add_partial(&signers[1], &partial_signature(&wallets[1], &other)),
+            Err(MultiSigError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_sign_multi_and_sequential_cosigning() {
+        let (alice, bob, carol) = (WalletKeypair::generate(), WalletKeypair::generate(), WalletKeypair::generate());
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+        let builder = || BundleBuilder::new(&alice.hex_address()).unwrap().transfer(&to, "VXS", 1_000).unwrap();
+
+        let bundle = builder().sign_multi(&[&alice, &bob]).unwrap();
+        assert_eq!(bundle.signature.0.len(), 2 * LIST_ENTRY_LEN);
+        assert_eq!(bundle_signers(&bundle).unwrap(), vec![alice.public_key(), bob.public_key()]);
+
+        // The same list, built one co-signer at a time
+        let mut sequential = builder().build();
+        sequential.valid_until = bundle.valid_until;
+        add_signature(&mut sequential, &alice).unwrap();
+        add_signature(&mut sequential, &bob).unwrap();
+        add_signature(&mut sequential, &bob).unwrap();
+        assert_eq!(sequential.signature, bundle.signature);
+        add_signature(&mut sequential, &carol).unwrap();
+        assert_eq!(bundle_signers(&sequential).unwrap().len(), 3);
+
+        // Tampering with the bundle invalidates every entry
+        let mut tampered = bundle.clone();
+        tampered.nonce.0 += 1;
+        assert!(matches!(bundle_signers(&tampered), Err(MultiSigError::InvalidSignature(_))));
+
+        let mut single = builder().sign(&alice).unwrap();
+        assert!(matches!(add_signature(&mut single, &bob), Err(MultiSigError::Malformed(_))));
+    }
+}
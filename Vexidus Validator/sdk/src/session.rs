@@ -0,0 +1,470 @@
+//! Time-bound session keys — delegate limited signing power to a dApp.
+//!
+//! The root wallet signs a [`SessionAuthorization`] binding a freshly generated
+//! session key to a [`SessionScope`] and an expiry. [`verify_session`] checks
+//! the authorization, the scope, and the bundle signature.
+//!
+//! The authorization is bound to the root key's own account and to one chain
+//! id, so it can't be replayed against another account or network. Scope
+//! limits are per token and checked against every operation that moves value;
+//! operations whose value the SDK can't account for (intents, nested multisig
+//! proposals, name registration fees, LP tokens) are rejected rather than
+//! counted as zero.
+//!
+//! ```ignore
+//! let scope = SessionScope {
+//!     allowed_operations: vec!["Swap".into()],
+//!     max_amounts: vec![(Address::ZERO, 100_000_000_000)],
+//! };
+//! let session = wallet.create_session_key(scope, Timestamp::now() + 3600, 0x18b070)?;
+//! let bundle = session.sign_bundle(builder.build());
+//! verify_session(&bundle, session.authorization(), 0x18b070)?;
+//! ```
+//!
+//! ## Node support
+//!
+//! Session keys are checked off-chain only, by [`verify_session`] — e.g. by a
+//! relayer or dApp backend that holds the grant. The node doesn't know about
+//! them: [`SessionKey::sign_bundle`] only sets the bundle's `signature`,
+//! `TransactionBundle` has no field to carry the grant, and the
+//! `vexidus-session-v3` message is this SDK's format, not a node protocol. A
+//! session-signed bundle submitted as-is fails the node's signature check,
+//! since it's signed by the session key rather than the account's key.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use vexidus_types::{Address, Operation, PublicKey, Timestamp, TransactionBundle};
+
+use crate::address_utils;
+use crate::bundle::operation_name;
+use crate::wallet::{verify_with_pubkey, WalletKeypair};
+
+/// Domain separator for the authorization message.
+const SESSION_DOMAIN: &[u8] = b"vexidus-session-v3";
+
+#[derive(Error, Debug)]
+pub enum SessionError {
+    #[error("Session authorization signature is invalid")]
+    InvalidAuthorization,
+    #[error("Session was authorized for chain {authorized}, not {expected}")]
+    WrongChain { authorized: u64, expected: u64 },
+    #[error("Bundle account does not belong to the session's root key")]
+    AccountMismatch,
+    #[error("Operation {0} moves an amount the session limit can't account for")]
+    UnpricedOperation(String),
+    #[error("Session expired at {0}")]
+    Expired(u64),
+    #[error("Operation {0} is outside the session scope")]
+    OperationNotAllowed(String),
+    #[error("Bundle moves {total} of token 0x{}, session limit is {max}", hex::encode(.token.0))]
+    AmountExceeded { token: Address, total: u128, max: u128 },
+    #[error("Bundle signature does not match the session key")]
+    InvalidSignature,
+    #[error("Invalid session scope: {0}")]
+    InvalidScope(String),
+}
+
+/// What a session key may do.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionScope {
+    /// Allowed operation names (see [`operation_name`]), e.g. `"Swap"`.
+    pub allowed_operations: Vec<String>,
+    /// Per-token caps, `(token, max)`, on the raw amount a single bundle may
+    /// move; VXS is `Address::ZERO`. Tokens not listed can't be moved at all.
+    pub max_amounts: Vec<(Address, u128)>,
+}
+
+impl SessionScope {
+    /// Cap for `token`; 0 if it isn't listed.
+    fn max_amount(&self, token: &Address) -> u128 {
+        self.max_amounts.iter().find(|(t, _)| t == token).map_or(0, |(_, max)| *max)
+    }
+}
+
+/// Root-signed grant binding a session public key to a scope and expiry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionAuthorization {
+    pub root_pubkey: PublicKey,
+    pub session_pubkey: PublicKey,
+    pub scope: SessionScope,
+    pub valid_until: Timestamp,
+    /// Chain the grant is valid on (`eth_chainId`, e.g. `0x18b070` on testnet).
+    pub chain_id: u64,
+    /// Root key's Ed25519 signature over [`signing_hash`](Self::signing_hash).
+    pub signature: Vec<u8>,
+}
+
+impl SessionAuthorization {
+    /// Blake3 hash of the canonical authorization message:
+    /// `"vexidus-session-v3" || chain_id (u64 LE) || root_pubkey ||
+    /// session_pubkey || valid_until (u64 LE) || limit count (u32 LE) ||
+    /// for each limit: token || max (u128 LE) || op count (u32 LE) ||
+    /// for each op: len (u8) || name`.
+    ///
+    /// Fails with [`InvalidScope`](SessionError::InvalidScope) if an operation
+    /// name is over 255 bytes or a list is too long to count in a `u32`.
+    pub fn signing_hash(&self) -> Result<[u8; 32], SessionError> {
+        let count = |len: usize, what: &str| {
+            u32::try_from(len).map_err(|_| SessionError::InvalidScope(format!("too many {}", what)))
+        };
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(SESSION_DOMAIN);
+        hasher.update(&self.chain_id.to_le_bytes());
+        hasher.update(&self.root_pubkey.0);
+        hasher.update(&self.session_pubkey.0);
+        hasher.update(&self.valid_until.0.to_le_bytes());
+        hasher.update(&count(self.scope.max_amounts.len(), "limits")?.to_le_bytes());
+        for (token, max) in &self.scope.max_amounts {
+            hasher.update(&token.0);
+            hasher.update(&max.to_le_bytes());
+        }
+        hasher.update(&count(self.scope.allowed_operations.len(), "operations")?.to_le_bytes());
+        for op in &self.scope.allowed_operations {
+            let len = u8::try_from(op.len())
+                .map_err(|_| SessionError::InvalidScope(format!("operation name is {} bytes, max 255", op.len())))?;
+            hasher.update(&[len]);
+            hasher.update(op.as_bytes());
+        }
+        Ok(*hasher.finalize().as_bytes())
+    }
+}
+
+/// A subordinate keypair plus the root's authorization for it.
+pub struct SessionKey {
+    keypair: WalletKeypair,
+    authorization: SessionAuthorization,
+}
+
+impl SessionKey {
+    pub(crate) fn new(keypair: WalletKeypair, authorization: SessionAuthorization) -> Self {
+        Self { keypair, authorization }
+    }
+
+    /// The session's signing keypair.
+    pub fn keypair(&self) -> &WalletKeypair {
+        &self.keypair
+    }
+
+    /// The root-signed grant, for whoever runs [`verify_session`].
+    pub fn authorization(&self) -> &SessionAuthorization {
+        &self.authorization
+    }
+
+    /// Sign a bundle with the session key.
+    pub fn sign_bundle(&self, mut bundle: TransactionBundle) -> TransactionBundle {
+        bundle.signature = self.keypair.sign_bundle(&bundle);
+        bundle
+    }
+}
+
+/// Check that `bundle` was signed by the session key in `auth`, that `auth`
+/// was signed by its root key for `chain_id` and hasn't expired, that the
+/// bundle's account is the root key's account, and that every operation is
+/// within scope.
+pub fn verify_session(
+    bundle: &TransactionBundle,
+    auth: &SessionAuthorization,
+    chain_id: u64,
+) -> Result<(), SessionError> {
+    if !verify_with_pubkey(&auth.root_pubkey.0, &auth.signing_hash()?, &auth.signature) {
+        return Err(SessionError::InvalidAuthorization);
+    }
+    if auth.chain_id != chain_id {
+        return Err(SessionError::WrongChain { authorized: auth.chain_id, expected: chain_id });
+    }
+    if root_account(&auth.root_pubkey) != Some(bundle.user_account) {
+        return Err(SessionError::AccountMismatch);
+    }
+
+    if Timestamp::now().0 > auth.valid_until.0 {
+        return Err(SessionError::Expired(auth.valid_until.0));
+    }
+
+    let mut totals: Vec<(Address, u128)> = Vec::new();
+    for op in &bundle.operations {
+        let name = operation_name(op);
+        if !auth.scope.allowed_operations.iter().any(|allowed| allowed == name) {
+            return Err(SessionError::OperationNotAllowed(name.to_string()));
+        }
+        let amounts = operation_amounts(op)
+            .ok_or_else(|| SessionError::UnpricedOperation(name.to_string()))?;
+        for (token, amount) in amounts {
+            match totals.iter_mut().find(|(t, _)| *t == token) {
+                Some((_, total)) => *total = total.saturating_add(amount),
+                None => totals.push((token, amount)),
+            }
+        }
+    }
+    for (token, total) in totals {
+        let max = auth.scope.max_amount(&token);
+        if total > max {
+            return Err(SessionError::AmountExceeded { token, total, max });
+        }
+    }
+
+    if !bundle.verify_signature(&auth.session_pubkey) {
+        return Err(SessionError::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// The account address a root key controls (its Vx0 address, right-aligned).
+fn root_account(root_pubkey: &PublicKey) -> Option<Address> {
+    address_utils::vx0_to_bytes(&address_utils::vx0_from_pubkey(&root_pubkey.0))
+        .ok()
+        .map(Address)
+}
+
+/// Raw amounts an operation moves, per token, for scope limits; `None` if the
+/// SDK can't tell, which [`verify_session`] treats as out of scope.
+fn operation_amounts(op: &Operation) -> Option<Vec<(Address, u128)>> {
+    match op {
+        Operation::Transfer { token, amount, .. } => Some(vec![(*token, amount.0)]),
+        Operation::Stake { amount, .. }
+        | Operation::Unstake { amount }
+        | Operation::Delegate { amount, .. }
+        | Operation::Undelegate { amount, .. } => Some(vec![(Address::ZERO, amount.0)]),
+        Operation::Swap { from_token, amount_in, .. } => Some(vec![(*from_token, *amount_in)]),
+        Operation::AddLiquidity { token_a, token_b, amount_a, amount_b, .. }
+        | Operation::CreatePool { token_a, token_b, amount_a, amount_b, .. } => {
+            Some(vec![(*token_a, *amount_a), (*token_b, *amount_b)])
+        }
+        Operation::BridgeDeposit { proof, .. } => Some(vec![(
+            address_utils::bridged_mint(&proof.source_chain, &proof.token_contract),
+            proof.amount,
+        )]),
+        Operation::CreateProposal { treasury_amount, .. } => {
+            Some(vec![(Address::ZERO, treasury_amount.unwrap_or(0))])
+        }
+        Operation::AddKey { .. }
+        | Operation::RemoveKey { .. }
+        | Operation::RotateKey { .. }
+        | Operation::ClaimUnstake
+        | Operation::ClaimRewards
+        | Operation::SetCommission { .. }
+        | Operation::Unjail
+        | Operation::SetAutoCompound { .. }
+        | Operation::SetPoolConfig { .. }
+        | Operation::SetValidatorMetadata { .. }
+        | Operation::ScheduleUpgrade { .. }
+        | Operation::CancelUpgrade { .. }
+        | Operation::VoteUpgrade { .. }
+        | Operation::VoteProposal { .. }
+        | Operation::ExecuteProposal { .. }
+        | Operation::CancelProposal { .. }
+        | Operation::CreateMultiSig { .. }
+        | Operation::ApproveMultiSigTx { .. }
+        | Operation::RevokeMultiSigApproval { .. } => Some(Vec::new()),
+        // LP tokens (no mint address in the operation), intents, nested
+        // multisig proposals, name fees and anything newer
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::BundleBuilder;
+
+    const CHAIN: u64 = 0x18b070;
+
+    fn swap_session(root: &WalletKeypair) -> SessionKey {
+        let scope = SessionScope {
+            allowed_operations: vec!["Swap".into()],
+            max_amounts: vec![(Address::ZERO, 1_000)],
+        };
+        root.create_session_key(scope, Timestamp::now() + 3600, CHAIN).unwrap()
+    }
+
+    #[test]
+    fn test_session_in_scope_verifies() {
+        let root = WalletKeypair::generate();
+        let session = swap_session(&root);
+        let token_b = format!("0x{}", hex::encode([5u8; 32]));
+
+        let bundle = BundleBuilder::new(&root.hex_address())
+            .unwrap()
+            .swap("VXS", &token_b, 500, 0)
+            .unwrap()
+            .build();
+        let bundle = session.sign_bundle(bundle);
+        assert!(verify_session(&bundle, session.authorization(), CHAIN).is_ok());
+    }
+
+    #[test]
+    fn test_session_out_of_scope_rejected() {
+        let root = WalletKeypair::generate();
+        let session = swap_session(&root);
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+        let token_b = format!("0x{}", hex::encode([5u8; 32]));
+
+        // Transfer isn't in scope
+        let bundle = BundleBuilder::new(&root.hex_address())
+            .unwrap()
+            .transfer(&to, "VXS", 10)
+            .unwrap()
+            .build();
+        let bundle = session.sign_bundle(bundle);
+        assert!(matches!(
+            verify_session(&bundle, session.authorization(), CHAIN),
+            Err(SessionError::OperationNotAllowed(_))
+        ));
+
+        // Swap over the amount cap
+        let bundle = BundleBuilder::new(&root.hex_address())
+            .unwrap()
+            .swap("VXS", &token_b, 5_000, 0)
+            .unwrap()
+            .build();
+        let bundle = session.sign_bundle(bundle);
+        assert!(matches!(
+            verify_session(&bundle, session.authorization(), CHAIN),
+            Err(SessionError::AmountExceeded { .. })
+        ));
+
+        // Tampered scope invalidates the root signature
+        let mut auth = session.authorization().clone();
+        auth.scope.max_amounts = vec![(Address::ZERO, u128::MAX)];
+        assert!(matches!(
+            verify_session(&bundle, &auth, CHAIN),
+            Err(SessionError::InvalidAuthorization)
+        ));
+    }
+
+    #[test]
+    fn test_expired_session_rejected() {
+        let root = WalletKeypair::generate();
+        let scope = SessionScope { allowed_operations: vec!["Swap".into()], max_amounts: vec![(Address::ZERO, 1_000)] };
+        let session = root.create_session_key(scope, Timestamp(1), CHAIN).unwrap();
+        let bundle = BundleBuilder::new(&root.hex_address()).unwrap().build();
+        let bundle = session.sign_bundle(bundle);
+        assert!(matches!(
+            verify_session(&bundle, session.authorization(), CHAIN),
+            Err(SessionError::Expired(1))
+        ));
+    }
+
+    #[test]
+    fn test_session_for_other_account_rejected() {
+        let root = WalletKeypair::generate();
+        let victim = WalletKeypair::generate();
+        let session = swap_session(&root);
+        let token_b = format!("0x{}", hex::encode([5u8; 32]));
+
+        // A valid grant from `root` can't authorize bundles for `victim`
+        let bundle = BundleBuilder::new(&victim.hex_address())
+            .unwrap()
+            .swap("VXS", &token_b, 500, 0)
+            .unwrap()
+            .build();
+        let bundle = session.sign_bundle(bundle);
+        assert!(matches!(
+            verify_session(&bundle, session.authorization(), CHAIN),
+            Err(SessionError::AccountMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_session_limit_covers_unstake_and_undelegate() {
+        let root = WalletKeypair::generate();
+        let scope = SessionScope {
+            allowed_operations: vec!["Unstake".into(), "Undelegate".into(), "RegisterName".into()],
+            max_amounts: vec![(Address::ZERO, 1_000)],
+        };
+        let session = root.create_session_key(scope, Timestamp::now() + 3600, CHAIN).unwrap();
+        let validator = format!("0x{}", hex::encode([7u8; 32]));
+
+        let mut bundle = BundleBuilder::new(&root.hex_address()).unwrap().build();
+        bundle.operations = vec![Operation::Unstake { amount: vexidus_types::Amount(5_000) }];
+        let signed = session.sign_bundle(bundle.clone());
+        assert!(matches!(
+            verify_session(&signed, session.authorization(), CHAIN),
+            Err(SessionError::AmountExceeded { token: Address::ZERO, total: 5_000, max: 1_000 })
+        ));
+
+        bundle.operations = vec![Operation::Undelegate {
+            validator: crate::address_utils::parse_address(&validator).unwrap(),
+            amount: vexidus_types::Amount(600),
+        }; 2];
+        let signed = session.sign_bundle(bundle.clone());
+        assert!(matches!(
+            verify_session(&signed, session.authorization(), CHAIN),
+            Err(SessionError::AmountExceeded { token: Address::ZERO, total: 1_200, max: 1_000 })
+        ));
+
+        // In scope by name, but its cost isn't known to the SDK
+        bundle.operations = vec![Operation::RegisterName { name: "alice".into() }];
+        let signed = session.sign_bundle(bundle);
+        assert!(matches!(
+            verify_session(&signed, session.authorization(), CHAIN),
+            Err(SessionError::UnpricedOperation(_))
+        ));
+    }
+
+    #[test]
+    fn test_session_limits_are_per_token() {
+        let root = WalletKeypair::generate();
+        let usdc = Address([5u8; 32]);
+        let scope = SessionScope {
+            allowed_operations: vec!["Transfer".into(), "RemoveLiquidity".into()],
+            max_amounts: vec![(Address::ZERO, 1_000), (usdc, 50)],
+        };
+        let session = root.create_session_key(scope, Timestamp::now() + 3600, CHAIN).unwrap();
+        let to = Address([2u8; 32]);
+        let transfer = |token: Address, amount: u128| Operation::Transfer { to, token, amount: vexidus_types::Amount(amount) };
+        let check = |operations: Vec<Operation>| {
+            let mut bundle = BundleBuilder::new(&root.hex_address()).unwrap().build();
+            bundle.operations = operations;
+            verify_session(&session.sign_bundle(bundle), session.authorization(), CHAIN)
+        };
+
+        // 900 VXS and 40 USDC are each under their own cap, though not under one shared cap
+        assert!(check(vec![transfer(Address::ZERO, 900), transfer(usdc, 40)]).is_ok());
+        assert!(matches!(
+            check(vec![transfer(usdc, 40), transfer(usdc, 20)]),
+            Err(SessionError::AmountExceeded { token, total: 60, max: 50 }) if token == usdc
+        ));
+        // A token without a cap can't be moved
+        let other = Address([9u8; 32]);
+        assert!(matches!(
+            check(vec![transfer(other, 1)]),
+            Err(SessionError::AmountExceeded { token, total: 1, max: 0 }) if token == other
+        ));
+        // LP units have no token to cap
+        let withdraw = Operation::RemoveLiquidity {
+            token_a: Address::ZERO,
+            token_b: usdc,
+            lp_amount: 1,
+            min_amount_a: 0,
+            min_amount_b: 0,
+        };
+        assert!(matches!(check(vec![withdraw]), Err(SessionError::UnpricedOperation(_))));
+
+        // Names too long for the u8 length prefix are refused, not truncated
+        let scope = SessionScope { allowed_operations: vec!["x".repeat(256)], max_amounts: Vec::new() };
+        assert!(matches!(
+            root.create_session_key(scope, Timestamp::now() + 3600, CHAIN),
+            Err(SessionError::InvalidScope(_))
+        ));
+    }
+
+    #[test]
+    fn test_session_bound_to_chain() {
+        let root = WalletKeypair::generate();
+        let session = swap_session(&root);
+        let bundle = session.sign_bundle(BundleBuilder::new(&root.hex_address()).unwrap().build());
+
+        assert!(matches!(
+            verify_session(&bundle, session.authorization(), 0x18b071),
+            Err(SessionError::WrongChain { authorized: CHAIN, expected: 0x18b071 })
+        ));
+
+        // Re-labelling the chain breaks the root signature
+        let mut auth = session.authorization().clone();
+        auth.chain_id = 0x18b071;
+        assert!(matches!(
+            verify_session(&bundle, &auth, 0x18b071),
+            Err(SessionError::InvalidAuthorization)
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+//! Staking reward arithmetic.
+//!
+//! ```ignore
+//! let split = reward_split(1_000_000, 100_000, 900_000, 500);
+//! assert_eq!(split.validator_commission, 45_000);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// How one epoch's reward for a validator is divided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardSplit {
+    /// Commission taken from the delegated portion.
+    pub validator_commission: u128,
+    /// Reward earned on the validator's own self-bond.
+    pub validator_self_reward: u128,
+    /// What remains for delegators, shared pro rata.
+    pub delegators_reward: u128,
+}
+
+impl RewardSplit {
+    /// Everything the validator keeps: commission plus self-bond reward.
+    pub fn validator_total(&self) -> u128 {
+        self.validator_commission + self.validator_self_reward
+    }
+}
+
+/// Split `total_reward` between a validator and its delegators.
+///
+/// The reward is first divided pro rata by stake: the self-bond's share goes
+/// to the validator in full, and `commission_bps` (capped at 10000) of the
+/// delegated share is taken as commission. All math is integer; rounding dust
+/// goes to delegators, so the three parts always sum to `total_reward`. With
+/// no stake at all the whole reward counts as self-reward.
+pub fn reward_split(
+    total_reward: u128,
+    self_bond: u128,
+    total_delegated: u128,
+    commission_bps: u16,
+) -> RewardSplit {
+    let total_stake = self_bond.saturating_add(total_delegated);
+    let validator_self_reward = if total_stake == 0 {
+        total_reward
+    } else {
+        mul_div(total_reward, self_bond, total_stake)
+    };
+    let delegated_reward = total_reward - validator_self_reward;
+    let validator_commission = mul_div(delegated_reward, commission_bps.min(10_000) as u128, 10_000);
+    RewardSplit {
+        validator_commission,
+        validator_self_reward,
+        delegators_reward: delegated_reward - validator_commission,
+    }
+}
+
+/// `a * b / c` rounded down, for `b <= c`.
+///
+/// Exact unless `a * b` overflows u128; then `a = q*c + r` is split and the
+/// `r * b / c` term falls back to float math (off by at most a few units).
+fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    match a.checked_mul(b) {
+        Some(product) => product / c,
+        None => {
+            let remainder = match (a % c).checked_mul(b) {
+                Some(product) => product / c,
+                None => ((a % c) as f64 * b as f64 / c as f64) as u128,
+            };
+            (a / c) * b + remainder.min(b)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reward_split() {
+        // 10% self-bond, 5% commission
+        let split = reward_split(1_000_000, 100_000, 900_000, 500);
+        assert_eq!(split.validator_self_reward, 100_000);
+        assert_eq!(split.validator_commission, 45_000);
+        assert_eq!(split.delegators_reward, 855_000);
+        assert_eq!(split.validator_total(), 145_000);
+
+        // Uneven division: parts still sum to the total
+        let split = reward_split(1_000_003, 333, 667, 1_234);
+        assert_eq!(
+            split.validator_commission + split.validator_self_reward + split.delegators_reward,
+            1_000_003
+        );
+
+        assert_eq!(reward_split(500, 0, 0, 500).validator_self_reward, 500);
+        let huge = reward_split(u128::MAX, u128::MAX / 4, u128::MAX / 4, 10_000);
+        assert_eq!(huge.delegators_reward, 0);
+    }
+}
@@ -0,0 +1,634 @@
+//! JSON-RPC 2.0 transport shared by the SDK clients.
+//!
+//! Clients talk to a node over HTTP by default. [`MockTransport`] swaps in
+//! canned responses so client logic can be exercised without a node:
+//!
+//! ```ignore
+//! let mock = MockTransport::new().with_result("eth_blockNumber", json!("0x10"));
+//! let client = WalletClient::with_mock(mock.clone());
+//! assert_eq!(client.block_number().await?, 16);
+//! assert_eq!(mock.call_count("eth_blockNumber"), 1);
+//! ```
+//!
+//! ## Paging
+//!
+//! The `*_paged` list methods take a cursor and return the next one. A `None`
+//! cursor starts from the beginning; keep passing back the returned cursor
+//! until it comes back `None`. The `list_all_*` helpers walk every page for
+//! you, and error out if the node hands back a cursor it already gave
+//! instead of looping forever.
+//!
+//! The wire format is an assumption about node support, not something the
+//! node's RPC is documented to provide: the cursor is sent as the second
+//! param after the page size, and a paged response is `{ "items": [...],
+//! "next_cursor": "..." }` (or `nextCursor`). An `items` that is `null` or
+//! missing is an empty page. Nodes that don't page answer with a bare array,
+//! which is the only page, so the helpers still work against them.
+//!
+//! ## Tracing
+//!
+//! With the `tracing` feature, every request runs in an `rpc` span with
+//! `rpc.method`, `rpc.url` and, for HTTP, `http.status` fields, and ends with
+//! an event carrying `latency_ms`: `DEBUG` on success, `WARN` with the error
+//! on failure. Retried reads get one span per attempt; batches aren't traced.
+//! Params can contain addresses, so they're only recorded (as `rpc.params`)
+//! on clients built `with_log_params(true)`.
+
+use rand_core::{OsRng, RngCore};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+#[cfg(feature = "tracing")]
+use web_time::Instant;
+#[cfg(feature = "tracing")]
+use tracing::Instrument;
+
+use crate::wallet_client::WalletClientError;
+
+/// Per-request HTTP timeout used unless a client sets its own.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How idempotent read calls are retried after transient failures.
+///
+/// Only errors where [`WalletClientError::is_transient`] holds are retried.
+/// Attempt `n` (0-based) waits `backoff * 2^n` plus up to 50% random jitter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub const NONE: RetryPolicy = RetryPolicy { max_retries: 0, backoff: Duration::ZERO };
+
+    fn delay(&self, attempt: u32) -> Duration {
+        let base = self.backoff.saturating_mul(1u32 << attempt.min(16));
+        let jitter_range = base.as_millis() as u64 / 2;
+        let jitter = if jitter_range > 0 { OsRng.next_u64() % (jitter_range + 1) } else { 0 };
+        base + Duration::from_millis(jitter)
+    }
+}
+
+/// Where a client sends its JSON-RPC requests, and with what timeout and
+/// retry policy.
+#[derive(Clone)]
+pub(crate) struct Transport {
+    backend: Backend,
+    timeout: Duration,
+    retry: RetryPolicy,
+    /// Record request params on the `rpc` span.
+    #[cfg(feature = "tracing")]
+    log_params: bool,
+}
+
+#[derive(Clone)]
+enum Backend {
+    Http { url: String, client: reqwest::Client },
+    Mock(MockTransport),
+}
+
+impl Transport {
+    pub(crate) fn http(url: &str) -> Self {
+        Self {
+            backend: Backend::Http {
+                url: url.to_string(),
+                client: reqwest::Client::new(),
+            },
+            timeout: DEFAULT_TIMEOUT,
+            retry: RetryPolicy::NONE,
+            #[cfg(feature = "tracing")]
+            log_params: false,
+        }
+    }
+
+    pub(crate) fn mock(mock: MockTransport) -> Self {
+        Self {
+            backend: Backend::Mock(mock),
+            timeout: DEFAULT_TIMEOUT,
+            retry: RetryPolicy::NONE,
+            #[cfg(feature = "tracing")]
+            log_params: false,
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn set_log_params(&mut self, enabled: bool) {
+        self.log_params = enabled;
+    }
+
+    /// Effective per-request timeout.
+    pub(crate) fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub(crate) fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub(crate) fn retry_policy(&self) -> RetryPolicy {
+        self.retry
+    }
+
+    pub(crate) fn set_retry_policy(&mut self, retry: RetryPolicy) {
+        self.retry = retry;
+    }
+
+    /// Send an idempotent read, retrying transient failures per the
+    /// [`RetryPolicy`].
+    pub(crate) async fn call_read(&self, method: &str, params: Value) -> Result<Value, WalletClientError> {
+        let mut attempt = 0;
+        loop {
+            match self.call(method, params.clone()).await {
+                Err(e) if e.is_transient() && attempt < self.retry.max_retries => {
+                    let delay = self.retry.delay(attempt);
+                    log::debug!("{} failed ({}), retrying in {:?}", method, e, delay);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Send one JSON-RPC request and return its `result`. Never retried, so
+    /// writes go through here.
+    ///
+    /// A JSON-RPC `error` object comes back as [`WalletClientError::Rpc`].
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) async fn call(&self, method: &str, params: Value) -> Result<Value, WalletClientError> {
+        self.send(method, params).await
+    }
+
+    /// See the untraced `call`; this one wraps the request in an `rpc` span
+    /// (see the [module docs](self#tracing)).
+    #[cfg(feature = "tracing")]
+    pub(crate) async fn call(&self, method: &str, params: Value) -> Result<Value, WalletClientError> {
+        let url = match &self.backend {
+            Backend::Http { url, .. } => url.as_str(),
+            Backend::Mock(_) => "mock://",
+        };
+        let span = tracing::info_span!(
+            "rpc",
+            rpc.method = method,
+            rpc.url = url,
+            http.status = tracing::field::Empty,
+            rpc.params = tracing::field::Empty,
+        );
+        if self.log_params {
+            span.record("rpc.params", tracing::field::display(&params));
+        }
+        let start = Instant::now();
+        let result = self.send(method, params).instrument(span.clone()).await;
+        let latency_ms = start.elapsed().as_millis() as u64;
+        match &result {
+            Ok(_) => tracing::debug!(parent: &span, latency_ms, "RPC call succeeded"),
+            Err(e) => tracing::warn!(parent: &span, latency_ms, error = %e, "RPC call failed"),
+        }
+        result
+    }
+
+    /// Start a POST to `url` with the current timeout applied to the request.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn post(&self, client: &reqwest::Client, url: &str) -> reqwest::RequestBuilder {
+        client.post(url).timeout(self.timeout)
+    }
+
+    /// Browser `fetch` has no client-side timeout in reqwest; the browser's
+    /// own request timeout applies instead.
+    #[cfg(target_arch = "wasm32")]
+    fn post(&self, client: &reqwest::Client, url: &str) -> reqwest::RequestBuilder {
+        client.post(url)
+    }
+
+    async fn send(&self, method: &str, params: Value) -> Result<Value, WalletClientError> {
+        let resp = match &self.backend {
+            Backend::Http { url, client } => {
+                let body = json!({
+                    "jsonrpc": "2.0",
+                    "method": method,
+                    "params": params,
+                    "id": 1
+                });
+                let response = self.post(client, url).json(&body).send().await?;
+                #[cfg(feature = "tracing")]
+                tracing::Span::current().record("http.status", response.status().as_u16());
+                response.json::<Value>().await?
+            }
+            Backend::Mock(mock) => mock.respond(method, params)?,
+        };
+
+        if let Some(error) = resp.get("error") {
+            return Err(WalletClientError::from_rpc_error(error));
+        }
+
+        Ok(resp["result"].clone())
+    }
+
+    /// Send several requests as one JSON-RPC batch (a single POST), never
+    /// retried.
+    ///
+    /// Responses are matched back to requests by `id`, so the output is in
+    /// request order whatever order the node answers in. Entries fail
+    /// individually; the outer error is for the batch as a whole (transport
+    /// failure, or the node rejecting the batch outright).
+    pub(crate) async fn call_batch(
+        &self,
+        requests: &[(String, Value)],
+    ) -> Result<Vec<Result<Value, WalletClientError>>, WalletClientError> {
+        if requests.is_empty() {
+            return Ok(Vec::new());
+        }
+        let resp = match &self.backend {
+            Backend::Http { url, client } => {
+                let body: Vec<Value> = requests
+                    .iter()
+                    .enumerate()
+                    .map(|(id, (method, params))| {
+                        json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": id })
+                    })
+                    .collect();
+                self.post(client, url)
+                    .json(&body)
+                    .send()
+                    .await?
+                    .json::<Value>()
+                    .await?
+            }
+            Backend::Mock(mock) => mock.respond_batch(requests)?,
+        };
+
+        let entries = match resp {
+            Value::Array(entries) => entries,
+            other => {
+                return Err(match other.get("error") {
+                    Some(error) => WalletClientError::from_rpc_error(error),
+                    None => WalletClientError::Decode("Batch response is not an array".into()),
+                })
+            }
+        };
+        let mut results: Vec<Option<Result<Value, WalletClientError>>> =
+            (0..requests.len()).map(|_| None).collect();
+        for entry in entries {
+            let Some(slot) = entry["id"].as_u64().and_then(|id| results.get_mut(id as usize)) else {
+                log::debug!("Ignoring batch response with unknown id: {}", entry["id"]);
+                continue;
+            };
+            *slot = Some(match entry.get("error") {
+                Some(error) => Err(WalletClientError::from_rpc_error(error)),
+                None => Ok(entry["result"].clone()),
+            });
+        }
+        Ok(results
+            .into_iter()
+            .enumerate()
+            .map(|(id, result)| {
+                result.unwrap_or_else(|| {
+                    Err(WalletClientError::Decode(format!("No response for batch request {}", id)))
+                })
+            })
+            .collect())
+    }
+}
+
+
+/// Split a list response into its entries and the next page's cursor; see
+/// the [module docs](self#paging). An empty cursor means there's no next page,
+/// and a `null` or missing list is empty.
+pub(crate) fn split_page(result: Value) -> (Value, Option<String>) {
+    match result {
+        Value::Object(mut page) => {
+            let items = match page.remove("items") {
+                None | Some(Value::Null) => Value::Array(Vec::new()),
+                Some(items) => items,
+            };
+            let cursor = page
+                .get("next_cursor")
+                .or_else(|| page.get("nextCursor"))
+                .and_then(Value::as_str)
+                .filter(|c| !c.is_empty())
+                .map(str::to_string);
+            (items, cursor)
+        }
+        Value::Null => (Value::Array(Vec::new()), None),
+        other => (other, None),
+    }
+}
+
+/// Collect every page of a [paged](self#paging) list, fetching each with
+/// `fetch(cursor)`. A cursor the node already returned once means it's
+/// cycling, reported as `cycle(cursor)`.
+pub(crate) async fn collect_pages<T, E, F, Fut>(mut fetch: F, cycle: impl Fn(String) -> E) -> Result<Vec<T>, E>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<String>), E>>,
+{
+    let mut items = Vec::new();
+    let mut seen = HashSet::new();
+    let mut cursor = None;
+    loop {
+        let (page, next) = fetch(cursor.take()).await?;
+        items.extend(page);
+        match next {
+            None => return Ok(items),
+            Some(next) if !seen.insert(next.clone()) => return Err(cycle(next)),
+            Some(next) => cursor = Some(next),
+        }
+    }
+}
+
+/// Read a raw amount that the node may encode as a decimal string or a number.
+pub(crate) fn value_to_u128(value: &Value) -> Option<u128> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_u64().map(u128::from),
+        _ => None,
+    }
+}
+
+/// In-memory JSON-RPC responder for tests and offline tooling.
+///
+/// Responses are queued per method and returned in order; the last queued
+/// response for a method repeats once the queue is drained. Unknown methods
+/// answer with JSON-RPC error `-32601`. [`with_timeout_error`](Self::with_timeout_error)
+/// simulates a request that never got an answer, and
+/// [`with_connect_error`](Self::with_connect_error) one that never reached the
+/// node. Clones share state, so keep a clone
+/// to inspect [`calls`](Self::calls) after handing one to a client.
+///
+/// Batches are answered entry by entry from the same queues, with the
+/// responses in reverse order — servers may reorder them, so callers must
+/// match by `id`. A queued timeout or connect error fails the whole batch.
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    responses: HashMap<String, VecDeque<MockResponse>>,
+    calls: Vec<(String, Value)>,
+    batches: usize,
+}
+
+#[derive(Clone)]
+enum MockResponse {
+    Reply(Value),
+    Timeout,
+    ConnectError,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a successful `result` for `method`.
+    pub fn with_result(self, method: &str, result: Value) -> Self {
+        self.push(method, MockResponse::Reply(json!({ "jsonrpc": "2.0", "result": result, "id": 1 })));
+        self
+    }
+
+    /// Queue a request timeout for `method`.
+    pub fn with_timeout_error(self, method: &str) -> Self {
+        self.push(method, MockResponse::Timeout);
+        self
+    }
+
+    /// Queue a connection failure for `method`: the request never reaches the
+    /// node.
+    pub fn with_connect_error(self, method: &str) -> Self {
+        self.push(method, MockResponse::ConnectError);
+        self
+    }
+
+    /// Queue a JSON-RPC error response for `method`.
+    pub fn with_error(self, method: &str, code: i64, message: &str) -> Self {
+        self.push(
+            method,
+            MockResponse::Reply(json!({
+                "jsonrpc": "2.0",
+                "error": { "code": code, "message": message },
+                "id": 1
+            })),
+        );
+        self
+    }
+
+    /// Every `(method, params)` received so far, in order.
+    pub fn calls(&self) -> Vec<(String, Value)> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    /// Number of requests received for `method`.
+    pub fn call_count(&self, method: &str) -> usize {
+        self.state
+            .lock()
+            .unwrap()
+            .calls
+            .iter()
+            .filter(|(m, _)| m == method)
+            .count()
+    }
+
+    /// Number of batch requests received.
+    pub fn batch_count(&self) -> usize {
+        self.state.lock().unwrap().batches
+    }
+
+    fn push(&self, method: &str, response: MockResponse) {
+        self.state
+            .lock()
+            .unwrap()
+            .responses
+            .entry(method.to_string())
+            .or_default()
+            .push_back(response);
+    }
+
+    fn respond(&self, method: &str, params: Value) -> Result<Value, WalletClientError> {
+        let mut state = self.state.lock().unwrap();
+        state.calls.push((method.to_string(), params));
+        let response = match state.responses.get_mut(method) {
+            Some(queue) if queue.len() > 1 => queue.pop_front().unwrap(),
+            Some(queue) if !queue.is_empty() => queue[0].clone(),
+            _ => MockResponse::Reply(json!({
+                "jsonrpc": "2.0",
+                "error": { "code": -32601, "message": format!("Method not found: {}", method) },
+                "id": 1
+            })),
+        };
+        match response {
+            MockResponse::Reply(value) => Ok(value),
+            MockResponse::Timeout => Err(WalletClientError::Timeout),
+            MockResponse::ConnectError => Err(WalletClientError::Connect(format!("connection refused ({})", method))),
+        }
+    }
+
+    fn respond_batch(&self, requests: &[(String, Value)]) -> Result<Value, WalletClientError> {
+        self.state.lock().unwrap().batches += 1;
+        let mut entries = Vec::with_capacity(requests.len());
+        for (id, (method, params)) in requests.iter().enumerate() {
+            let mut entry = self.respond(method, params.clone())?;
+            entry["id"] = json!(id);
+            entries.push(entry);
+        }
+        entries.reverse();
+        Ok(Value::Array(entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_queue_and_errors() {
+        let mock = MockTransport::new()
+            .with_result("vex_a", json!(1))
+            .with_result("vex_a", json!(2))
+            .with_error("vex_b", -32000, "boom");
+        let transport = Transport::mock(mock.clone());
+
+        assert_eq!(transport.call("vex_a", json!([])).await.unwrap(), json!(1));
+        assert_eq!(transport.call("vex_a", json!([])).await.unwrap(), json!(2));
+        // Last response repeats
+        assert_eq!(transport.call("vex_a", json!([])).await.unwrap(), json!(2));
+
+        let err = transport.call("vex_b", json!([])).await.unwrap_err();
+        assert!(err.to_string().contains("boom"));
+        assert!(matches!(
+            transport.call("vex_unknown", json!([])).await,
+            Err(WalletClientError::Rpc { code: -32601, .. })
+        ));
+
+        assert_eq!(mock.call_count("vex_a"), 3);
+        assert_eq!(mock.calls().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_collect_pages_detects_cycles() {
+        let pages = |script: Vec<(Value, Option<&'static str>)>| {
+            let script = Mutex::new(VecDeque::from(script));
+            move |_cursor: Option<String>| {
+                let (items, next) = script.lock().unwrap().pop_front().unwrap();
+                async move { Ok::<_, String>((vec![items], next.map(str::to_string))) }
+            }
+        };
+        let all = collect_pages(pages(vec![(json!(1), Some("a")), (json!(2), Some("b")), (json!(3), None)]), |c| c)
+            .await
+            .unwrap();
+        assert_eq!(all, vec![json!(1), json!(2), json!(3)]);
+
+        // a -> b -> a would loop forever, though no cursor repeats back to back
+        let cycle = pages(vec![(json!(1), Some("a")), (json!(2), Some("b")), (json!(3), Some("a"))]);
+        assert_eq!(collect_pages(cycle, |c| c).await.unwrap_err(), "a");
+
+        assert_eq!(split_page(json!({ "items": null, "next_cursor": "" })), (json!([]), None));
+        assert_eq!(split_page(json!({ "next_cursor": "c" })), (json!([]), Some("c".into())));
+        assert_eq!(split_page(Value::Null), (json!([]), None));
+        assert_eq!(split_page(json!([1])), (json!([1]), None));
+    }
+
+    #[tokio::test]
+    async fn test_batch_matches_ids_and_keeps_order() {
+        let mock = MockTransport::new()
+            .with_result("vex_a", json!(1))
+            .with_error("vex_b", -32000, "boom")
+            .with_result("vex_c", json!(3));
+        let transport = Transport::mock(mock.clone());
+
+        let requests: Vec<(String, Value)> =
+            ["vex_a", "vex_b", "vex_c"].iter().map(|m| (m.to_string(), json!([]))).collect();
+        let results = transport.call_batch(&requests).await.unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap(), &json!(1));
+        assert!(matches!(results[1], Err(WalletClientError::Rpc { code: -32000, .. })));
+        assert_eq!(results[2].as_ref().unwrap(), &json!(3));
+        assert_eq!(mock.batch_count(), 1);
+
+        assert!(transport.call_batch(&[]).await.unwrap().is_empty());
+        assert_eq!(mock.batch_count(), 1);
+
+        let mock = MockTransport::new().with_result("vex_a", json!(1)).with_timeout_error("vex_b");
+        let transport = Transport::mock(mock);
+        assert!(matches!(transport.call_batch(&requests[..2]).await, Err(WalletClientError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_retry_only_reads() {
+        let mock = MockTransport::new()
+            .with_timeout_error("vex_a")
+            .with_timeout_error("vex_a")
+            .with_result("vex_a", json!(1));
+        let mut transport = Transport::mock(mock.clone());
+        assert_eq!(transport.timeout(), DEFAULT_TIMEOUT);
+
+        // Without a policy the first timeout is returned
+        assert!(matches!(
+            transport.call_read("vex_a", json!([])).await,
+            Err(WalletClientError::Timeout)
+        ));
+
+        transport.set_retry_policy(RetryPolicy { max_retries: 3, backoff: Duration::from_millis(1) });
+        assert_eq!(transport.call_read("vex_a", json!([])).await.unwrap(), json!(1));
+        assert_eq!(mock.call_count("vex_a"), 3);
+
+        // Writes go through `call` and are never retried
+        let mock = MockTransport::new().with_timeout_error("vex_w").with_result("vex_w", json!(1));
+        let mut transport = Transport::mock(mock.clone());
+        transport.set_retry_policy(RetryPolicy { max_retries: 3, backoff: Duration::from_millis(1) });
+        assert!(transport.call("vex_w", json!([])).await.is_err());
+        assert_eq!(mock.call_count("vex_w"), 1);
+    }
+    /// Formatted `tracing` output, shared with the test.
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    #[cfg(feature = "tracing")]
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn test_calls_emit_rpc_spans() {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mock = MockTransport::new()
+            .with_result("vex_a", json!(1))
+            .with_error("vex_b", -32000, "boom");
+        let mut transport = Transport::mock(mock);
+        transport.call("vex_a", json!(["0xsecret"])).await.unwrap();
+        transport.call("vex_b", json!([])).await.unwrap_err();
+        transport.set_log_params(true);
+        transport.call("vex_a", json!(["0xshown"])).await.unwrap();
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3, "{}", output);
+        assert!(lines[0].contains("DEBUG rpc{rpc.method=\"vex_a\" rpc.url=\"mock://\"}"), "{}", output);
+        assert!(lines[0].contains("latency_ms="), "{}", output);
+        assert!(!output.contains("0xsecret"), "{}", output);
+        assert!(lines[1].contains("WARN rpc{rpc.method=\"vex_b\""), "{}", output);
+        assert!(lines[1].contains("boom"), "{}", output);
+        assert!(lines[2].contains("rpc.params=[\"0xshown\"]"), "{}", output);
+    }
+}
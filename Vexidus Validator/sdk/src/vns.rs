@@ -0,0 +1,164 @@
+//! Vexidus Name Service lookups: `.vex` names to addresses and back.
+//!
+//! ```ignore
+//! let vns = VnsClient::new("http://localhost:9933");
+//! let chris = vns.resolve("chris.vex").await?;
+//! let to = vns.parse_address("Vx0abc...").await?; // names or plain addresses
+//! assert_eq!(vns.reverse_lookup(&chris).await?, Some("chris.vex".into()));
+//! ```
+//!
+//! Resolutions are cached per client for [`DEFAULT_CACHE_TTL`] (see
+//! [`VnsClient::with_cache_ttl`]), so a name that changes hands can resolve
+//! to its old holder until the entry expires. Reverse lookups aren't cached.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use web_time::Instant;
+use vexidus_types::Address;
+
+use crate::address_utils;
+use crate::transport::{MockTransport, Transport};
+
+/// How long a resolved name is reused before asking the node again.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Async client for `.vex` name resolution.
+pub struct VnsClient {
+    rpc_url: String,
+    transport: Transport,
+    cache_ttl: Duration,
+    /// Bare name (no `.vex`, lowercase) → address and when it was resolved.
+    cache: Mutex<HashMap<String, (Address, Instant)>>,
+}
+
+impl VnsClient {
+    /// Create a new VNS client.
+    pub fn new(rpc_url: &str) -> Self {
+        Self::with_transport(rpc_url, Transport::http(rpc_url))
+    }
+
+    /// Create a client backed by canned responses (see [`MockTransport`]).
+    pub fn with_mock(mock: MockTransport) -> Self {
+        Self::with_transport("mock://", Transport::mock(mock))
+    }
+
+    fn with_transport(rpc_url: &str, transport: Transport) -> Self {
+        Self {
+            rpc_url: rpc_url.to_string(),
+            transport,
+            cache_ttl: DEFAULT_CACHE_TTL,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reuse resolutions for `ttl` (default [`DEFAULT_CACHE_TTL`], 60s).
+    /// `Duration::ZERO` disables the cache.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// RPC endpoint this client talks to.
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// Resolve `name` (with or without `.vex`, any case) to its holder's
+    /// address via `vex_resolveName`. Errors if the name isn't registered.
+    pub async fn resolve(&self, name: &str) -> Result<Address> {
+        let name = bare_name(name);
+        if let Some((address, at)) = self.cache.lock().unwrap().get(&name) {
+            if at.elapsed() < self.cache_ttl {
+                return Ok(*address);
+            }
+        }
+        let result = self.transport.call_read("vex_resolveName", json!([name])).await?;
+        let Some(holder) = result.as_str() else {
+            anyhow::bail!("{}.vex is not registered", name);
+        };
+        let address = address_utils::parse_address(holder)?;
+        self.cache.lock().unwrap().insert(name, (address, Instant::now()));
+        Ok(address)
+    }
+
+    /// The `.vex` name `address` reverse-resolves to (`vex_reverseLookup`),
+    /// or `None` if it has none.
+    pub async fn reverse_lookup(&self, address: &Address) -> Result<Option<String>> {
+        let hex_address = format!("0x{}", hex::encode(address.0));
+        let result = self.transport.call_read("vex_reverseLookup", json!([hex_address])).await?;
+        Ok(match result {
+            Value::String(name) if !name.is_empty() => Some(format!("{}.vex", bare_name(&name))),
+            _ => None,
+        })
+    }
+
+    /// [`address_utils::parse_address`] that also accepts `name.vex`,
+    /// resolved with [`resolve`](Self::resolve).
+    pub async fn parse_address(&self, input: &str) -> Result<Address> {
+        let input = input.trim();
+        if input.to_lowercase().ends_with(".vex") {
+            self.resolve(input).await
+        } else {
+            Ok(address_utils::parse_address(input)?)
+        }
+    }
+
+    /// Drop every cached resolution.
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+}
+
+/// `"Chris.VEX"` → `"chris"`, the form `vex_resolveName` takes.
+pub(crate) fn bare_name(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    name.strip_suffix(".vex").unwrap_or(&name).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolve_cache_and_reverse_lookup() {
+        let chris = Address([7u8; 32]);
+        let chris_hex = format!("0x{}", hex::encode(chris.0));
+        let mock = MockTransport::new()
+            .with_result("vex_resolveName", json!(chris_hex))
+            .with_result("vex_resolveName", Value::Null)
+            .with_result("vex_reverseLookup", json!("chris"))
+            .with_result("vex_reverseLookup", Value::Null);
+        let vns = VnsClient::with_mock(mock.clone());
+
+        assert_eq!(vns.resolve("chris.vex").await.unwrap(), chris);
+        assert_eq!(vns.resolve("Chris.VEX").await.unwrap(), chris);
+        assert_eq!(vns.parse_address("chris.vex").await.unwrap(), chris);
+        assert_eq!(mock.call_count("vex_resolveName"), 1);
+        let (_, params) = mock.calls().into_iter().find(|(m, _)| m == "vex_resolveName").unwrap();
+        assert_eq!(params, json!(["chris"]));
+
+        assert!(vns.resolve("nobody.vex").await.is_err());
+        assert_eq!(vns.parse_address(&chris_hex).await.unwrap(), chris);
+        assert_eq!(mock.call_count("vex_resolveName"), 2);
+
+        assert_eq!(vns.reverse_lookup(&chris).await.unwrap(), Some("chris.vex".into()));
+        assert_eq!(vns.reverse_lookup(&Address([8u8; 32])).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries_are_refetched() {
+        let first = format!("0x{}", hex::encode([1u8; 32]));
+        let second = format!("0x{}", hex::encode([2u8; 32]));
+        let mock = MockTransport::new()
+            .with_result("vex_resolveName", json!(first))
+            .with_result("vex_resolveName", json!(second));
+        let vns = VnsClient::with_mock(mock.clone()).with_cache_ttl(Duration::ZERO);
+
+        assert_eq!(vns.resolve("alice").await.unwrap(), Address([1u8; 32]));
+        assert_eq!(vns.resolve("alice").await.unwrap(), Address([2u8; 32]));
+        assert_eq!(mock.call_count("vex_resolveName"), 2);
+    }
+}
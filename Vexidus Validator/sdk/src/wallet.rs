@@ -3,13 +3,50 @@
 //! Distinct from [`ValidatorKeypair`](crate::ValidatorKeypair) which is used for
 //! block signing in consensus. `WalletKeypair` is for end-user transaction signing
 //! and includes Vx0 address derivation.
+//!
+//! ## WebAssembly
+//!
+//! Key generation, signing and keystores also work in a browser wallet built
+//! for `wasm32-unknown-unknown`, with entropy from the browser's
+//! `crypto.getRandomValues` (`getrandom`'s `js` feature). There is no
+//! filesystem there, so the file-based `load`/`save` methods are left out;
+//! keep the [`to_keystore_json`](WalletKeypair::to_keystore_json) output in
+//! browser storage instead. To check the build:
+//!
+//! ```text
+//! rustup target add wasm32-unknown-unknown
+//! cargo check -p vexidus-sdk --target wasm32-unknown-unknown
+//! ```
+//!
+//! Tokio only supports its `sync`, `macros`, `io-util`, `rt` and `time`
+//! features on that target, so the workspace's `tokio` dependency must not
+//! enable more (e.g. `full`) for it. `std::time::Instant` panics there, so
+//! the rate limiter and client caches use `web_time::Instant`; anything that
+//! sleeps on a tokio timer (RPC retry backoff, the `wait_for_*` helpers)
+//! still needs a tokio runtime, which a browser doesn't have.
 
-use ed25519_dalek::{SigningKey, Signer};
+use anyhow::Result;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use ed25519_dalek::{SigningKey, Signer, Verifier, VerifyingKey};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+#[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
 use thiserror::Error;
-use vexidus_types::{PublicKey, Signature, TransactionBundle};
+use vexidus_types::{Address, PublicKey, Signature, Timestamp, TransactionBundle};
+use web_time::Instant;
+use zeroize::Zeroize;
 
 use crate::address_utils;
+use crate::session::{SessionAuthorization, SessionError, SessionKey, SessionScope};
+use crate::wallet_client::{WalletClient, WalletClientError};
+
+pub mod hd;
 
 #[derive(Error, Debug)]
 pub enum WalletError {
@@ -19,6 +56,102 @@ pub enum WalletError {
     Format(String),
     #[error("Hex decode error: {0}")]
     Hex(#[from] hex::FromHexError),
+    #[error("Wrong password or corrupted keystore")]
+    WrongPassword,
+    #[error("Invalid derivation path: {0}")]
+    DerivationPath(String),
+    #[error("Signing rate limit of {limit} per {window:?} reached")]
+    RateLimited { limit: u32, window: Duration },
+    #[error("Rate limit window must be longer than zero")]
+    ZeroRateWindow,
+}
+
+/// Keystore format version written by [`WalletKeypair::save_encrypted`].
+const KEYSTORE_VERSION: u32 = 1;
+
+/// scrypt cost for new keystores: N = 2^15, r = 8, p = 1.
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// Largest scrypt cost a keystore may ask for: N up to 2^20, r up to 32,
+/// p up to 16 and at most 1 GiB of memory (`128 * r * N` bytes). A keystore
+/// from an untrusted source could otherwise pin the CPU or exhaust memory
+/// before the password is even checked.
+const MAX_SCRYPT_LOG_N: u8 = 20;
+const MAX_SCRYPT_R: u32 = 32;
+const MAX_SCRYPT_P: u32 = 16;
+const MAX_SCRYPT_MEMORY: u64 = 1 << 30;
+
+/// Self-describing encrypted keystore (JSON on disk).
+///
+/// The secret key is encrypted with XChaCha20-Poly1305 under a key derived
+/// from the password with scrypt. Everything needed to decrypt except the
+/// password is stored alongside the ciphertext.
+#[derive(Debug, Serialize, Deserialize)]
+struct Keystore {
+    version: u32,
+    /// Vx0 address of the key, for identification without decrypting.
+    address: String,
+    kdf: String,
+    kdf_params: ScryptParams,
+    cipher: String,
+    /// 24-byte XChaCha20 nonce, hex
+    nonce: String,
+    /// Encrypted secret key plus 16-byte Poly1305 tag, hex
+    ciphertext: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ScryptParams {
+    log_n: u8,
+    r: u32,
+    p: u32,
+    /// 32-byte salt, hex
+    salt: String,
+}
+
+impl ScryptParams {
+    fn derive_key(&self, password: &str) -> Result<[u8; 32], WalletError> {
+        if self.log_n > MAX_SCRYPT_LOG_N
+            || self.r > MAX_SCRYPT_R
+            || self.p > MAX_SCRYPT_P
+            || (128 * self.r as u64) << self.log_n > MAX_SCRYPT_MEMORY
+        {
+            return Err(WalletError::Format(format!(
+                "scrypt params log_n={} r={} p={} exceed the supported cost",
+                self.log_n, self.r, self.p
+            )));
+        }
+        let salt = hex::decode(&self.salt)?;
+        let params = scrypt::Params::new(self.log_n, self.r, self.p, 32)
+            .map_err(|e| WalletError::Format(format!("Invalid scrypt params: {}", e)))?;
+        let mut key = [0u8; 32];
+        scrypt::scrypt(password.as_bytes(), &salt, &params, &mut key)
+            .map_err(|e| WalletError::Format(format!("scrypt failed: {}", e)))?;
+        Ok(key)
+    }
+}
+
+/// Write `contents` to `path`, readable only by the owner on Unix. A new file
+/// is created with mode 600 rather than chmodded after the secret is already
+/// on disk; an existing one is narrowed to 600 before it's overwritten.
+#[cfg(not(target_arch = "wasm32"))]
+fn write_private(path: &Path, contents: &[u8]) -> Result<(), WalletError> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents)?;
+    Ok(())
 }
 
 /// Ed25519 wallet keypair for signing transactions.
@@ -30,14 +163,18 @@ pub struct WalletKeypair {
 }
 
 impl WalletKeypair {
-    /// Generate a new random wallet keypair.
+    /// Generate a new random wallet keypair from the OS CSPRNG.
     pub fn generate() -> Self {
-        let rng = rand_bytes();
         Self {
-            signing_key: SigningKey::from_bytes(&rng),
+            signing_key: SigningKey::generate(&mut OsRng),
         }
     }
 
+    /// Generate `count` independent random wallets (e.g. for load-testing accounts).
+    pub fn generate_batch(count: usize) -> Vec<WalletKeypair> {
+        (0..count).map(|_| Self::generate()).collect()
+    }
+
     /// Create from a hex-encoded secret key string.
     pub fn from_secret_hex(hex_str: &str) -> Result<Self, WalletError> {
         let bytes = hex::decode(hex_str.trim())?;
@@ -62,21 +199,115 @@ impl WalletKeypair {
     }
 
     /// Load from a hex-encoded secret key file.
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, WalletError> {
         let contents = std::fs::read_to_string(path)?;
         Self::from_secret_hex(&contents)
     }
 
-    /// Save secret key as hex to a file (chmod 600 on Unix).
+    /// Save secret key as hex to a file (mode 600 on Unix).
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), WalletError> {
-        let hex_str = hex::encode(self.signing_key.to_bytes());
-        std::fs::write(&path, &hex_str)?;
-        #[cfg(unix)]
+        let mut hex_str = hex::encode(self.signing_key.to_bytes());
+        let written = write_private(path.as_ref(), hex_str.as_bytes());
+        hex_str.zeroize();
+        written
+    }
+
+    /// Save the secret key as a password-encrypted JSON keystore (mode 600 on Unix).
+    ///
+    /// The file holds [`to_keystore_json`](Self::to_keystore_json). Read it back
+    /// with [`load_encrypted`](Self::load_encrypted).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_encrypted<P: AsRef<Path>>(&self, path: P, password: &str) -> Result<(), WalletError> {
+        write_private(path.as_ref(), self.to_keystore_json(password)?.as_bytes())
+    }
+
+    /// Load a keystore written by [`save_encrypted`](Self::save_encrypted).
+    ///
+    /// Returns [`WalletError::WrongPassword`] if the password doesn't decrypt it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_encrypted<P: AsRef<Path>>(path: P, password: &str) -> Result<Self, WalletError> {
+        Self::from_keystore_json(&std::fs::read_to_string(path)?, password)
+    }
+
+    /// The secret key as a password-encrypted JSON keystore.
+    ///
+    /// Uses scrypt (N = 2^15, r = 8, p = 1) and XChaCha20-Poly1305 with a fresh
+    /// random salt and nonce. Read it back with
+    /// [`from_keystore_json`](Self::from_keystore_json).
+    pub fn to_keystore_json(&self, password: &str) -> Result<String, WalletError> {
+        let mut salt = [0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+        let mut nonce = [0u8; 24];
+        OsRng.fill_bytes(&mut nonce);
+
+        let kdf_params = ScryptParams {
+            log_n: SCRYPT_LOG_N,
+            r: SCRYPT_R,
+            p: SCRYPT_P,
+            salt: hex::encode(salt),
+        };
+        let mut key = kdf_params.derive_key(password)?;
+        let cipher = XChaCha20Poly1305::new(&key.into());
+        key.zeroize();
+
+        let mut secret = self.signing_key.to_bytes();
+        let ciphertext = cipher.encrypt(XNonce::from_slice(&nonce), secret.as_slice());
+        secret.zeroize();
+        let ciphertext =
+            ciphertext.map_err(|_| WalletError::Format("Encryption failed".into()))?;
+
+        let keystore = Keystore {
+            version: KEYSTORE_VERSION,
+            address: self.vx0_address(),
+            kdf: "scrypt".into(),
+            kdf_params,
+            cipher: "xchacha20-poly1305".into(),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        };
+        serde_json::to_string_pretty(&keystore).map_err(|e| WalletError::Format(e.to_string()))
+    }
+
+    /// Decrypt a keystore from [`to_keystore_json`](Self::to_keystore_json).
+    ///
+    /// Returns [`WalletError::WrongPassword`] if the password doesn't decrypt it.
+    pub fn from_keystore_json(json: &str, password: &str) -> Result<Self, WalletError> {
+        let keystore: Keystore = serde_json::from_str(json)
+            .map_err(|e| WalletError::Format(format!("Invalid keystore: {}", e)))?;
+        if keystore.version != KEYSTORE_VERSION
+            || keystore.kdf != "scrypt"
+            || keystore.cipher != "xchacha20-poly1305"
         {
-            use std::os::unix::fs::PermissionsExt;
-            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+            return Err(WalletError::Format(format!(
+                "Unsupported keystore: v{} {}/{}",
+                keystore.version, keystore.kdf, keystore.cipher
+            )));
         }
-        Ok(())
+        let nonce = hex::decode(&keystore.nonce)?;
+        if nonce.len() != 24 {
+            return Err(WalletError::Format("Invalid keystore nonce".into()));
+        }
+        let ciphertext = hex::decode(&keystore.ciphertext)?;
+
+        let mut key = keystore.kdf_params.derive_key(password)?;
+        let cipher = XChaCha20Poly1305::new(&key.into());
+        key.zeroize();
+
+        let mut plaintext = cipher
+            .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| WalletError::WrongPassword)?;
+        if plaintext.len() != 32 {
+            plaintext.zeroize();
+            return Err(WalletError::Format("Invalid keystore secret length".into()));
+        }
+        let mut secret = [0u8; 32];
+        secret.copy_from_slice(&plaintext);
+        plaintext.zeroize();
+        let wallet = Self::from_secret_bytes(&secret);
+        secret.zeroize();
+        Ok(wallet)
     }
 
     /// Get the native Vx0 address (user-facing format).
@@ -113,6 +344,11 @@ impl WalletKeypair {
         sig.to_bytes().to_vec()
     }
 
+    /// Verify a signature made by this wallet (see [`verify_with_pubkey`]).
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        verify_with_pubkey(&self.public_key_bytes(), message, signature)
+    }
+
     /// Sign a TransactionBundle, returning the Signature to set on it.
     ///
     /// Computes the Blake3 bundle hash and signs it with Ed25519.
@@ -121,33 +357,227 @@ impl WalletKeypair {
         let sig_bytes = self.sign(hash.as_bytes());
         Signature(sig_bytes)
     }
+
+    /// Sign a bundle produced by [`BundleBuilder::build_unsigned_encoded`](crate::BundleBuilder::build_unsigned_encoded),
+    /// returning only the signature as `0x` hex — the air-gapped half of
+    /// [offline signing](crate::bundle#offline-signing). Needs no network access.
+    pub fn sign_encoded_bundle(&self, encoded: &str) -> Result<String> {
+        let bundle = crate::bundle::decode_bundle(encoded)?;
+        Ok(format!("0x{}", hex::encode(self.sign_bundle(&bundle).0)))
+    }
+
+    /// Create a short-lived session key limited to `scope` until `valid_until`
+    /// on chain `chain_id` (see [`WalletClient::chain_id`]).
+    ///
+    /// Generates a fresh subordinate keypair and signs a
+    /// [`SessionAuthorization`] for it with this (root) key. See
+    /// [`session`](crate::session) for verification. Fails if the scope can't
+    /// be encoded (see [`SessionAuthorization::signing_hash`]).
+    pub fn create_session_key(
+        &self,
+        scope: SessionScope,
+        valid_until: Timestamp,
+        chain_id: u64,
+    ) -> Result<SessionKey, SessionError> {
+        let session = WalletKeypair::generate();
+        let mut authorization = SessionAuthorization {
+            root_pubkey: self.public_key(),
+            session_pubkey: session.public_key(),
+            scope,
+            valid_until,
+            chain_id,
+            signature: Vec::new(),
+        };
+        authorization.signature = self.sign(&authorization.signing_hash()?);
+        Ok(SessionKey::new(session, authorization))
+    }
 }
 
-/// Generate 32 random bytes for key generation.
-fn rand_bytes() -> [u8; 32] {
-    use sha2::{Digest, Sha256};
-    let mut hasher = Sha256::new();
-    hasher.update(
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos()
-            .to_le_bytes(),
-    );
-    hasher.update(std::process::id().to_le_bytes());
-    #[cfg(unix)]
-    {
-        use std::io::Read;
-        if let Ok(mut f) = std::fs::File::open("/dev/urandom") {
-            let mut buf = [0u8; 32];
-            let _ = f.read_exact(&mut buf);
-            hasher.update(&buf);
+/// Verify an Ed25519 signature against a raw 32-byte public key.
+///
+/// Returns `false` (never panics) for keys that aren't valid curve points and
+/// for signatures that aren't exactly 64 bytes.
+pub fn verify_with_pubkey(pubkey: &[u8; 32], message: &[u8], signature: &[u8]) -> bool {
+    let Ok(key) = VerifyingKey::from_bytes(pubkey) else {
+        return false;
+    };
+    let Ok(sig) = ed25519_dalek::Signature::from_slice(signature) else {
+        return false;
+    };
+    key.verify(message, &sig).is_ok()
+}
+
+/// Read-only view of an account you don't hold the key for.
+///
+/// Exposes the same address formats as [`WalletKeypair`] plus balance and
+/// history lookups through a [`WalletClient`], but can't sign anything.
+#[derive(Debug, Clone)]
+pub struct WatchOnlyAccount {
+    address: Address,
+    vx0: Option<String>,
+}
+
+impl WatchOnlyAccount {
+    /// Watch the account owned by a hex-encoded 32-byte Ed25519 public key.
+    pub fn from_pubkey_hex(hex_str: &str) -> Result<Self, WalletError> {
+        let hex_str = hex_str.trim();
+        let bytes = hex::decode(hex_str.strip_prefix("0x").unwrap_or(hex_str))?;
+        if bytes.len() != 32 {
+            return Err(WalletError::Format(format!(
+                "Expected 32 bytes, got {}",
+                bytes.len()
+            )));
+        }
+        let vx0 = address_utils::vx0_from_pubkey(&bytes);
+        let address = address_utils::parse_address(&vx0)
+            .map_err(|e| WalletError::Format(e.to_string()))?;
+        Ok(Self { address, vx0: Some(vx0) })
+    }
+
+    /// Watch an address given in any supported format (Vx0, Vx1, or 0x).
+    ///
+    /// The Vx0 form is only known when `addr` is itself a Vx0/Vx1 address.
+    pub fn from_address(addr: &str) -> Result<Self, WalletError> {
+        let addr = addr.trim();
+        let address = address_utils::parse_address(addr)
+            .map_err(|e| WalletError::Format(e.to_string()))?;
+        let vx0 = address_utils::is_valid_vx0(addr).then(|| addr.to_string());
+        Ok(Self { address, vx0 })
+    }
+
+    /// The 32-byte internal address.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// The native Vx0 address, if known.
+    pub fn vx0_address(&self) -> Option<&str> {
+        self.vx0.as_deref()
+    }
+
+    /// The full 32-byte address as 0x hex (internal state format).
+    pub fn hex_address(&self) -> String {
+        format!("0x{}", hex::encode(self.address.0))
+    }
+
+    /// The 20-byte EVM-compatible address (last 20 bytes).
+    pub fn evm_address(&self) -> String {
+        format!("0x{}", hex::encode(&self.address.0[12..]))
+    }
+
+    /// Token balance of the watched account.
+    pub async fn balance(&self, client: &WalletClient, token: &str) -> Result<String, WalletClientError> {
+        client.get_balance(&self.hex_address(), token).await
+    }
+
+    /// Recent transactions of the watched account.
+    pub async fn history(&self, client: &WalletClient, limit: u32) -> Result<Value, WalletClientError> {
+        client.get_transaction_history(&self.hex_address(), limit).await
+    }
+}
+
+/// A [`WalletKeypair`] that refuses to sign faster than a configured rate.
+///
+/// A guardrail for server-side hot wallets: a token bucket holding `limit`
+/// signatures refills continuously over `window`, so bursts of up to `limit`
+/// are allowed but the sustained rate can't exceed `limit` per `window`.
+/// Refused signs aren't counted in the statistics.
+pub struct RateLimitedSigner {
+    keypair: WalletKeypair,
+    limit: u32,
+    window: Duration,
+    state: Mutex<SignerState>,
+}
+
+struct SignerState {
+    tokens: f64,
+    refilled_at: Instant,
+    total: u64,
+    /// Times of signatures made in the last minute.
+    recent: VecDeque<Instant>,
+}
+
+impl RateLimitedSigner {
+    /// Allow at most `limit` signatures per `window`. A zero `window` is
+    /// rejected: the bucket would never refill.
+    pub fn new(keypair: WalletKeypair, limit: u32, window: Duration) -> Result<Self, WalletError> {
+        if window.is_zero() {
+            return Err(WalletError::ZeroRateWindow);
+        }
+        Ok(Self::with_window(keypair, limit, window))
+    }
+
+    /// Allow at most `limit` signatures per minute.
+    pub fn per_minute(keypair: WalletKeypair, limit: u32) -> Self {
+        Self::with_window(keypair, limit, Duration::from_secs(60))
+    }
+
+    fn with_window(keypair: WalletKeypair, limit: u32, window: Duration) -> Self {
+        Self {
+            keypair,
+            limit,
+            window,
+            state: Mutex::new(SignerState {
+                tokens: limit as f64,
+                refilled_at: Instant::now(),
+                total: 0,
+                recent: VecDeque::new(),
+            }),
         }
     }
-    let result = hasher.finalize();
-    let mut out = [0u8; 32];
-    out.copy_from_slice(&result);
-    out
+
+    /// The wrapped keypair, e.g. for its address. Signing through it directly
+    /// bypasses the limit.
+    pub fn keypair(&self) -> &WalletKeypair {
+        &self.keypair
+    }
+
+    /// Sign a message, or fail with [`WalletError::RateLimited`].
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>, WalletError> {
+        self.acquire()?;
+        Ok(self.keypair.sign(message))
+    }
+
+    /// Sign a bundle, or fail with [`WalletError::RateLimited`].
+    pub fn sign_bundle(&self, bundle: &TransactionBundle) -> Result<Signature, WalletError> {
+        self.acquire()?;
+        Ok(self.keypair.sign_bundle(bundle))
+    }
+
+    /// Signatures made since creation.
+    pub fn signs_total(&self) -> u64 {
+        self.state.lock().unwrap().total
+    }
+
+    /// Signatures made in the last 60 seconds.
+    pub fn signs_last_minute(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        prune_recent(&mut state.recent, Instant::now());
+        state.recent.len()
+    }
+
+    fn acquire(&self) -> Result<(), WalletError> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.refilled_at).as_secs_f64();
+        let refill = elapsed * self.limit as f64 / self.window.as_secs_f64();
+        state.tokens = (state.tokens + refill).min(self.limit as f64);
+        state.refilled_at = now;
+        if state.tokens < 1.0 {
+            return Err(WalletError::RateLimited { limit: self.limit, window: self.window });
+        }
+        state.tokens -= 1.0;
+        state.total += 1;
+        prune_recent(&mut state.recent, now);
+        state.recent.push_back(now);
+        Ok(())
+    }
+}
+
+fn prune_recent(recent: &mut VecDeque<Instant>, now: Instant) {
+    while recent.front().is_some_and(|t| now.duration_since(*t) >= Duration::from_secs(60)) {
+        recent.pop_front();
+    }
 }
 
 #[cfg(test)]
@@ -171,6 +601,22 @@ mod tests {
         assert_eq!(evm_addr.len(), 42);
     }
 
+    #[test]
+    fn test_generate_batch_distinct() {
+        let wallets = WalletKeypair::generate_batch(100);
+        assert_eq!(wallets.len(), 100);
+        let addresses: std::collections::HashSet<String> =
+            wallets.iter().map(|w| w.vx0_address()).collect();
+        assert_eq!(addresses.len(), 100);
+    }
+
+    #[test]
+    fn test_generate_no_collisions() {
+        let keys: std::collections::HashSet<[u8; 32]> =
+            (0..10_000).map(|_| WalletKeypair::generate().public_key_bytes()).collect();
+        assert_eq!(keys.len(), 10_000);
+    }
+
     #[test]
     fn test_sign_and_verify() {
         let wallet = WalletKeypair::generate();
@@ -183,6 +629,24 @@ mod tests {
         assert!(wallet.signing_key.verifying_key().verify(msg, &ed_sig).is_ok());
     }
 
+    #[test]
+    fn test_verify_helpers() {
+        let wallet = WalletKeypair::generate();
+        let msg = b"hello vexidus";
+        let sig = wallet.sign(msg);
+        assert!(wallet.verify(msg, &sig));
+        assert!(!wallet.verify(b"other message", &sig));
+        assert!(verify_with_pubkey(&wallet.public_key_bytes(), msg, &sig));
+
+        // Malformed inputs return false rather than panicking
+        assert!(!verify_with_pubkey(&wallet.public_key_bytes(), msg, &sig[..63]));
+        assert!(!verify_with_pubkey(&wallet.public_key_bytes(), msg, &[]));
+        let mut not_a_point = [0u8; 32];
+        not_a_point[0] = 2;
+        not_a_point[31] = 0x80;
+        assert!(!verify_with_pubkey(&not_a_point, msg, &sig));
+    }
+
     #[test]
     fn test_sign_bundle() {
         let wallet = WalletKeypair::generate();
@@ -222,6 +686,72 @@ mod tests {
         let w2 = WalletKeypair::load(&path).unwrap();
         assert_eq!(w1.public_key_bytes(), w2.public_key_bytes());
         assert_eq!(w1.vx0_address(), w2.vx0_address());
+
+        // Owner-only, whether the file is new or was world-readable before
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: &std::path::Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&path), 0o600);
+            let loose = dir.path().join("loose.key");
+            std::fs::write(&loose, "").unwrap();
+            std::fs::set_permissions(&loose, std::fs::Permissions::from_mode(0o644)).unwrap();
+            w1.save_encrypted(&loose, "pw").unwrap();
+            assert_eq!(mode(&loose), 0o600);
+        }
+    }
+
+    #[test]
+    fn test_watch_only_matches_keypair() {
+        let wallet = WalletKeypair::generate();
+        let watch = WatchOnlyAccount::from_pubkey_hex(&hex::encode(wallet.public_key_bytes()))
+            .unwrap();
+        assert_eq!(watch.vx0_address(), Some(wallet.vx0_address().as_str()));
+        assert_eq!(watch.hex_address(), wallet.hex_address());
+        assert_eq!(watch.evm_address(), wallet.evm_address());
+
+        let by_hex = WatchOnlyAccount::from_address(&wallet.hex_address()).unwrap();
+        assert_eq!(by_hex.address(), watch.address());
+        assert_eq!(by_hex.vx0_address(), None);
+
+        assert!(WatchOnlyAccount::from_pubkey_hex("abcd").is_err());
+    }
+
+    #[test]
+    fn test_encrypted_keystore() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wallet.json");
+
+        let w1 = WalletKeypair::generate();
+        w1.save_encrypted(&path, "correct horse").unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains(&hex::encode(w1.signing_key.to_bytes())));
+
+        let w2 = WalletKeypair::load_encrypted(&path, "correct horse").unwrap();
+        assert_eq!(w1.public_key_bytes(), w2.public_key_bytes());
+
+        assert!(matches!(
+            WalletKeypair::load_encrypted(&path, "battery staple"),
+            Err(WalletError::WrongPassword)
+        ));
+
+        // In-memory form, e.g. for browser storage
+        let json = w1.to_keystore_json("pw").unwrap();
+        assert_eq!(WalletKeypair::from_keystore_json(&json, "pw").unwrap().vx0_address(), w1.vx0_address());
+        assert!(matches!(WalletKeypair::from_keystore_json(&json, "nope"), Err(WalletError::WrongPassword)));
+
+        // A hostile cost is refused before scrypt runs
+        for (field, value) in [("log_n", 40), ("r", 1_000_000), ("p", 1_000), ("r", 32)] {
+            let mut keystore: serde_json::Value = serde_json::from_str(&json).unwrap();
+            keystore["kdf_params"][field] = serde_json::json!(value);
+            if field == "r" && value == 32 {
+                // Each bound is fine alone; together they're 4 GiB
+                keystore["kdf_params"]["log_n"] = serde_json::json!(20);
+            }
+            let err = WalletKeypair::from_keystore_json(&keystore.to_string(), "pw").err().unwrap();
+            assert!(matches!(&err, WalletError::Format(msg) if msg.contains("exceed")), "{}", err);
+        }
     }
 
     #[test]
@@ -231,4 +761,30 @@ mod tests {
         let loaded = WalletKeypair::from_secret_hex(&hex_str).unwrap();
         assert_eq!(wallet.public_key_bytes(), loaded.public_key_bytes());
     }
+
+    #[test]
+    fn test_rate_limited_signer() {
+        let window = Duration::from_millis(300);
+        let signer = RateLimitedSigner::new(WalletKeypair::generate(), 3, window).unwrap();
+        for _ in 0..3 {
+            let sig = signer.sign(b"msg").unwrap();
+            assert!(signer.keypair().verify(b"msg", &sig));
+        }
+        assert!(matches!(
+            signer.sign(b"msg"),
+            Err(WalletError::RateLimited { limit: 3, .. })
+        ));
+        assert_eq!(signer.signs_total(), 3);
+
+        std::thread::sleep(window + Duration::from_millis(50));
+        let bundle = crate::bundle::BundleBuilder::new(&signer.keypair().hex_address()).unwrap().build();
+        assert!(signer.sign_bundle(&bundle).is_ok());
+        assert_eq!(signer.signs_total(), 4);
+        assert_eq!(signer.signs_last_minute(), 4);
+
+        assert!(matches!(
+            RateLimitedSigner::new(WalletKeypair::generate(), 3, Duration::ZERO),
+            Err(WalletError::ZeroRateWindow)
+        ));
+    }
 }
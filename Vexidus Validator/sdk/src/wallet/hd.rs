@@ -0,0 +1,151 @@
+//! Hierarchical deterministic wallets — SLIP-0010 Ed25519 key derivation.
+//!
+//! Ed25519 only supports hardened derivation, so every path segment must carry
+//! the `'` (or `h`) marker:
+//!
+//! ```ignore
+//! // Scan the first 10 accounts of a seed
+//! for account in 0..10 {
+//!     let wallet = WalletKeypair::derive_path(&seed, &account_path(account))?;
+//!     println!("{}", wallet.vx0_address());
+//! }
+//! ```
+
+use ed25519_dalek::SigningKey;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use zeroize::Zeroize;
+
+use super::{WalletError, WalletKeypair};
+
+/// Coin type used for Vexidus derivation paths (`m/44'/9999'/...`).
+///
+/// A provisional choice of this project, not a type registered in SLIP-0044;
+/// other wallets won't derive the same accounts from a seed unless they use
+/// it too, and it may change if Vexidus registers one.
+pub const VEXIDUS_COIN_TYPE: u32 = 9999;
+
+/// Offset marking a hardened child index.
+const HARDENED: u32 = 0x8000_0000;
+
+/// Default path for account `account`: `m/44'/9999'/<account>'/0'/0'`.
+pub fn account_path(account: u32) -> String {
+    format!("m/44'/{}'/{}'/0'/0'", VEXIDUS_COIN_TYPE, account)
+}
+
+impl WalletKeypair {
+    /// Derive a wallet from a BIP-39 seed along a SLIP-0010 path such as
+    /// `m/44'/9999'/0'/0'/0'`.
+    ///
+    /// The seed must be 16–64 bytes. Non-hardened segments are rejected.
+    pub fn derive_path(seed: &[u8], path: &str) -> Result<Self, WalletError> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(WalletError::Format(format!(
+                "Seed must be 16-64 bytes, got {}",
+                seed.len()
+            )));
+        }
+        let indices = parse_path(path)?;
+
+        let mut key = hmac_sha512(b"ed25519 seed", &[seed]);
+        for index in indices {
+            let (secret, chain_code) = key.split_at(32);
+            let child = hmac_sha512(chain_code, &[&[0u8], secret, &index.to_be_bytes()]);
+            key.zeroize();
+            key = child;
+        }
+
+        let mut secret = [0u8; 32];
+        secret.copy_from_slice(&key[..32]);
+        key.zeroize();
+        let wallet = Self {
+            signing_key: SigningKey::from_bytes(&secret),
+        };
+        secret.zeroize();
+        Ok(wallet)
+    }
+}
+
+/// Parse `m/44'/9999'/0'` into hardened child indices.
+fn parse_path(path: &str) -> Result<Vec<u32>, WalletError> {
+    let mut segments = path.trim().split('/');
+    if segments.next() != Some("m") {
+        return Err(WalletError::DerivationPath(format!(
+            "{}: must start with \"m\"",
+            path
+        )));
+    }
+    segments
+        .map(|segment| {
+            let index = segment
+                .strip_suffix('\'')
+                .or_else(|| segment.strip_suffix('h'))
+                .ok_or_else(|| {
+                    WalletError::DerivationPath(format!(
+                        "{}: segment \"{}\" is not hardened (Ed25519 requires a ' marker)",
+                        path, segment
+                    ))
+                })?;
+            let index: u32 = index.parse().map_err(|_| {
+                WalletError::DerivationPath(format!("{}: invalid segment \"{}\"", path, segment))
+            })?;
+            if index >= HARDENED {
+                return Err(WalletError::DerivationPath(format!(
+                    "{}: index {} out of range",
+                    path, index
+                )));
+            }
+            Ok(index | HARDENED)
+        })
+        .collect()
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts any key length");
+    for chunk in data {
+        mac.update(chunk);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SLIP-0010 test vector 1 for ed25519.
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+    fn assert_secret(path: &str, expected: &str) {
+        let seed = hex::decode(SEED).unwrap();
+        let wallet = WalletKeypair::derive_path(&seed, path).unwrap();
+        assert_eq!(hex::encode(wallet.signing_key.to_bytes()), expected, "{}", path);
+    }
+
+    #[test]
+    fn test_slip10_vector_1() {
+        assert_secret("m", "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7");
+        assert_secret("m/0'", "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3");
+        assert_secret("m/0'/1'", "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2");
+        assert_secret("m/0'/1'/2'", "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9");
+        assert_secret("m/0'/1'/2'/2'", "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662");
+        assert_secret(
+            "m/0h/1h/2h/2h/1000000000h",
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_hardened() {
+        let seed = hex::decode(SEED).unwrap();
+        assert!(matches!(
+            WalletKeypair::derive_path(&seed, "m/44'/9999'/0'/0/0"),
+            Err(WalletError::DerivationPath(_))
+        ));
+        assert!(WalletKeypair::derive_path(&seed, "44'/0'").is_err());
+        assert!(WalletKeypair::derive_path(&[0u8; 8], "m/0'").is_err());
+
+        let a = WalletKeypair::derive_path(&seed, &account_path(0)).unwrap();
+        let b = WalletKeypair::derive_path(&seed, &account_path(1)).unwrap();
+        assert_ne!(a.public_key_bytes(), b.public_key_bytes());
+    }
+}
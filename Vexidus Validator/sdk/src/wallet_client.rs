@@ -9,51 +9,707 @@
 //! let tx = client.transfer(&wallet, "Vx0def...", "VXS", 5_000_000_000).await?;
 //! ```
 
-use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use vexidus_types::TransactionBundle;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use thiserror::Error;
+use vexidus_types::{Address, KeyRole, Operation, PublicKey, Timestamp, TransactionBundle};
 
-use crate::bundle::BundleBuilder;
+use crate::address_utils::{self, AddressError};
+use crate::bundle::{self, BundleBuilder, BundleError, FeeSchedule};
+use crate::transport::{collect_pages, split_page, value_to_u128, MockTransport, RetryPolicy, Transport};
+use crate::vns;
 use crate::wallet::WalletKeypair;
 
+/// Base gas price (nanoVXS per gas) assumed when the node doesn't report one.
+pub const DEFAULT_GAS_PRICE: u64 = 10;
+
+/// Priority fee (nanoVXS per gas) assumed when the node doesn't suggest one.
+pub const DEFAULT_PRIORITY_FEE: u64 = 1;
+
+/// First and longest gaps between receipt polls in
+/// [`WalletClient::wait_for_confirmation`].
+const CONFIRMATION_POLL_START: Duration = Duration::from_millis(200);
+const CONFIRMATION_POLL_MAX: Duration = Duration::from_secs(5);
+
+/// Number of recent blocks [`WalletClient::congestion_level`] averages over.
+const CONGESTION_BLOCKS: u64 = 20;
+
+/// Unstaked VXS is locked for 21 days before `ClaimUnstake` can withdraw it.
+pub const UNBONDING_PERIOD_SECS: u64 = 21 * 24 * 60 * 60;
+
+/// Slack a [`ScheduledClaim`] adds to the unbonding period. Unbonding starts
+/// at the block that includes the Unstake, which is after it's submitted.
+pub const CLAIM_MARGIN_SECS: u64 = 10 * 60;
+
+/// Why a [`WalletClient`] call failed.
+///
+/// Only [`Connect`](Self::Connect), [`Http`](Self::Http) request failures and
+/// [`Timeout`](Self::Timeout) are transient (see [`is_transient`](Self::is_transient)); an
+/// [`Rpc`](Self::Rpc) error means the node received and rejected the request,
+/// so retrying it unchanged will fail the same way.
+#[derive(Error, Debug)]
+pub enum WalletClientError {
+    #[error("HTTP error: {0}")]
+    Http(reqwest::Error),
+    /// The node couldn't be reached (refused connection, DNS failure), so the
+    /// request was never sent.
+    #[error("Could not connect to node: {0}")]
+    Connect(String),
+    #[error("RPC error {code}: {message}")]
+    Rpc { code: i64, message: String },
+    #[error("Decode error: {0}")]
+    Decode(String),
+    #[error("Request timed out")]
+    Timeout,
+    #[error("Address error: {0}")]
+    Address(#[from] AddressError),
+    #[error("Bundle error: {0}")]
+    Bundle(#[from] BundleError),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Transaction {0} not confirmed before the deadline")]
+    ConfirmationTimeout(String),
+    #[error("Transaction {tx_hash} reverted: {reason}")]
+    Reverted { tx_hash: String, reason: String, gas_used: u64 },
+    #[error("Bundle would revert: {reason}")]
+    WouldRevert { reason: String },
+}
+
+impl WalletClientError {
+    /// Whether the node rejected a bundle over its nonce (too low, too high,
+    /// already used). Matched on the RPC error message, which names the nonce.
+    pub fn is_nonce_error(&self) -> bool {
+        match self {
+            WalletClientError::Rpc { message, .. } => message.to_ascii_lowercase().contains("nonce"),
+            _ => false,
+        }
+    }
+
+    /// Whether the node reported that what was asked for doesn't exist: a
+    /// [`NotFound`](Self::NotFound), or an RPC error whose message says "not
+    /// found".
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            WalletClientError::NotFound(_) => true,
+            WalletClientError::Rpc { message, .. } => message.to_ascii_lowercase().contains("not found"),
+            _ => false,
+        }
+    }
+
+    /// Whether the request may succeed if retried as-is.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            WalletClientError::Timeout | WalletClientError::Connect(_) => true,
+            WalletClientError::Http(e) => e.is_request(),
+            _ => false,
+        }
+    }
+
+    /// Build an [`Rpc`](Self::Rpc) error from a JSON-RPC `error` object.
+    pub(crate) fn from_rpc_error(error: &Value) -> Self {
+        WalletClientError::Rpc {
+            code: error["code"].as_i64().unwrap_or(0),
+            message: error["message"]
+                .as_str()
+                .map(String::from)
+                .unwrap_or_else(|| error.to_string()),
+        }
+    }
+}
+
+impl From<reqwest::Error> for WalletClientError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            WalletClientError::Timeout
+        } else if e.is_decode() {
+            WalletClientError::Decode(e.to_string())
+        } else if is_connect(&e) {
+            WalletClientError::Connect(e.to_string())
+        } else {
+            WalletClientError::Http(e)
+        }
+    }
+}
+
+// reqwest can't tell connect errors apart under browser `fetch`
+#[cfg(not(target_arch = "wasm32"))]
+fn is_connect(e: &reqwest::Error) -> bool {
+    e.is_connect()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn is_connect(_: &reqwest::Error) -> bool {
+    false
+}
+
+impl From<serde_json::Error> for WalletClientError {
+    fn from(e: serde_json::Error) -> Self {
+        WalletClientError::Decode(e.to_string())
+    }
+}
+
+/// Where a [`FeeEstimate`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeeSource {
+    /// Computed by the node (`vex_estimateFee`) — matches what will be charged.
+    Node,
+    /// Computed locally from gas constants — a rough guide only.
+    Local,
+}
+
+/// Estimated cost of a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    pub gas: u64,
+    /// nanoVXS per gas unit
+    pub gas_price: u64,
+    /// Total fee in raw VXS units (`gas * gas_price`)
+    pub total_fee: u128,
+    pub source: FeeSource,
+}
+
+/// Coarse network load, from recent block fullness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CongestionLevel {
+    /// Blocks under 50% full.
+    Low,
+    /// Blocks 50–80% full.
+    Medium,
+    /// Blocks over 80% full; the default priority fee may get stuck.
+    High,
+}
+
+/// Network congestion as returned by [`WalletClient::congestion_level`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Congestion {
+    pub level: CongestionLevel,
+    /// Factor to scale the priority fee by.
+    pub suggested_fee_multiplier: f64,
+    /// Mean gas-used ratio of the sampled blocks (0.0–1.0).
+    pub block_utilization: f64,
+}
+
+impl Congestion {
+    /// Classify a mean block gas-used ratio.
+    pub fn from_utilization(block_utilization: f64) -> Self {
+        let (level, suggested_fee_multiplier) = if block_utilization >= 0.8 {
+            (CongestionLevel::High, 3.0)
+        } else if block_utilization >= 0.5 {
+            (CongestionLevel::Medium, 1.5)
+        } else {
+            (CongestionLevel::Low, 1.0)
+        };
+        Self { level, suggested_fee_multiplier, block_utilization }
+    }
+}
+
+/// An account's nonce state, as returned by [`WalletClient::diagnose_nonce`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NonceDiagnosis {
+    /// Nonce the next executed bundle must carry.
+    pub chain_nonce: u64,
+    /// Bundles from the account waiting in the mempool.
+    pub pending_count: u32,
+    /// Whether the pending nonces skip one, so nothing after the hole can land.
+    pub gap: bool,
+    /// Hash of the lowest-nonce pending bundle, which everything else waits on.
+    pub stuck_tx: Option<String>,
+    /// Nonces to submit (e.g. no-op bundles) to fill the gap, ascending.
+    pub missing_nonces: Vec<u64>,
+    /// Priority fee (nanoVXS per gas) a replacement for `stuck_tx` should
+    /// offer, when something is stuck.
+    pub replacement_priority_fee: Option<u64>,
+}
+
+/// A bundle waiting in the mempool, as returned by [`WalletClient::get_pending`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingTx {
+    pub hash: String,
+    pub sender: String,
+    pub nonce: u64,
+    /// Unix seconds the node first saw the bundle, if it reports it.
+    pub submitted_at: Option<u64>,
+    /// Priority fee (nanoVXS per gas) the bundle offers, if reported.
+    pub max_priority_fee: Option<u64>,
+}
+
+impl PendingTx {
+    /// Parse one `vex_getPendingTransactions` entry. Accepts snake_case or
+    /// camelCase and numbers or `0x` hex; `sender` falls back to
+    /// `default_sender` when the entry omits it. `None` without a hash and nonce.
+    pub fn from_value(value: &Value, default_sender: &str) -> Option<Self> {
+        let field = |snake: &str, camel: &str| {
+            let v = &value[snake];
+            if v.is_null() { &value[camel] } else { v }
+        };
+        Some(Self {
+            hash: value["hash"].as_str()?.to_string(),
+            sender: field("sender", "from").as_str().unwrap_or(default_sender).to_string(),
+            nonce: quantity(&value["nonce"])?,
+            submitted_at: quantity(field("submitted_at", "submittedAt")),
+            max_priority_fee: quantity(field("max_priority_fee", "maxPriorityFee")),
+        })
+    }
+}
+
+/// Where a submitted transaction is, per [`WalletClient::get_transaction_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxStatus {
+    /// In the mempool, not yet in a block.
+    Pending,
+    Included { block: u64 },
+    /// Evicted or expired from the mempool without being included; safe to
+    /// rebroadcast (with the same nonce) or replace.
+    Dropped,
+    /// The node has no record of it.
+    Unknown,
+}
+
+/// A transaction, as returned by [`WalletClient::get_transaction`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionInfo {
+    pub hash: String,
+    pub sender: String,
+    pub nonce: u64,
+    /// Block it was included in; `None` while pending.
+    pub block_height: Option<u64>,
+    /// The bundle's operations, or `None` if the node's encoding of any of
+    /// them doesn't decode with this SDK's `Operation`.
+    pub operations: Option<Vec<Operation>>,
+    /// The node's full response, for fields not mapped above.
+    pub raw: Value,
+}
+
+impl TransactionInfo {
+    /// Parse a `vex_getTransactionByHash` result (or a full transaction in a
+    /// block). Operations come from a `raw` hex-encoded bundle (see
+    /// [`bundle::decode_bundle`]) if present, else from an `operations`
+    /// array. Accepts snake_case or camelCase, and numbers or `0x` hex.
+    pub fn from_value(value: &Value) -> Result<Self, WalletClientError> {
+        let field = |snake: &str, camel: &str| {
+            let v = &value[snake];
+            if v.is_null() { &value[camel] } else { v }
+        };
+        let hash = value["hash"]
+            .as_str()
+            .ok_or_else(|| WalletClientError::Decode("transaction has no hash".into()))?
+            .to_string();
+        let bundle = value["raw"].as_str().and_then(|raw| bundle::decode_bundle(raw).ok());
+        let operations = match &bundle {
+            Some(bundle) => Some(bundle.operations.clone()),
+            None => serde_json::from_value(value["operations"].clone()).ok(),
+        };
+        let sender = match field("sender", "from").as_str() {
+            Some(sender) => sender.to_string(),
+            None => bundle
+                .as_ref()
+                .map(|b| format!("0x{}", hex::encode(b.user_account.0)))
+                .unwrap_or_default(),
+        };
+        Ok(Self {
+            hash,
+            sender,
+            nonce: quantity(&value["nonce"]).or(bundle.as_ref().map(|b| b.nonce.0)).unwrap_or(0),
+            block_height: quantity(field("block_number", "blockNumber")),
+            operations,
+            raw: value.clone(),
+        })
+    }
+}
+
+/// A block, as returned by [`WalletClient::get_block`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockInfo {
+    pub number: u64,
+    pub hash: String,
+    pub parent_hash: String,
+    /// Unix seconds.
+    pub timestamp: u64,
+    /// Hashes of every transaction in the block, in order.
+    pub transaction_hashes: Vec<String>,
+    /// The transactions themselves, when the node sent full objects.
+    pub transactions: Vec<TransactionInfo>,
+}
+
+impl BlockInfo {
+    /// Parse a `vex_getBlockByNumber` result. `transactions` may hold hashes
+    /// or full transaction objects.
+    pub fn from_value(value: &Value) -> Result<Self, WalletClientError> {
+        let field = |snake: &str, camel: &str| {
+            let v = &value[snake];
+            if v.is_null() { &value[camel] } else { v }
+        };
+        let number = quantity(&value["number"])
+            .ok_or_else(|| WalletClientError::Decode("block has no number".into()))?;
+        let mut transaction_hashes = Vec::new();
+        let mut transactions = Vec::new();
+        for tx in value["transactions"].as_array().into_iter().flatten() {
+            match tx {
+                Value::String(hash) => transaction_hashes.push(hash.clone()),
+                _ => {
+                    let tx = TransactionInfo::from_value(tx)?;
+                    transaction_hashes.push(tx.hash.clone());
+                    transactions.push(tx);
+                }
+            }
+        }
+        Ok(Self {
+            number,
+            hash: value["hash"].as_str().unwrap_or_default().to_string(),
+            parent_hash: field("parent_hash", "parentHash").as_str().unwrap_or_default().to_string(),
+            timestamp: quantity(&value["timestamp"]).unwrap_or(0),
+            transaction_hashes,
+            transactions,
+        })
+    }
+}
+
+/// Which way a [`TokenTransfer`] moved relative to the queried address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransferDirection {
+    Incoming,
+    Outgoing,
+    /// Sent from the address to itself.
+    SelfTransfer,
+}
+
+/// One transfer of a single token, as returned by
+/// [`WalletClient::get_token_transfers`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenTransfer {
+    pub hash: String,
+    pub from: String,
+    pub to: String,
+    /// Raw units
+    pub amount: u128,
+    pub timestamp: u64,
+    pub direction: TransferDirection,
+}
+
+/// One receipt log entry, as returned by [`WalletClient::get_logs`].
+///
+/// For known Vexidus events (`SwapExecuted`, `LiquidityAdded`,
+/// `LiquidityRemoved`, `Transfer`) amount fields are normalized to decimal
+/// strings and address fields to `0x` hex. Unknown events, and fields that
+/// don't decode, keep the node's raw values; non-object `data` is stored under
+/// `"raw"`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedEvent {
+    pub kind: String,
+    pub fields: HashMap<String, Value>,
+}
+
+impl DecodedEvent {
+    /// Read a raw amount field.
+    pub fn amount(&self, field: &str) -> Option<u128> {
+        self.fields.get(field).and_then(value_to_u128)
+    }
+}
+
+/// An included transaction, as returned by
+/// [`WalletClient::wait_for_confirmation`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxReceipt {
+    pub tx_hash: String,
+    pub block_height: u64,
+    pub gas_used: u64,
+    pub success: bool,
+    /// Revert reason reported by the node, for failed transactions.
+    pub error: Option<String>,
+    pub logs: Vec<DecodedEvent>,
+}
+
+impl TxReceipt {
+    /// Parse a `vex_getTransactionReceipt` result.
+    ///
+    /// Accepts snake_case or camelCase field names, numbers or `0x` hex for
+    /// heights and gas, and `status` as a bool, `"success"`/`"failed"`, or
+    /// `"0x1"`/`"0x0"`.
+    pub fn from_value(tx_hash: &str, receipt: &Value) -> Result<Self, WalletClientError> {
+        let field = |snake: &str, camel: &str| {
+            let v = &receipt[snake];
+            if v.is_null() { receipt[camel].clone() } else { v.clone() }
+        };
+        let block_height = quantity(&field("block_number", "blockNumber"))
+            .ok_or_else(|| WalletClientError::Decode("receipt has no block number".into()))?;
+        let gas_used = quantity(&field("gas_used", "gasUsed")).unwrap_or(0);
+        let success = match &receipt["status"] {
+            Value::Bool(b) => *b,
+            Value::String(s) => matches!(s.to_ascii_lowercase().as_str(), "success" | "0x1" | "1"),
+            Value::Number(n) => n.as_u64() == Some(1),
+            _ => return Err(WalletClientError::Decode("receipt has no status".into())),
+        };
+        let error = field("error", "revertReason").as_str().map(String::from);
+        Ok(Self {
+            tx_hash: tx_hash.to_string(),
+            block_height,
+            gas_used,
+            success,
+            error,
+            logs: decode_logs(receipt),
+        })
+    }
+}
+
+/// Outcome of one operation in a [`SimulationResult`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationOutcome {
+    pub success: bool,
+    pub gas_used: u64,
+    /// Why the operation failed, if it did.
+    pub error: Option<String>,
+}
+
+/// Net change to one account's balance of one token.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceDelta {
+    pub address: String,
+    pub token: String,
+    /// Raw units; negative when the balance goes down.
+    pub delta: i128,
+}
+
+/// What a bundle would do, from [`WalletClient::simulate_bundle`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationResult {
+    /// Whether the whole bundle would succeed, as the node reports it.
+    pub success: bool,
+    pub gas_used: u64,
+    /// One entry per operation, in bundle order.
+    pub operations: Vec<OperationOutcome>,
+    /// Balance changes the bundle would make if it succeeded.
+    pub balance_deltas: Vec<BalanceDelta>,
+    pub revert_reason: Option<String>,
+}
+
+impl SimulationResult {
+    /// Parse a `vex_simulateBundle` result.
+    ///
+    /// Accepts snake_case or camelCase field names, numbers or `0x` hex for
+    /// gas, and deltas as decimal strings or numbers.
+    pub fn from_value(result: &Value) -> Result<Self, WalletClientError> {
+        let field = |v: &Value, snake: &str, camel: &str| {
+            let f = &v[snake];
+            if f.is_null() { v[camel].clone() } else { f.clone() }
+        };
+        let success = result["success"]
+            .as_bool()
+            .ok_or_else(|| WalletClientError::Decode("simulation has no success flag".into()))?;
+        let operations = result["operations"]
+            .as_array()
+            .map(|ops| {
+                ops.iter()
+                    .map(|op| OperationOutcome {
+                        success: op["success"].as_bool().unwrap_or(false),
+                        gas_used: quantity(&field(op, "gas_used", "gasUsed")).unwrap_or(0),
+                        error: op["error"].as_str().map(String::from),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut balance_deltas = Vec::new();
+        for change in field(result, "balance_deltas", "balanceDeltas").as_array().into_iter().flatten() {
+            let delta = match &change["delta"] {
+                Value::String(s) => s.parse().ok(),
+                Value::Number(n) => n.as_i64().map(i128::from),
+                _ => None,
+            }
+            .ok_or_else(|| WalletClientError::Decode(format!("Unreadable balance delta: {}", change)))?;
+            balance_deltas.push(BalanceDelta {
+                address: change["address"].as_str().unwrap_or_default().to_string(),
+                token: change["token"].as_str().unwrap_or("VXS").to_string(),
+                delta,
+            });
+        }
+        Ok(Self {
+            success,
+            gas_used: quantity(&field(result, "gas_used", "gasUsed")).unwrap_or(0),
+            operations,
+            balance_deltas,
+            revert_reason: field(result, "revert_reason", "revertReason").as_str().map(String::from),
+        })
+    }
+
+    /// Net change to `address`'s balance of `token` (0 if untouched).
+    pub fn delta_for(&self, address: &str, token: &str) -> i128 {
+        self.balance_deltas
+            .iter()
+            .filter(|d| d.address.eq_ignore_ascii_case(address) && d.token.eq_ignore_ascii_case(token))
+            .map(|d| d.delta)
+            .sum()
+    }
+}
+
+/// Read a number that may be JSON or a `0x` hex string.
+pub(crate) fn quantity(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => match s.strip_prefix("0x") {
+            Some(hex_str) => u64::from_str_radix(hex_str, 16).ok(),
+            None => s.parse().ok(),
+        },
+        _ => None,
+    }
+}
+
+/// A `ClaimUnstake` waiting for unbonding to finish.
+///
+/// Returned by [`WalletClient::unstake_and_schedule_claim`]. The claim only
+/// happens while something awaits [`wait_and_claim`](Self::wait_and_claim) —
+/// if the process exits first, persist this (it's serde) and resume later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledClaim {
+    /// Hash of the Unstake transaction.
+    pub unstake_tx: String,
+    /// When to claim: the unbonding period plus [`CLAIM_MARGIN_SECS`] after
+    /// the Unstake was submitted. If the Unstake took longer than the margin
+    /// to land, the node refuses the claim; retry it later.
+    pub completes_at: Timestamp,
+}
+
+impl ScheduledClaim {
+    /// Schedule a claim for an unstake submitted at `unstaked_at`.
+    pub fn new(unstake_tx: String, unstaked_at: Timestamp) -> Self {
+        Self {
+            unstake_tx,
+            completes_at: unstaked_at + UNBONDING_PERIOD_SECS + CLAIM_MARGIN_SECS,
+        }
+    }
+
+    /// Seconds left until the claim can be submitted (0 once unbonded).
+    pub fn remaining_secs(&self) -> u64 {
+        self.completes_at.0.saturating_sub(Timestamp::now().0)
+    }
+
+    /// Sleep until unbonding completes, then submit `ClaimUnstake`.
+    pub async fn wait_and_claim(
+        &self,
+        client: &WalletClient,
+        wallet: &WalletKeypair,
+    ) -> Result<String, WalletClientError> {
+        let remaining = self.remaining_secs();
+        if remaining > 0 {
+            tokio::time::sleep(std::time::Duration::from_secs(remaining)).await;
+        }
+        client.claim_unstake(wallet).await
+    }
+}
+
 /// Async RPC client for wallet operations on a Vexidus node.
 pub struct WalletClient {
     rpc_url: String,
-    client: reqwest::Client,
+    transport: Transport,
+    fee_schedule: Mutex<Option<FeeSchedule>>,
+    /// Next nonce to use per sender, for the convenience submitters.
+    nonces: Mutex<HashMap<String, u64>>,
+    strict: bool,
 }
 
 impl WalletClient {
     /// Create a new wallet client pointing at a Vexidus node RPC endpoint.
     pub fn new(rpc_url: &str) -> Self {
+        Self::with_transport(rpc_url, Transport::http(rpc_url))
+    }
+
+    /// Create a client backed by canned responses (see [`MockTransport`]).
+    pub fn with_mock(mock: MockTransport) -> Self {
+        Self::with_transport("mock://", Transport::mock(mock))
+    }
+
+    fn with_transport(rpc_url: &str, transport: Transport) -> Self {
         Self {
             rpc_url: rpc_url.to_string(),
-            client: reqwest::Client::new(),
+            transport,
+            fee_schedule: Mutex::new(None),
+            nonces: Mutex::new(HashMap::new()),
+            strict: false,
         }
     }
 
+    /// Check every bundle with [`bundle::assert_roundtrip`] before
+    /// [`submit_bundle`](Self::submit_bundle) sends it. Off by default.
+    pub fn strict(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Set the per-request timeout (default [`DEFAULT_TIMEOUT`](crate::transport::DEFAULT_TIMEOUT), 30s).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.transport.set_timeout(timeout);
+        self
+    }
+
+    /// Retry idempotent reads up to `max` times on transient failures, with
+    /// exponential backoff starting at `backoff` plus jitter. Writes are never
+    /// retried.
+    pub fn with_retries(mut self, max: u32, backoff: Duration) -> Self {
+        self.transport.set_retry_policy(RetryPolicy { max_retries: max, backoff });
+        self
+    }
+
+    /// Record request params on the client's `rpc` tracing spans (`tracing`
+    /// feature). Off by default, since params can contain addresses.
+    #[cfg(feature = "tracing")]
+    pub fn with_log_params(mut self, enabled: bool) -> Self {
+        self.transport.set_log_params(enabled);
+        self
+    }
+
+    /// Effective per-request timeout.
+    pub fn timeout(&self) -> Duration {
+        self.transport.timeout()
+    }
+
+    /// Effective retry policy for reads.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.transport.retry_policy()
+    }
+
+    /// RPC endpoint this client talks to.
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// A client on the same endpoint and transport, for a background task
+    /// that outlives `&self`. Tracked nonces and the cached fee schedule
+    /// aren't shared.
+    #[cfg(feature = "ws")]
+    pub(crate) fn detached(&self) -> WalletClient {
+        Self::with_transport(&self.rpc_url, self.transport.clone()).strict(self.strict)
+    }
+
     /// Raw JSON-RPC 2.0 call.
-    async fn rpc_call(&self, method: &str, params: Value) -> Result<Value> {
-        let body = json!({
-            "jsonrpc": "2.0",
-            "method": method,
-            "params": params,
-            "id": 1
-        });
-
-        let resp = self
-            .client
-            .post(&self.rpc_url)
-            .json(&body)
-            .send()
-            .await?
-            .json::<Value>()
-            .await?;
+    /// Idempotent read, retried per the client's [`RetryPolicy`].
+    async fn rpc_call(&self, method: &str, params: Value) -> Result<Value, WalletClientError> {
+        self.transport.call_read(method, params).await
+    }
 
-        if let Some(error) = resp.get("error") {
-            anyhow::bail!("RPC error: {}", error);
-        }
+    /// State-changing call, sent exactly once.
+    async fn rpc_write(&self, method: &str, params: Value) -> Result<Value, WalletClientError> {
+        self.transport.call(method, params).await
+    }
 
-        Ok(resp["result"].clone())
+    /// Call any node RPC method directly, for methods the SDK doesn't wrap yet.
+    ///
+    /// Bypasses the typed helpers: the `result` comes back as raw JSON and no
+    /// retry policy is applied.
+    pub async fn raw_call(&self, method: &str, params: Value) -> Result<Value, WalletClientError> {
+        self.rpc_write(method, params).await
+    }
+
+    /// Send several calls in one JSON-RPC batch: a single HTTP round trip.
+    ///
+    /// Results come back in request order, each entry succeeding or failing on
+    /// its own; the outer error means the batch as a whole failed. Like
+    /// [`raw_call`](Self::raw_call), no retry policy is applied.
+    pub async fn batch_call(
+        &self,
+        requests: &[(String, Value)],
+    ) -> Result<Vec<Result<Value, WalletClientError>>, WalletClientError> {
+        self.transport.call_batch(requests).await
     }
 
     // --- Balance & Account ---
@@ -62,13 +718,34 @@ impl WalletClient {
     ///
     /// Returns the raw balance string from the node (human-readable VXS units).
     /// `token` can be "VXS" or a mint address.
-    pub async fn get_balance(&self, address: &str, token: &str) -> Result<String> {
+    pub async fn get_balance(&self, address: &str, token: &str) -> Result<String, WalletClientError> {
         let result = self.rpc_call("vex_getBalance", json!([address, token])).await?;
         Ok(result.as_str().unwrap_or("0").to_string())
     }
 
+    /// [`get_balance`](Self::get_balance) for many addresses in one batch.
+    ///
+    /// Results are in `addresses` order; an address the node rejects fails on
+    /// its own without affecting the rest.
+    pub async fn get_balances(
+        &self,
+        addresses: &[&str],
+        token: &str,
+    ) -> Result<Vec<Result<String, WalletClientError>>, WalletClientError> {
+        let requests: Vec<(String, Value)> = addresses
+            .iter()
+            .map(|address| ("vex_getBalance".to_string(), json!([address, token])))
+            .collect();
+        Ok(self
+            .batch_call(&requests)
+            .await?
+            .into_iter()
+            .map(|result| result.map(|balance| balance.as_str().unwrap_or("0").to_string()))
+            .collect())
+    }
+
     /// Get the current nonce for an address (for replay protection).
-    pub async fn get_nonce(&self, address: &str) -> Result<u64> {
+    pub async fn get_nonce(&self, address: &str) -> Result<u64, WalletClientError> {
         let result = self
             .rpc_call("eth_getTransactionCount", json!([address, "latest"]))
             .await?;
@@ -77,23 +754,378 @@ impl WalletClient {
         Ok(nonce)
     }
 
+    /// Next nonce for `address` counting bundles still in the node's pool
+    /// (`eth_getTransactionCount` at `"pending"`), so a new bundle doesn't
+    /// reuse the nonce of one that hasn't executed yet.
+    pub async fn get_pending_nonce(&self, address: &str) -> Result<u64, WalletClientError> {
+        let result = self
+            .rpc_call("eth_getTransactionCount", json!([address, "pending"]))
+            .await?;
+        let hex_str = result
+            .as_str()
+            .ok_or_else(|| WalletClientError::Decode(format!("Unexpected nonce: {}", result)))?;
+        u64::from_str_radix(hex_str.trim_start_matches("0x"), 16)
+            .map_err(|e| WalletClientError::Decode(format!("Invalid nonce {}: {}", hex_str, e)))
+    }
+
+    /// Next nonce the convenience submitters ([`transfer`](Self::transfer) and
+    /// friends) will use for `address`, or `None` if it isn't tracked yet and
+    /// will be read from the node.
+    pub fn peek_nonce(&self, address: &str) -> Option<u64> {
+        self.nonces.lock().unwrap().get(address).copied()
+    }
+
+    /// Forget the tracked nonce for `address`, so the next submission re-reads
+    /// it from the node. Needed after sending bundles for the same account
+    /// some other way.
+    pub fn reset_nonce(&self, address: &str) {
+        self.nonces.lock().unwrap().remove(address);
+    }
+
+    /// Reserve the next nonce for `address`: the tracked one if any, else
+    /// [`get_pending_nonce`](Self::get_pending_nonce). Each call hands out a
+    /// different nonce, so concurrent submissions from one wallet don't collide.
+    async fn next_nonce(&self, address: &str) -> Result<u64, WalletClientError> {
+        if let Some(next) = self.nonces.lock().unwrap().get_mut(address) {
+            *next += 1;
+            return Ok(*next - 1);
+        }
+        let chain_nonce = self.get_pending_nonce(address).await?;
+        // Another task may have synced while we were fetching; its count wins
+        let mut nonces = self.nonces.lock().unwrap();
+        let next = nonces.entry(address.to_string()).or_insert(chain_nonce);
+        *next += 1;
+        Ok(*next - 1)
+    }
+
+    /// Sign and submit a bundle of `operations` from `wallet` under the next
+    /// tracked nonce. The nonce is re-synced from the node next time if the
+    /// bundle never reached it (it failed to build, or the node couldn't be
+    /// connected to) or the node rejected its nonce; other errors (a timeout,
+    /// a revert) may have consumed the nonce, so it stays reserved.
+    async fn submit_with_next_nonce(
+        &self,
+        wallet: &WalletKeypair,
+        operations: impl FnOnce(BundleBuilder) -> Result<BundleBuilder, BundleError>,
+    ) -> Result<String, WalletClientError> {
+        let sender = wallet.hex_address();
+        let nonce = self.next_nonce(&sender).await?;
+        let bundle = BundleBuilder::new(&sender)
+            .and_then(operations)
+            .and_then(|builder| builder.nonce(nonce).valid_for(3600).sign(wallet));
+        let result = match bundle {
+            Ok(bundle) => self.submit_bundle(&bundle).await,
+            Err(e) => {
+                self.reset_nonce(&sender);
+                return Err(e.into());
+            }
+        };
+        if result
+            .as_ref()
+            .is_err_and(|e| e.is_nonce_error() || matches!(e, WalletClientError::Connect(_)))
+        {
+            self.reset_nonce(&sender);
+        }
+        result
+    }
+
+    /// Explain why an account's bundles aren't landing.
+    ///
+    /// Compares the confirmed nonce with the account's pending bundles
+    /// (`vex_getPendingTransactions`). Pending nonces must run on from the
+    /// confirmed one without holes; any missing nonce is reported in
+    /// `missing_nonces`. If anything is pending, the lowest-nonce bundle is
+    /// `stuck_tx`, and `replacement_priority_fee` is the
+    /// [`suggest_priority_fee`](Self::suggest_priority_fee) for current
+    /// congestion, raised to at least 10% over the stuck bundle's own fee.
+    pub async fn diagnose_nonce(&self, address: &str) -> Result<NonceDiagnosis, WalletClientError> {
+        let chain_nonce = self.get_nonce(address).await?;
+        let pending = self.get_pending(address).await?;
+
+        let mut missing_nonces = Vec::new();
+        let mut expected = chain_nonce;
+        for tx in &pending {
+            missing_nonces.extend(expected..tx.nonce);
+            expected = expected.max(tx.nonce + 1);
+        }
+
+        let stuck = pending.first();
+        let replacement_priority_fee = match stuck {
+            Some(tx) => {
+                let suggested = self.suggest_priority_fee().await?;
+                let bump = tx.max_priority_fee.map_or(0, |fee| fee.saturating_add(fee.div_ceil(10)));
+                Some(suggested.max(bump))
+            }
+            None => None,
+        };
+        Ok(NonceDiagnosis {
+            chain_nonce,
+            pending_count: pending.len() as u32,
+            gap: !missing_nonces.is_empty(),
+            stuck_tx: stuck.map(|tx| tx.hash.clone()),
+            missing_nonces,
+            replacement_priority_fee,
+        })
+    }
+
+    /// `address`'s bundles waiting in the mempool (`vex_getPendingTransactions`),
+    /// lowest nonce first. Entries the node sends without a hash or nonce are
+    /// skipped.
+    pub async fn get_pending(&self, address: &str) -> Result<Vec<PendingTx>, WalletClientError> {
+        let result = self
+            .rpc_call("vex_getPendingTransactions", json!([address]))
+            .await?;
+        let mut pending: Vec<PendingTx> = result
+            .as_array()
+            .map(|txs| txs.iter().filter_map(|tx| PendingTx::from_value(tx, address)).collect())
+            .unwrap_or_default();
+        pending.sort_by_key(|tx| tx.nonce);
+        Ok(pending)
+    }
+
+    /// Whether `tx_hash` is pending, included, dropped, or unknown to the node.
+    ///
+    /// A receipt (`vex_getTransactionReceipt`) means included. Otherwise
+    /// `vex_getTransactionByHash` decides: null is [`Unknown`](TxStatus::Unknown),
+    /// a `status` of `"dropped"`, `"evicted"` or `"expired"` is
+    /// [`Dropped`](TxStatus::Dropped), and anything else is
+    /// [`Pending`](TxStatus::Pending).
+    pub async fn get_transaction_status(&self, tx_hash: &str) -> Result<TxStatus, WalletClientError> {
+        let receipt = self.rpc_call("vex_getTransactionReceipt", json!([tx_hash])).await?;
+        if !receipt.is_null() {
+            let receipt = TxReceipt::from_value(tx_hash, &receipt)?;
+            return Ok(TxStatus::Included { block: receipt.block_height });
+        }
+        let tx = self.rpc_call("vex_getTransactionByHash", json!([tx_hash])).await?;
+        if tx.is_null() {
+            return Ok(TxStatus::Unknown);
+        }
+        let status = tx["status"].as_str().unwrap_or_default().to_ascii_lowercase();
+        Ok(match status.as_str() {
+            "dropped" | "evicted" | "expired" => TxStatus::Dropped,
+            _ => TxStatus::Pending,
+        })
+    }
+
+    /// Look up a transaction (pending or included) by hash via
+    /// `vex_getTransactionByHash`; `None` if the node doesn't know it.
+    pub async fn get_transaction(&self, tx_hash: &str) -> Result<Option<TransactionInfo>, WalletClientError> {
+        let result = self.rpc_call("vex_getTransactionByHash", json!([tx_hash])).await?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        TransactionInfo::from_value(&result).map(Some)
+    }
+
+    /// Fetch the block at `height`, with full transactions, via
+    /// `vex_getBlockByNumber`; `None` if there is no such block yet.
+    pub async fn get_block(&self, height: u64) -> Result<Option<BlockInfo>, WalletClientError> {
+        let result = self
+            .rpc_call("vex_getBlockByNumber", json!([format!("0x{:x}", height), true]))
+            .await?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        BlockInfo::from_value(&result).map(Some)
+    }
+
+    /// Get recent transactions for an address, newest first.
+    pub async fn get_transaction_history(&self, address: &str, limit: u32) -> Result<Value, WalletClientError> {
+        self.rpc_call("vex_getTransactionHistory", json!([address, limit]))
+            .await
+    }
+
+    /// Resolve a `.vex` name (with or without `.vex`, any case) to its
+    /// holder's address, or `None` if unregistered. Names are normalised the
+    /// same way as [`VnsClient::resolve`](crate::VnsClient::resolve).
+    pub async fn resolve_name(&self, name: &str) -> Result<Option<String>, WalletClientError> {
+        let name = vns::bare_name(name);
+        let result = self.rpc_call("vex_resolveName", json!([name])).await?;
+        Ok(result.as_str().map(String::from))
+    }
+
+    /// List the hex public keys holding the Recovery role on an account.
+    pub async fn list_recovery_keys(&self, address: &str) -> Result<Vec<String>, WalletClientError> {
+        let result = self.rpc_call("vex_getAccountKeys", json!([address])).await?;
+        Ok(result
+            .as_array()
+            .map(|keys| {
+                keys.iter()
+                    .filter(|k| k["role"] == "Recovery")
+                    .filter_map(|k| k["pubkey"].as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Check a signed bundle against the sender account's VSA v2 key set.
+    ///
+    /// Fetches the authorized keys (`vex_getAccountKeys`), finds the key that
+    /// produced the bundle signature, and checks that its role permits every
+    /// operation (see [`bundle::role_permits`]). Returns `false` if no
+    /// authorized key signed the bundle.
+    ///
+    /// The key set comes from the node, but the role policy is the SDK's own,
+    /// so this is a pre-flight check, not a prediction of what the node
+    /// accepts.
+    pub async fn verify_authorized(&self, bundle: &TransactionBundle) -> Result<bool, WalletClientError> {
+        let account = format!("0x{}", hex::encode(bundle.user_account.0));
+        let result = self.rpc_call("vex_getAccountKeys", json!([account])).await?;
+        let keys = result.as_array().cloned().unwrap_or_default();
+
+        for key in &keys {
+            let pubkey_hex = key["pubkey"].as_str().unwrap_or_default();
+            let Ok(bytes) = hex::decode(pubkey_hex.trim_start_matches("0x")) else {
+                continue;
+            };
+            let Ok(pubkey) = <[u8; 32]>::try_from(bytes.as_slice()) else {
+                continue;
+            };
+            if !bundle.verify_signature(&PublicKey(pubkey)) {
+                continue;
+            }
+            let role: KeyRole = serde_json::from_value(key["role"].clone())?;
+            return Ok(bundle
+                .operations
+                .iter()
+                .all(|op| bundle::role_permits(&role, op)));
+        }
+        Ok(false)
+    }
+
+    /// Fetch and decode the event logs emitted by transaction `tx_hash`.
+    ///
+    /// See [`DecodedEvent`] for how fields are normalized. For swaps, pass the
+    /// result to [`ExecutionReport::from_events`](crate::dex::ExecutionReport::from_events)
+    /// to compare against the quote.
+    pub async fn get_logs(&self, tx_hash: &str) -> Result<Vec<DecodedEvent>, WalletClientError> {
+        let receipt = self.rpc_call("vex_getTransactionReceipt", json!([tx_hash])).await?;
+        if receipt.is_null() {
+            return Err(WalletClientError::NotFound(format!("transaction {}", tx_hash)));
+        }
+        Ok(decode_logs(&receipt))
+    }
+
+    /// Get transfers of a single token to or from `address`, newest first.
+    ///
+    /// `token` is "VXS", a mint address, or a registered symbol (resolved via
+    /// [`get_token_info`](Self::get_token_info)). Uses the node's filtered
+    /// `vex_getTokenTransfers` when available; otherwise filters the last
+    /// `limit` entries of the transaction history client-side, which may
+    /// return fewer than `limit` transfers. Entries without a `token` field
+    /// are native transfers and count as VXS.
+    pub async fn get_token_transfers(
+        &self,
+        address: &str,
+        token: &str,
+        limit: u32,
+    ) -> Result<Vec<TokenTransfer>, WalletClientError> {
+        let account = address_utils::parse_address(address)?;
+        let mint = self.resolve_mint(token).await?;
+        let mint_arg = format!("0x{}", hex::encode(mint.0));
+
+        let entries = match self
+            .rpc_call("vex_getTokenTransfers", json!([address, mint_arg, limit]))
+            .await
+        {
+            Ok(result) => result.as_array().cloned().unwrap_or_default(),
+            Err(e) => {
+                log::debug!("vex_getTokenTransfers unavailable, filtering history: {}", e);
+                self.get_transaction_history(address, limit)
+                    .await?
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default()
+            }
+        };
+
+        Ok(entries
+            .iter()
+            .filter(|tx| match &tx["token"] {
+                Value::Null => mint == Address::ZERO,
+                token => token
+                    .as_str()
+                    .and_then(|t| bundle::parse_token(t).ok())
+                    .is_some_and(|t| t == mint),
+            })
+            .filter_map(|tx| token_transfer(tx, &account))
+            .collect())
+    }
+
+    /// Resolve "VXS", a mint address, or a registry symbol to a mint address.
+    async fn resolve_mint(&self, token: &str) -> Result<Address, WalletClientError> {
+        if let Ok(mint) = bundle::parse_token(token) {
+            return Ok(mint);
+        }
+        let info = self.get_token_info(token).await?;
+        let mint = info["address"]
+            .as_str()
+            .or_else(|| info["mint"].as_str())
+            .ok_or_else(|| WalletClientError::NotFound(format!("token {}", token)))?;
+        Ok(address_utils::parse_address(mint)?)
+    }
+
     // --- Transactions ---
 
     /// Submit a pre-signed TransactionBundle to the network.
     ///
     /// The bundle is Borsh-serialized and hex-encoded before submission
-    /// via `vex_submitBundle`.
-    pub async fn submit_bundle(&self, bundle: &TransactionBundle) -> Result<String> {
-        let bytes = borsh::to_vec(bundle)?;
+    /// via `vex_submitBundle`. In [`strict`](Self::strict) mode its encoding
+    /// and signature are checked first.
+    pub async fn submit_bundle(&self, bundle: &TransactionBundle) -> Result<String, WalletClientError> {
+        if self.strict {
+            bundle::assert_roundtrip(bundle)?;
+        }
+        let bytes = borsh::to_vec(bundle).map_err(|e| WalletClientError::Decode(e.to_string()))?;
         let hex_str = format!("0x{}", hex::encode(&bytes));
-        let result = self.rpc_call("vex_submitBundle", json!([hex_str])).await?;
+        let result = self.rpc_write("vex_submitBundle", json!([hex_str])).await?;
         Ok(result.as_str().unwrap_or_default().to_string())
     }
 
+    /// Poll `vex_getTransactionReceipt` until `tx_hash` is included, waiting at
+    /// most `timeout`.
+    ///
+    /// Polls back off exponentially from 200ms up to 5s. Returns
+    /// [`ConfirmationTimeout`](WalletClientError::ConfirmationTimeout) if the
+    /// transaction is still pending at the deadline, and
+    /// [`Reverted`](WalletClientError::Reverted) if it was included but failed.
+    pub async fn wait_for_confirmation(
+        &self,
+        tx_hash: &str,
+        timeout: Duration,
+    ) -> Result<TxReceipt, WalletClientError> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut delay = CONFIRMATION_POLL_START;
+        loop {
+            let receipt = self.rpc_call("vex_getTransactionReceipt", json!([tx_hash])).await?;
+            if !receipt.is_null() {
+                let receipt = TxReceipt::from_value(tx_hash, &receipt)?;
+                if !receipt.success {
+                    return Err(WalletClientError::Reverted {
+                        tx_hash: receipt.tx_hash,
+                        reason: receipt.error.unwrap_or_else(|| "unknown".into()),
+                        gas_used: receipt.gas_used,
+                    });
+                }
+                return Ok(receipt);
+            }
+
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                return Err(WalletClientError::ConfirmationTimeout(tx_hash.to_string()));
+            }
+            tokio::time::sleep(delay.min(deadline - now)).await;
+            delay = (delay * 2).min(CONFIRMATION_POLL_MAX);
+        }
+    }
+
     /// Convenience: build, sign, and submit a VXS transfer in one call.
     ///
-    /// Automatically fetches the current nonce, builds a bundle, signs it,
-    /// and submits it to the network.
+    /// Takes the next nonce from the client's tracker (reading it from the
+    /// node the first time, and again after a failed submission), builds a
+    /// bundle, signs it, and submits it to the network. Back-to-back or
+    /// concurrent transfers from one wallet get consecutive nonces; see
+    /// [`reset_nonce`](Self::reset_nonce).
     ///
     /// `amount` is in raw units (1 VXS = 1_000_000_000).
     pub async fn transfer(
@@ -102,42 +1134,222 @@ impl WalletClient {
         to: &str,
         token: &str,
         amount: u128,
-    ) -> Result<String> {
-        let sender = wallet.hex_address();
-        let nonce = self.get_nonce(&sender).await?;
+    ) -> Result<String, WalletClientError> {
+        self.submit_with_next_nonce(wallet, |b| b.transfer(to, token, amount))
+            .await
+    }
 
-        let bundle = BundleBuilder::new(&sender)?
-            .transfer(to, token, amount)?
-            .nonce(nonce)
-            .valid_for(3600)
-            .sign(wallet);
+    // --- Fees ---
 
-        self.submit_bundle(&bundle).await
+    /// Get the node's per-operation gas schedule (`vex_feeSchedule`).
+    ///
+    /// Fetched once and cached on the client; call
+    /// [`invalidate_fee_schedule`](Self::invalidate_fee_schedule) to refetch.
+    pub async fn fee_schedule(&self) -> Result<FeeSchedule, WalletClientError> {
+        if let Some(cached) = self.fee_schedule.lock().unwrap().clone() {
+            return Ok(cached);
+        }
+        let result = self.rpc_call("vex_feeSchedule", json!([])).await?;
+        let schedule: FeeSchedule = serde_json::from_value(result)?;
+        *self.fee_schedule.lock().unwrap() = Some(schedule.clone());
+        Ok(schedule)
+    }
+
+    /// Drop the cached fee schedule so the next call refetches it.
+    pub fn invalidate_fee_schedule(&self) {
+        *self.fee_schedule.lock().unwrap() = None;
+    }
+
+    /// Estimate the fee for a bundle (signed or unsigned).
+    ///
+    /// Asks the node via `vex_estimateFee`; if that fails, falls back to a
+    /// local `estimate_gas * gas_price` using the cached fee schedule when
+    /// available. Check [`FeeEstimate::source`] before showing it as exact.
+    pub async fn estimate_fee(&self, bundle: &TransactionBundle) -> Result<FeeEstimate, WalletClientError> {
+        let bytes = borsh::to_vec(bundle).map_err(|e| WalletClientError::Decode(e.to_string()))?;
+        let bundle_hex = format!("0x{}", hex::encode(bytes));
+        match self.rpc_call("vex_estimateFee", json!([bundle_hex])).await {
+            Ok(result) => {
+                let gas = result["gas"].as_u64().unwrap_or(0);
+                let gas_price = result["gas_price"].as_u64().unwrap_or(DEFAULT_GAS_PRICE);
+                let computed = gas as u128 * gas_price as u128;
+                let total_fee = match &result["total_fee"] {
+                    Value::String(s) => s.parse().unwrap_or(computed),
+                    Value::Number(n) => n.as_u64().map(u128::from).unwrap_or(computed),
+                    _ => computed,
+                };
+                Ok(FeeEstimate { gas, gas_price, total_fee, source: FeeSource::Node })
+            }
+            Err(e) => {
+                log::debug!("vex_estimateFee unavailable ({}), estimating locally", e);
+                let schedule = self.fee_schedule().await.ok();
+                let gas = bundle::estimate_operations_gas(&bundle.operations, schedule.as_ref());
+                let gas_price = schedule
+                    .map(|s| s.gas_price)
+                    .filter(|p| *p > 0)
+                    .unwrap_or(DEFAULT_GAS_PRICE);
+                Ok(FeeEstimate {
+                    gas,
+                    gas_price,
+                    total_fee: gas as u128 * gas_price as u128,
+                    source: FeeSource::Local,
+                })
+            }
+        }
+    }
+
+    /// Gas the node expects `bundle` to use (`vex_estimateGas`), simulated
+    /// against current state.
+    ///
+    /// Fails with [`WouldRevert`](WalletClientError::WouldRevert) if the
+    /// simulation reverts — reported either as JSON-RPC error code 3 (the
+    /// `eth_estimateGas` convention) or as `{ "reverted": true, "reason" }`.
+    pub async fn estimate_gas(&self, bundle: &TransactionBundle) -> Result<u64, WalletClientError> {
+        let bytes = borsh::to_vec(bundle).map_err(|e| WalletClientError::Decode(e.to_string()))?;
+        let bundle_hex = format!("0x{}", hex::encode(bytes));
+        let result = match self.rpc_call("vex_estimateGas", json!([bundle_hex])).await {
+            Err(WalletClientError::Rpc { code: 3, message }) => {
+                return Err(WalletClientError::WouldRevert { reason: message })
+            }
+            other => other?,
+        };
+        if result["reverted"].as_bool() == Some(true) {
+            let reason = result["reason"].as_str().unwrap_or("unknown").to_string();
+            return Err(WalletClientError::WouldRevert { reason });
+        }
+        let gas = if result.is_object() { &result["gas"] } else { &result };
+        quantity(gas).ok_or_else(|| WalletClientError::Decode(format!("Unreadable gas estimate: {}", result)))
+    }
+
+    /// Dry-run `bundle` against current state (`vex_simulateBundle`) without
+    /// submitting it.
+    ///
+    /// The bundle may be unsigned (empty signature, e.g. straight from
+    /// [`BundleBuilder::build`]), so a wallet can preview "you will receive
+    /// ~X" before asking for the key. A bundle that would revert is a
+    /// successful call with `success: false`.
+    pub async fn simulate_bundle(&self, bundle: &TransactionBundle) -> Result<SimulationResult, WalletClientError> {
+        let bytes = borsh::to_vec(bundle).map_err(|e| WalletClientError::Decode(e.to_string()))?;
+        let bundle_hex = format!("0x{}", hex::encode(bytes));
+        let result = self.rpc_call("vex_simulateBundle", json!([bundle_hex])).await?;
+        SimulationResult::from_value(&result)
+    }
+
+    /// Estimate congestion from the fullness of the last 20 blocks
+    /// (`eth_feeHistory` `gasUsedRatio`).
+    pub async fn congestion_level(&self) -> Result<Congestion, WalletClientError> {
+        let block_count = format!("0x{:x}", CONGESTION_BLOCKS);
+        let result = self
+            .rpc_call("eth_feeHistory", json!([block_count, "latest", []]))
+            .await?;
+        let ratios: Vec<f64> = result["gasUsedRatio"]
+            .as_array()
+            .map(|r| r.iter().filter_map(Value::as_f64).collect())
+            .unwrap_or_default();
+        if ratios.is_empty() {
+            return Err(WalletClientError::Decode("eth_feeHistory returned no gasUsedRatio".into()));
+        }
+        let utilization = ratios.iter().sum::<f64>() / ratios.len() as f64;
+        Ok(Congestion::from_utilization(utilization.clamp(0.0, 1.0)))
+    }
+
+    /// Suggest a priority fee (nanoVXS per gas) for the current network load.
+    ///
+    /// Starts from the node's `eth_maxPriorityFeePerGas` (or
+    /// [`DEFAULT_PRIORITY_FEE`]) and scales it by the
+    /// [`congestion_level`](Self::congestion_level) multiplier, rounding up.
+    /// If fee history is unavailable the base fee is returned unscaled.
+    pub async fn suggest_priority_fee(&self) -> Result<u64, WalletClientError> {
+        let base = match self.rpc_call("eth_maxPriorityFeePerGas", json!([])).await {
+            Ok(result) => result
+                .as_str()
+                .and_then(|h| u64::from_str_radix(h.trim_start_matches("0x"), 16).ok())
+                .unwrap_or(DEFAULT_PRIORITY_FEE),
+            Err(e) => {
+                log::debug!("eth_maxPriorityFeePerGas unavailable ({}), using default", e);
+                DEFAULT_PRIORITY_FEE
+            }
+        };
+        let multiplier = match self.congestion_level().await {
+            Ok(congestion) => congestion.suggested_fee_multiplier,
+            Err(e) => {
+                log::debug!("Congestion unknown ({}), not scaling priority fee", e);
+                1.0
+            }
+        };
+        Ok((base as f64 * multiplier).ceil() as u64)
+    }
+
+    /// Build, sign, and submit a `ClaimUnstake` for matured unbonding VXS.
+    pub async fn claim_unstake(&self, wallet: &WalletKeypair) -> Result<String, WalletClientError> {
+        self.submit_with_next_nonce(wallet, |b| Ok(b.claim_unstake()))
+            .await
+    }
+
+    /// Unstake `amount` now and schedule the claim for when unbonding ends.
+    ///
+    /// Await (or spawn) [`ScheduledClaim::wait_and_claim`] on the result to
+    /// actually withdraw; nothing claims automatically on-chain.
+    pub async fn unstake_and_schedule_claim(
+        &self,
+        wallet: &WalletKeypair,
+        amount: u128,
+    ) -> Result<ScheduledClaim, WalletClientError> {
+        let tx = self
+            .submit_with_next_nonce(wallet, |b| Ok(b.unstake(amount)))
+            .await?;
+        Ok(ScheduledClaim::new(tx, Timestamp::now()))
     }
 
     // --- Token Info ---
 
     /// Get token metadata by mint address or symbol.
-    pub async fn get_token_info(&self, address_or_symbol: &str) -> Result<Value> {
+    pub async fn get_token_info(&self, address_or_symbol: &str) -> Result<Value, WalletClientError> {
         self.rpc_call("vex_getTokenInfo", json!([address_or_symbol]))
             .await
     }
 
     /// List tokens registered on the network.
-    pub async fn list_tokens(&self, limit: u32) -> Result<Value> {
+    pub async fn list_tokens(&self, limit: u32) -> Result<Value, WalletClientError> {
         self.rpc_call("vex_listTokens", json!([limit])).await
     }
 
+    /// One page of up to `limit` tokens starting at `cursor`, and the cursor of
+    /// the next page. See [paging](crate::transport#paging).
+    pub async fn list_tokens_paged(
+        &self,
+        cursor: Option<String>,
+        limit: u32,
+    ) -> Result<(Vec<Value>, Option<String>), WalletClientError> {
+        let result = self.rpc_call("vex_listTokens", json!([limit, cursor])).await?;
+        let (items, next) = split_page(result);
+        let tokens = match items {
+            Value::Array(tokens) => tokens,
+            other => return Err(WalletClientError::Decode(format!("Token list is not an array: {}", other))),
+        };
+        Ok((tokens, next))
+    }
+
+    /// Every registered token, fetched `page_size` at a time with
+    /// [`list_tokens_paged`](Self::list_tokens_paged).
+    pub async fn list_all_tokens(&self, page_size: u32) -> Result<Vec<Value>, WalletClientError> {
+        collect_pages(
+            |cursor| self.list_tokens_paged(cursor, page_size),
+            |cursor| WalletClientError::Decode(format!("Node repeated token cursor {}", cursor)),
+        )
+        .await
+    }
+
     // --- Chain Info ---
 
     /// Get the chain ID (testnet: "0x18b070", mainnet: "0x18b071").
-    pub async fn chain_id(&self) -> Result<String> {
+    pub async fn chain_id(&self) -> Result<String, WalletClientError> {
         let result = self.rpc_call("eth_chainId", json!([])).await?;
         Ok(result.as_str().unwrap_or("0x0").to_string())
     }
 
     /// Get the current block height.
-    pub async fn block_number(&self) -> Result<u64> {
+    pub async fn block_number(&self) -> Result<u64, WalletClientError> {
         let result = self.rpc_call("eth_blockNumber", json!([])).await?;
         let hex_str = result.as_str().unwrap_or("0x0");
         let height = u64::from_str_radix(hex_str.trim_start_matches("0x"), 16).unwrap_or(0);
@@ -150,6 +1362,74 @@ impl WalletClient {
     }
 }
 
+/// Decode a receipt's `logs` array (`[{ "event": .., "data": {..} }]`).
+pub fn decode_logs(receipt: &Value) -> Vec<DecodedEvent> {
+    let Some(logs) = receipt["logs"].as_array() else {
+        return Vec::new();
+    };
+    logs.iter().map(decode_event).collect()
+}
+
+/// Address and amount fields of the known event types.
+fn event_schema(kind: &str) -> (&'static [&'static str], &'static [&'static str]) {
+    match kind {
+        "SwapExecuted" => (&["pool", "from_token", "to_token", "trader"], &["amount_in", "amount_out", "fee"]),
+        "LiquidityAdded" => (&["pool", "provider", "token_a", "token_b"], &["amount_a", "amount_b", "lp_minted"]),
+        "LiquidityRemoved" => (&["pool", "provider", "token_a", "token_b"], &["amount_a", "amount_b", "lp_burned"]),
+        "Transfer" => (&["from", "to", "token"], &["amount"]),
+        _ => (&[], &[]),
+    }
+}
+
+fn decode_event(log: &Value) -> DecodedEvent {
+    let kind = log["event"].as_str().unwrap_or("Unknown").to_string();
+    let mut fields: HashMap<String, Value> = match &log["data"] {
+        Value::Object(map) => map.clone().into_iter().collect(),
+        Value::Null => HashMap::new(),
+        other => HashMap::from([("raw".to_string(), other.clone())]),
+    };
+
+    let (addresses, amounts) = event_schema(&kind);
+    for name in addresses {
+        if let Some(value) = fields.get_mut(*name) {
+            if let Some(addr) = value.as_str().and_then(|s| address_utils::parse_address(s).ok()) {
+                *value = json!(format!("0x{}", hex::encode(addr.0)));
+            }
+        }
+    }
+    for name in amounts {
+        if let Some(value) = fields.get_mut(*name) {
+            if let Some(amount) = value_to_u128(value) {
+                *value = json!(amount.to_string());
+            }
+        }
+    }
+    DecodedEvent { kind, fields }
+}
+
+/// Convert a history entry into a [`TokenTransfer`] seen from `account`.
+/// Entries that don't involve `account` are dropped.
+fn token_transfer(tx: &Value, account: &Address) -> Option<TokenTransfer> {
+    let from = tx["from"].as_str()?;
+    let to = tx["to"].as_str()?;
+    let is_from = address_utils::parse_address(from).ok().as_ref() == Some(account);
+    let is_to = address_utils::parse_address(to).ok().as_ref() == Some(account);
+    let direction = match (is_from, is_to) {
+        (true, true) => TransferDirection::SelfTransfer,
+        (true, false) => TransferDirection::Outgoing,
+        (false, true) => TransferDirection::Incoming,
+        (false, false) => return None,
+    };
+    Some(TokenTransfer {
+        hash: tx["hash"].as_str().unwrap_or_default().to_string(),
+        from: from.to_string(),
+        to: to.to_string(),
+        amount: value_to_u128(&tx["amount"])?,
+        timestamp: tx["timestamp"].as_u64().unwrap_or(0),
+        direction,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,4 +1439,610 @@ mod tests {
         let client = WalletClient::new("http://localhost:9933");
         assert_eq!(client.rpc_url, "http://localhost:9933");
     }
+
+    fn transfer_bundle() -> TransactionBundle {
+        let sender = format!("0x{}", hex::encode([1u8; 32]));
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+        BundleBuilder::new(&sender)
+            .unwrap()
+            .transfer(&to, "VXS", 1_000)
+            .unwrap()
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee_from_node() {
+        let mock = MockTransport::new().with_result(
+            "vex_estimateFee",
+            json!({ "gas": 25_000, "gas_price": 12, "total_fee": "300000" }),
+        );
+        let client = WalletClient::with_mock(mock);
+
+        let estimate = client.estimate_fee(&transfer_bundle()).await.unwrap();
+        assert_eq!(estimate.source, FeeSource::Node);
+        assert_eq!(estimate.gas, 25_000);
+        assert_eq!(estimate.gas_price, 12);
+        assert_eq!(estimate.total_fee, 300_000);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_gas_fills_max_gas() {
+        let mock = MockTransport::new()
+            .with_result("vex_estimateGas", json!("0x61a8"))
+            .with_result("vex_estimateGas", json!({ "gas": 25_000 }))
+            .with_result("vex_estimateGas", json!({ "gas": 21_000, "reverted": true, "reason": "insufficient balance" }))
+            .with_error("vex_estimateGas", 3, "execution reverted: nonce too low");
+        let client = WalletClient::with_mock(mock);
+        assert_eq!(client.estimate_gas(&transfer_bundle()).await.unwrap(), 25_000);
+
+        let sender = format!("0x{}", hex::encode([1u8; 32]));
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+        let builder = || BundleBuilder::new(&sender).unwrap().transfer(&to, "VXS", 1_000).unwrap();
+        let bundle = builder().with_estimated_gas(&client).await.unwrap().build();
+        assert_eq!(bundle.max_gas, 30_000);
+
+        let err = builder().with_estimated_gas(&client).await.err().unwrap();
+        assert!(matches!(err, WalletClientError::WouldRevert { ref reason } if reason == "insufficient balance"));
+        let err = client.estimate_gas(&transfer_bundle()).await.unwrap_err();
+        assert!(matches!(err, WalletClientError::WouldRevert { ref reason } if reason.contains("nonce too low")));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_unsigned_bundle() {
+        let me = format!("0x{}", hex::encode([1u8; 32]));
+        let mock = MockTransport::new()
+            .with_result(
+                "vex_simulateBundle",
+                json!({
+                    "success": true,
+                    "gasUsed": "0x5208",
+                    "operations": [{ "success": true, "gas_used": 21_000 }],
+                    "balance_deltas": [
+                        { "address": me, "token": "VXS", "delta": "-1000" },
+                        { "address": format!("0x{}", hex::encode([2u8; 32])), "token": "VXS", "delta": 1000 }
+                    ]
+                }),
+            )
+            .with_result(
+                "vex_simulateBundle",
+                json!({
+                    "success": false,
+                    "gas_used": 21_000,
+                    "operations": [{ "success": false, "gas_used": 21_000, "error": "insufficient balance" }],
+                    "revert_reason": "insufficient balance"
+                }),
+            );
+        let client = WalletClient::with_mock(mock.clone());
+        let bundle = transfer_bundle();
+        assert!(bundle.signature.0.is_empty());
+
+        let sim = client.simulate_bundle(&bundle).await.unwrap();
+        assert!(sim.success);
+        assert_eq!(sim.gas_used, 21_000);
+        assert_eq!(sim.operations, vec![OperationOutcome { success: true, gas_used: 21_000, error: None }]);
+        assert_eq!(sim.delta_for(&me, "vxs"), -1000);
+        assert_eq!(sim.delta_for(&me, "USDC"), 0);
+
+        let sim = client.simulate_bundle(&bundle).await.unwrap();
+        assert!(!sim.success);
+        assert_eq!(sim.revert_reason.as_deref(), Some("insufficient balance"));
+        assert_eq!(sim.operations[0].error.as_deref(), Some("insufficient balance"));
+        assert!(sim.balance_deltas.is_empty());
+        assert_eq!(mock.call_count("vex_submitBundle"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee_local_fallback() {
+        // Node has neither vex_estimateFee nor vex_feeSchedule
+        let client = WalletClient::with_mock(MockTransport::new());
+
+        let estimate = client.estimate_fee(&transfer_bundle()).await.unwrap();
+        assert_eq!(estimate.source, FeeSource::Local);
+        assert_eq!(estimate.gas, 21_000);
+        assert_eq!(estimate.gas_price, DEFAULT_GAS_PRICE);
+        assert_eq!(estimate.total_fee, 210_000);
+    }
+
+    #[tokio::test]
+    async fn test_verify_authorized_by_role() {
+        let owner = WalletKeypair::generate();
+        let hot = WalletKeypair::generate();
+        let mock = MockTransport::new().with_result(
+            "vex_getAccountKeys",
+            json!([
+                { "pubkey": hex::encode(owner.public_key_bytes()), "key_type": "Ed25519", "role": "Owner" },
+                { "pubkey": hex::encode(hot.public_key_bytes()), "key_type": "Ed25519", "role": "Transfer" },
+            ]),
+        );
+        let client = WalletClient::with_mock(mock);
+        let sender = owner.hex_address();
+
+        let transfer = BundleBuilder::new(&sender)
+            .unwrap()
+            .transfer(&format!("0x{}", hex::encode([2u8; 32])), "VXS", 1_000)
+            .unwrap()
+            .sign(&hot)
+            .unwrap();
+        assert!(client.verify_authorized(&transfer).await.unwrap());
+
+        let stake = BundleBuilder::new(&sender)
+            .unwrap()
+            .stake(1_000, hot.public_key_bytes().to_vec())
+            .sign(&hot)
+            .unwrap();
+        assert!(!client.verify_authorized(&stake).await.unwrap());
+
+        // Owner key may stake; an unknown key may do nothing
+        let stake = BundleBuilder::new(&sender)
+            .unwrap()
+            .stake(1_000, owner.public_key_bytes().to_vec())
+            .sign(&owner)
+            .unwrap();
+        assert!(client.verify_authorized(&stake).await.unwrap());
+        let stranger = BundleBuilder::new(&sender)
+            .unwrap()
+            .transfer(&format!("0x{}", hex::encode([2u8; 32])), "VXS", 1_000)
+            .unwrap()
+            .sign(&WalletKeypair::generate())
+            .unwrap();
+        assert!(!client.verify_authorized(&stranger).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_list_recovery_keys() {
+        let mock = MockTransport::new().with_result(
+            "vex_getAccountKeys",
+            json!([
+                { "pubkey": "0xaa", "key_type": "Ed25519", "role": "Owner" },
+                { "pubkey": "0xbb", "key_type": "Ed25519", "role": "Recovery" },
+            ]),
+        );
+        let client = WalletClient::with_mock(mock);
+        assert_eq!(client.list_recovery_keys("Vx0abc").await.unwrap(), vec!["0xbb"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_token_transfers_filters_by_token() {
+        let me = format!("0x{}", hex::encode([1u8; 32]));
+        let other = format!("0x{}", hex::encode([2u8; 32]));
+        let usdc = format!("0x{}", hex::encode([5u8; 32]));
+        let mock = MockTransport::new()
+            .with_result("vex_getTokenInfo", json!({ "symbol": "USDC", "address": usdc }))
+            .with_result(
+                "vex_getTransactionHistory",
+                json!([
+                    { "hash": "0x01", "from": me, "to": other, "token": usdc, "amount": "500", "timestamp": 3 },
+                    { "hash": "0x02", "from": me, "to": other, "token": "VXS", "amount": "900", "timestamp": 2 },
+                    { "hash": "0x03", "from": other, "to": me, "token": usdc, "amount": 250, "timestamp": 1 },
+                    { "hash": "0x04", "from": other, "to": me, "amount": "40", "timestamp": 0 },
+                ]),
+            );
+        let client = WalletClient::with_mock(mock);
+
+        let transfers = client.get_token_transfers(&me, "USDC", 10).await.unwrap();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].hash, "0x01");
+        assert_eq!(transfers[0].amount, 500);
+        assert_eq!(transfers[0].direction, TransferDirection::Outgoing);
+        assert_eq!(transfers[1].hash, "0x03");
+        assert_eq!(transfers[1].direction, TransferDirection::Incoming);
+
+        // No `token` field means a native VXS transfer
+        let native = client.get_token_transfers(&me, "VXS", 10).await.unwrap();
+        assert_eq!(native.len(), 2);
+        assert_eq!(native[0].amount, 900);
+        assert_eq!(native[1].hash, "0x04");
+        assert_eq!(native[1].direction, TransferDirection::Incoming);
+    }
+
+    #[tokio::test]
+    async fn test_transfers_take_consecutive_nonces() {
+        let mock = MockTransport::new()
+            .with_result("eth_getTransactionCount", json!("0x0"))
+            .with_result("vex_submitBundle", json!("0x1"))
+            .with_result("vex_submitBundle", json!("0x2"))
+            .with_result("vex_submitBundle", json!("0x3"))
+            .with_error("vex_submitBundle", -32000, "insufficient balance")
+            .with_error("vex_submitBundle", -32000, "Nonce too low")
+            .with_result("eth_getTransactionCount", json!("0x7"))
+            .with_result("vex_submitBundle", json!("0x4"));
+        let client = WalletClient::with_mock(mock.clone());
+        let wallet = WalletKeypair::generate();
+        let sender = wallet.hex_address();
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+        let submitted_nonces = || -> Vec<u64> {
+            mock.calls()
+                .into_iter()
+                .filter(|(m, _)| m == "vex_submitBundle")
+                .map(|(_, params)| {
+                    let bytes = hex::decode(params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+                    borsh::from_slice::<TransactionBundle>(&bytes).unwrap().nonce.0
+                })
+                .collect()
+        };
+
+        let (a, b, c) = tokio::join!(
+            client.transfer(&wallet, &to, "VXS", 1),
+            client.transfer(&wallet, &to, "VXS", 2),
+            client.transfer(&wallet, &to, "VXS", 3),
+        );
+        assert!(a.is_ok() && b.is_ok() && c.is_ok());
+        let mut nonces = submitted_nonces();
+        nonces.sort();
+        assert_eq!(nonces, vec![0, 1, 2]);
+        assert_eq!(mock.call_count("eth_getTransactionCount"), 1);
+        // Synced against the pool, not just executed bundles
+        assert_eq!(mock.calls()[0].1[1], "pending");
+        assert_eq!(client.peek_nonce(&sender), Some(3));
+
+        // Other errors keep the tracked nonce
+        assert!(client.transfer(&wallet, &to, "VXS", 4).await.is_err());
+        assert_eq!(client.peek_nonce(&sender), Some(4));
+
+        // A nonce rejection re-syncs from the node
+        assert!(client.transfer(&wallet, &to, "VXS", 4).await.is_err());
+        assert_eq!(client.peek_nonce(&sender), None);
+        client.transfer(&wallet, &to, "VXS", 5).await.unwrap();
+        assert_eq!(submitted_nonces().last(), Some(&7));
+        client.reset_nonce(&sender);
+        assert_eq!(client.peek_nonce(&sender), None);
+    }
+
+    #[tokio::test]
+    async fn test_connect_error_releases_nonce() {
+        let mock = MockTransport::new()
+            .with_result("eth_getTransactionCount", json!("0x5"))
+            .with_connect_error("vex_submitBundle")
+            .with_result("vex_submitBundle", json!("0x1"));
+        let client = WalletClient::with_mock(mock.clone());
+        let wallet = WalletKeypair::generate();
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+
+        let err = client.transfer(&wallet, &to, "VXS", 1).await.unwrap_err();
+        assert!(matches!(err, WalletClientError::Connect(_)));
+        assert!(err.is_transient());
+        assert_eq!(client.peek_nonce(&wallet.hex_address()), None);
+
+        // The retry re-syncs and reuses the nonce the failed bundle never spent
+        client.transfer(&wallet, &to, "VXS", 1).await.unwrap();
+        assert_eq!(mock.call_count("eth_getTransactionCount"), 2);
+        let nonces: Vec<u64> = mock
+            .calls()
+            .into_iter()
+            .filter(|(m, _)| m == "vex_submitBundle")
+            .map(|(_, params)| {
+                let bytes = hex::decode(params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+                borsh::from_slice::<TransactionBundle>(&bytes).unwrap().nonce.0
+            })
+            .collect();
+        assert_eq!(nonces, vec![5, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_unstake_and_schedule_claim() {
+        let mock = MockTransport::new()
+            .with_result("eth_getTransactionCount", json!("0x2"))
+            .with_result("vex_submitBundle", json!("0xabc"));
+        let client = WalletClient::with_mock(mock.clone());
+        let wallet = WalletKeypair::generate();
+
+        let before = Timestamp::now().0;
+        let claim = client.unstake_and_schedule_claim(&wallet, 1_000).await.unwrap();
+        let after = Timestamp::now().0;
+
+        assert_eq!(claim.unstake_tx, "0xabc");
+        // Not before the Unstake's block can have started unbonding
+        assert!(claim.completes_at.0 >= before + UNBONDING_PERIOD_SECS + CLAIM_MARGIN_SECS);
+        assert!(claim.completes_at.0 <= after + UNBONDING_PERIOD_SECS + CLAIM_MARGIN_SECS);
+        assert_eq!(mock.call_count("vex_submitBundle"), 1);
+
+        // Already matured: claims immediately
+        let matured = ScheduledClaim::new("0xabc".into(), Timestamp(1));
+        assert_eq!(matured.remaining_secs(), 0);
+        assert_eq!(matured.wait_and_claim(&client, &wallet).await.unwrap(), "0xabc");
+        assert_eq!(mock.call_count("vex_submitBundle"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_decodes_swap() {
+        let pool = format!("0x{}", hex::encode([0xab; 32]));
+        let mock = MockTransport::new().with_result(
+            "vex_getTransactionReceipt",
+            json!({ "logs": [
+                { "event": "SwapExecuted", "data": {
+                    "pool": format!("0x{}", hex::encode_upper([0xab; 32])),
+                    "amount_in": 1_000_000,
+                    "amount_out": "990000",
+                    "fee": "3000",
+                } },
+                { "event": "PoolRebalanced", "data": "0xdeadbeef" },
+            ] }),
+        );
+        let client = WalletClient::with_mock(mock);
+
+        let events = client.get_logs("0xabc").await.unwrap();
+        assert_eq!(events.len(), 2);
+        let swap = &events[0];
+        assert_eq!(swap.kind, "SwapExecuted");
+        assert_eq!(swap.fields["pool"], json!(pool));
+        assert_eq!(swap.fields["amount_in"], json!("1000000"));
+        assert_eq!(swap.amount("amount_out"), Some(990_000));
+        assert_eq!(swap.amount("fee"), Some(3_000));
+
+        assert_eq!(events[1].kind, "PoolRebalanced");
+        assert_eq!(events[1].fields["raw"], json!("0xdeadbeef"));
+    }
+
+    #[tokio::test]
+    async fn test_rpc_error_is_typed() {
+        let mock = MockTransport::new().with_error("vex_getBalance", -32602, "invalid address");
+        let client = WalletClient::with_mock(mock);
+
+        let err = client.get_balance("0x00", "VXS").await.unwrap_err();
+        assert!(matches!(&err, WalletClientError::Rpc { code: -32602, message } if message == "invalid address"));
+        assert!(!err.is_transient());
+
+        let err = client.get_logs("0xabc").await.unwrap_err();
+        assert!(matches!(err, WalletClientError::Rpc { code: -32601, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_congestion_high_scales_priority_fee() {
+        let mock = MockTransport::new()
+            .with_result(
+                "eth_feeHistory",
+                json!({ "oldestBlock": "0x10", "gasUsedRatio": [0.92, 0.97, 0.88, 0.99] }),
+            )
+            .with_result("eth_maxPriorityFeePerGas", json!("0x2"));
+        let client = WalletClient::with_mock(mock);
+
+        let congestion = client.congestion_level().await.unwrap();
+        assert_eq!(congestion.level, CongestionLevel::High);
+        assert!(congestion.suggested_fee_multiplier > 1.0);
+        assert_eq!(client.suggest_priority_fee().await.unwrap(), 6);
+
+        // Without fee history the base fee is returned unscaled
+        let client = WalletClient::with_mock(MockTransport::new());
+        assert_eq!(client.suggest_priority_fee().await.unwrap(), DEFAULT_PRIORITY_FEE);
+    }
+
+    #[tokio::test]
+    async fn test_get_balances_partial_failure() {
+        let mock = MockTransport::new()
+            .with_result("vex_getBalance", json!("100"))
+            .with_error("vex_getBalance", -32602, "invalid address")
+            .with_result("vex_getBalance", json!("300"));
+        let client = WalletClient::with_mock(mock.clone());
+
+        let balances = client.get_balances(&["0xaa", "0xbad", "0xcc"], "VXS").await.unwrap();
+        assert_eq!(balances[0].as_deref().unwrap(), "100");
+        assert!(matches!(balances[1], Err(WalletClientError::Rpc { code: -32602, .. })));
+        assert_eq!(balances[2].as_deref().unwrap(), "300");
+        assert_eq!(mock.batch_count(), 1);
+        assert_eq!(mock.calls()[1], ("vex_getBalance".to_string(), json!(["0xbad", "VXS"])));
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_nonce_gap() {
+        // Confirmed nonce 5, pending 7 and 8: nonces 5 and 6 never arrived
+        let mock = MockTransport::new()
+            .with_result("eth_getTransactionCount", json!("0x5"))
+            .with_result(
+                "vex_getPendingTransactions",
+                json!([
+                    { "hash": "0xeight", "nonce": 8, "max_priority_fee": 1 },
+                    { "hash": "0xseven", "nonce": "0x7", "max_priority_fee": 20 }
+                ]),
+            )
+            .with_result("eth_feeHistory", json!({ "gasUsedRatio": [0.6, 0.6] }))
+            .with_result("eth_maxPriorityFeePerGas", json!("0x4"));
+        let client = WalletClient::with_mock(mock);
+        let address = format!("0x{}", hex::encode([1u8; 32]));
+
+        let diagnosis = client.diagnose_nonce(&address).await.unwrap();
+        assert_eq!(diagnosis.chain_nonce, 5);
+        assert_eq!(diagnosis.pending_count, 2);
+        assert!(diagnosis.gap);
+        assert_eq!(diagnosis.missing_nonces, vec![5, 6]);
+        assert_eq!(diagnosis.stuck_tx.as_deref(), Some("0xseven"));
+        // 10% over the stuck fee beats the congestion suggestion (4 * 1.5)
+        assert_eq!(diagnosis.replacement_priority_fee, Some(22));
+
+        // Nothing pending: healthy
+        let mock = MockTransport::new()
+            .with_result("eth_getTransactionCount", json!("0x5"))
+            .with_result("vex_getPendingTransactions", json!([]));
+        let diagnosis = WalletClient::with_mock(mock).diagnose_nonce(&address).await.unwrap();
+        assert!(!diagnosis.gap);
+        assert!(diagnosis.stuck_tx.is_none() && diagnosis.replacement_priority_fee.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pending_and_transaction_status() {
+        let me = format!("0x{}", hex::encode([1u8; 32]));
+        let mock = MockTransport::new()
+            .with_result(
+                "vex_getPendingTransactions",
+                json!([
+                    { "hash": "0xb", "nonce": "0x4", "submittedAt": 1_700_000_050 },
+                    { "hash": "0xa", "nonce": 3, "sender": "0xother", "submitted_at": "0x6553f100", "max_priority_fee": 2 },
+                    { "nonce": 5 }
+                ]),
+            )
+            .with_result("vex_getTransactionReceipt", Value::Null)
+            .with_result("vex_getTransactionByHash", json!({ "hash": "0xa", "status": "pending" }))
+            .with_result("vex_getTransactionReceipt", json!({ "blockNumber": "0x2a", "status": true }))
+            .with_result("vex_getTransactionReceipt", Value::Null)
+            .with_result("vex_getTransactionByHash", json!({ "hash": "0xc", "status": "Evicted" }))
+            .with_result("vex_getTransactionReceipt", Value::Null)
+            .with_result("vex_getTransactionByHash", Value::Null);
+        let client = WalletClient::with_mock(mock);
+
+        let pending = client.get_pending(&me).await.unwrap();
+        assert_eq!(
+            pending,
+            vec![
+                PendingTx {
+                    hash: "0xa".into(),
+                    sender: "0xother".into(),
+                    nonce: 3,
+                    submitted_at: Some(1_700_000_000),
+                    max_priority_fee: Some(2),
+                },
+                PendingTx {
+                    hash: "0xb".into(),
+                    sender: me.clone(),
+                    nonce: 4,
+                    submitted_at: Some(1_700_000_050),
+                    max_priority_fee: None,
+                },
+            ]
+        );
+
+        assert_eq!(client.get_transaction_status("0xa").await.unwrap(), TxStatus::Pending);
+        assert_eq!(client.get_transaction_status("0xb").await.unwrap(), TxStatus::Included { block: 42 });
+        assert_eq!(client.get_transaction_status("0xc").await.unwrap(), TxStatus::Dropped);
+        assert_eq!(client.get_transaction_status("0xd").await.unwrap(), TxStatus::Unknown);
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_and_block() {
+        let wallet = WalletKeypair::generate();
+        let to = format!("0x{}", hex::encode([2u8; 32]));
+        let bundle = BundleBuilder::new(&wallet.hex_address())
+            .unwrap()
+            .transfer(&to, "VXS", 750)
+            .unwrap()
+            .nonce(12)
+            .sign(&wallet)
+            .unwrap();
+        let transfer_json = serde_json::to_value(&bundle.operations).unwrap();
+
+        // Hand-written responses covering the shapes the parsers accept, not
+        // output captured from a node
+        let mock = MockTransport::new()
+            .with_result(
+                "vex_getTransactionByHash",
+                json!({ "hash": "0xt1", "raw": bundle::encode_bundle(&bundle).unwrap(), "blockNumber": "0x10" }),
+            )
+            .with_result("vex_getTransactionByHash", Value::Null)
+            .with_result(
+                "vex_getBlockByNumber",
+                json!({
+                    "number": "0x10",
+                    "hash": "0xblock16",
+                    "parentHash": "0xblock15",
+                    "timestamp": 1_700_000_000,
+                    "transactions": [
+                        { "hash": "0xt1", "from": "0xsender", "nonce": "0xc", "operations": transfer_json },
+                        { "hash": "0xt2", "from": "0xsender", "nonce": 13, "operations": [{ "FutureOp": {} }] }
+                    ]
+                }),
+            )
+            .with_result("vex_getBlockByNumber", json!({ "number": 17, "transactions": ["0xt3"] }))
+            .with_result("vex_getBlockByNumber", Value::Null);
+        let client = WalletClient::with_mock(mock.clone());
+
+        let tx = client.get_transaction("0xt1").await.unwrap().unwrap();
+        assert_eq!(tx.sender, wallet.hex_address());
+        assert_eq!(tx.nonce, 12);
+        assert_eq!(tx.block_height, Some(16));
+        match tx.operations.as_deref() {
+            Some([Operation::Transfer { amount, .. }]) => assert_eq!(amount.0, 750),
+            other => panic!("Expected one Transfer, got {:?}", other),
+        }
+        assert!(client.get_transaction("0xmissing").await.unwrap().is_none());
+
+        let block = client.get_block(16).await.unwrap().unwrap();
+        assert_eq!((block.number, block.parent_hash.as_str(), block.timestamp), (16, "0xblock15", 1_700_000_000));
+        assert_eq!(block.transaction_hashes, vec!["0xt1", "0xt2"]);
+        assert_eq!(block.transactions[0].nonce, 12);
+        assert!(matches!(block.transactions[0].operations.as_deref(), Some([Operation::Transfer { .. }])));
+        assert!(block.transactions[1].operations.is_none());
+        let (_, params) = mock.calls().into_iter().find(|(m, _)| m == "vex_getBlockByNumber").unwrap();
+        assert_eq!(params, json!(["0x10", true]));
+
+        let block = client.get_block(17).await.unwrap().unwrap();
+        assert_eq!(block.transaction_hashes, vec!["0xt3"]);
+        assert!(block.transactions.is_empty());
+        assert!(client.get_block(18).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_timeout_and_retry_policy() {
+        let client = WalletClient::new("http://localhost:9933");
+        assert_eq!(client.timeout(), Duration::from_secs(30));
+        let client = client.with_timeout(Duration::from_secs(5));
+        assert_eq!(client.timeout(), Duration::from_secs(5));
+
+        let mock = MockTransport::new()
+            .with_timeout_error("eth_blockNumber")
+            .with_result("eth_blockNumber", json!("0x10"))
+            .with_timeout_error("vex_submitBundle")
+            .with_result("vex_submitBundle", json!("0xabc"));
+        let client = WalletClient::with_mock(mock.clone()).with_retries(2, Duration::from_millis(1));
+
+        assert_eq!(client.block_number().await.unwrap(), 16);
+        assert_eq!(mock.call_count("eth_blockNumber"), 2);
+
+        // Submission is never retried, even on a transient error
+        let err = client.submit_bundle(&transfer_bundle()).await.unwrap_err();
+        assert!(err.is_transient());
+        assert_eq!(mock.call_count("vex_submitBundle"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_confirmation() {
+        let mock = MockTransport::new()
+            .with_result("vex_getTransactionReceipt", Value::Null)
+            .with_result("vex_getTransactionReceipt", Value::Null)
+            .with_result(
+                "vex_getTransactionReceipt",
+                json!({ "blockNumber": "0x2a", "gasUsed": 21_000, "status": "0x1", "logs": [] }),
+            );
+        let client = WalletClient::with_mock(mock.clone());
+        let receipt = client
+            .wait_for_confirmation("0xabc", Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(receipt.block_height, 42);
+        assert_eq!(receipt.gas_used, 21_000);
+        assert!(receipt.success);
+        assert_eq!(mock.call_count("vex_getTransactionReceipt"), 3);
+
+        let mock = MockTransport::new().with_result(
+            "vex_getTransactionReceipt",
+            json!({ "block_number": 7, "gas_used": 30_000, "status": false, "error": "insufficient balance" }),
+        );
+        let client = WalletClient::with_mock(mock);
+        let err = client
+            .wait_for_confirmation("0xdef", Duration::from_secs(10))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, WalletClientError::Reverted { gas_used: 30_000, ref reason, .. } if reason == "insufficient balance"));
+
+        let mock = MockTransport::new().with_result("vex_getTransactionReceipt", Value::Null);
+        let client = WalletClient::with_mock(mock);
+        let err = client
+            .wait_for_confirmation("0x123", Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, WalletClientError::ConfirmationTimeout(_)));
+    }
+
+    #[tokio::test]
+    async fn test_raw_call() {
+        let mock = MockTransport::new()
+            .with_result("vex_someFutureMethod", json!({ "answer": 42 }));
+        let client = WalletClient::with_mock(mock.clone());
+
+        let result = client
+            .raw_call("vex_someFutureMethod", json!(["arg"]))
+            .await
+            .unwrap();
+        assert_eq!(result["answer"], 42);
+        assert_eq!(mock.calls(), vec![("vex_someFutureMethod".to_string(), json!(["arg"]))]);
+
+        assert!(client.raw_call("vex_notThere", json!([])).await.is_err());
+    }
 }
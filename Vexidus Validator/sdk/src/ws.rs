@@ -0,0 +1,443 @@
+//! WebSocket subscriptions for new blocks and balance changes (`ws` feature).
+//!
+//! Subscriptions use `eth_subscribe`-style RPC: one `eth_subscribe` request
+//! per connection, then `eth_subscription` notifications. Each stream runs on
+//! a background task that reconnects with exponential backoff (0.5s doubling
+//! to 30s) and resubscribes after a dropped connection; notifications sent
+//! while disconnected are missed. The task ends when the stream is dropped.
+//!
+//! A connection that goes quiet is pinged every 20s and treated as dropped
+//! after 60s without any frame from the node, so half-open connections (a
+//! NAT or load balancer silently forgetting the socket) also reconnect.
+//! `wss://` URLs use the platform TLS stack, the same as the HTTP client.
+//!
+//! Only `newHeads` is subscribed to. Balance pushes would need a node
+//! subscription for them, which the SDK doesn't assume, so
+//! [`WalletClient::subscribe_balance`] reads [`WalletClient::get_balance`] on
+//! each new block and yields only the changes.
+//!
+//! The streams must be created inside a Tokio runtime, since the background
+//! task is started with `tokio::spawn`:
+//!
+//! ```ignore
+//! use futures_util::StreamExt;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = WalletClient::new("http://localhost:9933");
+//!     let mut blocks = Box::pin(client.subscribe_blocks());
+//!     while let Some(header) = blocks.next().await {
+//!         println!("block {} {}", header.number, header.hash);
+//!     }
+//! }
+//! ```
+//!
+//! [`WalletClient`] subscribes on its RPC URL with the scheme switched to
+//! `ws`/`wss`; use [`subscribe_blocks_at`] for a node that serves WebSockets
+//! elsewhere.
+
+use futures_util::{SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::wallet_client::{quantity, WalletClient};
+
+/// First reconnect delay; doubles per failed attempt.
+const RECONNECT_BACKOFF: Duration = Duration::from_millis(500);
+/// Reconnect delay cap.
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+/// Items buffered per stream before the background task waits for the reader.
+const STREAM_BUFFER: usize = 64;
+/// Ping the node after 20s of quiet; give up on the connection after 60s.
+const KEEPALIVE: Keepalive = Keepalive {
+    ping_interval: Duration::from_secs(20),
+    idle_timeout: Duration::from_secs(60),
+};
+
+/// How a connection detects that the node has gone away.
+#[derive(Debug, Clone, Copy)]
+struct Keepalive {
+    /// Quiet time after which the node is pinged.
+    ping_interval: Duration,
+    /// Quiet time after which the connection is considered dead.
+    idle_timeout: Duration,
+}
+
+/// A new block, from a `newHeads` subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockHeader {
+    pub number: u64,
+    pub hash: String,
+    pub parent_hash: String,
+    pub timestamp: u64,
+}
+
+impl BlockHeader {
+    fn from_value(value: &Value) -> Option<Self> {
+        Some(Self {
+            number: quantity(&value["number"])?,
+            hash: value["hash"].as_str()?.to_string(),
+            parent_hash: value["parentHash"].as_str().unwrap_or_default().to_string(),
+            timestamp: quantity(&value["timestamp"]).unwrap_or(0),
+        })
+    }
+}
+
+/// A change in an account's VXS balance, from
+/// [`subscribe_balance`](WalletClient::subscribe_balance).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceUpdate {
+    pub address: String,
+    /// The block whose header prompted the read.
+    pub block: u64,
+    pub previous: String,
+    pub balance: String,
+}
+
+impl WalletClient {
+    /// Stream of new block headers. See the [module docs](crate::ws).
+    pub fn subscribe_blocks(&self) -> impl Stream<Item = BlockHeader> {
+        subscribe_blocks_at(&ws_url(self.rpc_url()))
+    }
+
+    /// Stream of changes to `address`'s VXS balance.
+    ///
+    /// The balance is read over HTTP on every new block header; the first
+    /// read is the baseline, and an update is yielded only when a later read
+    /// differs from the one before. Failed reads are logged and skipped, and
+    /// changes made and undone between two blocks' reads aren't seen. The
+    /// background task ends at the first block after the stream is dropped.
+    pub fn subscribe_balance(&self, address: &str) -> impl Stream<Item = BalanceUpdate> {
+        watch_balance(self.detached(), self.subscribe_blocks(), address)
+    }
+}
+
+fn watch_balance(
+    client: WalletClient,
+    blocks: impl Stream<Item = BlockHeader> + Send + 'static,
+    address: &str,
+) -> impl Stream<Item = BalanceUpdate> {
+    let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+    let address = address.to_string();
+    tokio::spawn(async move {
+        let mut blocks = Box::pin(blocks);
+        let mut last: Option<String> = None;
+        while let Some(header) = blocks.next().await {
+            if tx.is_closed() {
+                break;
+            }
+            let balance = match client.get_balance(&address, "VXS").await {
+                Ok(balance) => balance,
+                Err(e) => {
+                    log::warn!("Balance read for {} at block {} failed: {}", address, header.number, e);
+                    continue;
+                }
+            };
+            match last.replace(balance.clone()) {
+                Some(previous) if previous != balance => {
+                    let update = BalanceUpdate { address: address.clone(), block: header.number, previous, balance };
+                    if tx.send(update).await.is_err() {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
+    receiver_stream(rx)
+}
+
+/// Stream of new block headers from the WebSocket endpoint `url`.
+pub fn subscribe_blocks_at(url: &str) -> impl Stream<Item = BlockHeader> {
+    subscribe(url, json!(["newHeads"]), BlockHeader::from_value, KEEPALIVE)
+}
+
+/// `http(s)://` → `ws(s)://`; other URLs are used as-is.
+fn ws_url(rpc_url: &str) -> String {
+    if let Some(rest) = rpc_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        rpc_url.to_string()
+    }
+}
+
+fn subscribe<T: Send + 'static>(
+    url: &str,
+    params: Value,
+    parse: fn(&Value) -> Option<T>,
+    keepalive: Keepalive,
+) -> impl Stream<Item = T> {
+    let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+    tokio::spawn(run_subscription(url.to_string(), params, parse, keepalive, tx));
+    receiver_stream(rx)
+}
+
+fn receiver_stream<T>(rx: mpsc::Receiver<T>) -> impl Stream<Item = T> {
+    futures_util::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|item| (item, rx)) })
+}
+
+/// Keep a subscription alive until the receiving stream is dropped.
+async fn run_subscription<T>(
+    url: String,
+    params: Value,
+    parse: fn(&Value) -> Option<T>,
+    keepalive: Keepalive,
+    tx: mpsc::Sender<T>,
+) {
+    let mut attempt = 0u32;
+    while !tx.is_closed() {
+        match subscribe_once(&url, &params, parse, keepalive, &tx).await {
+            // Got as far as a live subscription: start the backoff over
+            Ok(true) => attempt = 0,
+            Ok(false) => {}
+            Err(e) => log::warn!("WebSocket subscription to {} failed: {}", url, e),
+        }
+        if tx.is_closed() {
+            break;
+        }
+        let delay = reconnect_delay(attempt);
+        log::debug!("Resubscribing to {} in {:?}", url, delay);
+        tokio::time::sleep(delay).await;
+        attempt = attempt.saturating_add(1);
+    }
+}
+
+fn reconnect_delay(attempt: u32) -> Duration {
+    RECONNECT_BACKOFF
+        .saturating_mul(1u32 << attempt.min(16))
+        .min(MAX_RECONNECT_BACKOFF)
+}
+
+/// One connection's worth of subscription. Returns whether the subscription
+/// was confirmed before the connection ended or went idle.
+async fn subscribe_once<T>(
+    url: &str,
+    params: &Value,
+    parse: fn(&Value) -> Option<T>,
+    keepalive: Keepalive,
+    tx: &mpsc::Sender<T>,
+) -> Result<bool, String> {
+    let (mut socket, _) = tokio_tungstenite::connect_async(url).await.map_err(|e| e.to_string())?;
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "eth_subscribe", "params": params });
+    socket
+        .send(Message::Text(request.to_string()))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut subscription: Option<Value> = None;
+    let mut last_seen = Instant::now();
+    loop {
+        let message = match tokio::time::timeout(keepalive.ping_interval, socket.next()).await {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(_) if last_seen.elapsed() >= keepalive.idle_timeout => {
+                log::warn!("No traffic from {} for {:?}, reconnecting", url, keepalive.idle_timeout);
+                break;
+            }
+            Err(_) => {
+                socket.send(Message::Ping(Vec::new())).await.map_err(|e| e.to_string())?;
+                continue;
+            }
+        };
+        last_seen = Instant::now();
+        let text = match message.map_err(|e| e.to_string())? {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let Ok(msg) = serde_json::from_str::<Value>(&text) else {
+            log::debug!("Ignoring non-JSON WebSocket message: {}", text);
+            continue;
+        };
+
+        if subscription.is_none() && msg["id"] == json!(1) {
+            if let Some(error) = msg.get("error") {
+                return Err(format!("eth_subscribe rejected: {}", error));
+            }
+            subscription = Some(msg["result"].clone());
+            continue;
+        }
+        let params = &msg["params"];
+        if msg["method"] != "eth_subscription" || subscription.as_ref() != Some(&params["subscription"]) {
+            continue;
+        }
+        match parse(&params["result"]) {
+            Some(item) => {
+                if tx.send(item).await.is_err() {
+                    // Stream dropped
+                    return Ok(true);
+                }
+            }
+            None => log::debug!("Ignoring malformed notification: {}", params["result"]),
+        }
+    }
+    Ok(subscription.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockTransport;
+    use tokio::net::TcpListener;
+
+    /// Serve two connections, each acking the subscription and sending one
+    /// header before dropping, so the second header only arrives if the
+    /// client reconnected and resubscribed.
+    async fn flaky_node() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for (i, number) in [(0u64, "0x10"), (1, "0x11")] {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+                let Some(Ok(Message::Text(req))) = socket.next().await else { return };
+                let req: Value = serde_json::from_str(&req).unwrap();
+                assert_eq!(req["method"], "eth_subscribe");
+                assert_eq!(req["params"], json!(["newHeads"]));
+                let sub = format!("0xsub{}", i);
+                let ack = json!({ "jsonrpc": "2.0", "id": 1, "result": sub });
+                socket.send(Message::Text(ack.to_string())).await.unwrap();
+                let note = json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_subscription",
+                    "params": {
+                        "subscription": sub,
+                        "result": { "number": number, "hash": format!("0xh{}", i), "parentHash": "0x0", "timestamp": "0x64" }
+                    }
+                });
+                socket.send(Message::Text(note.to_string())).await.unwrap();
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_blocks_resubscribes_after_drop() {
+        let client = WalletClient::new(&flaky_node().await);
+        let mut blocks = Box::pin(client.subscribe_blocks());
+
+        let first = blocks.next().await.unwrap();
+        assert_eq!(first, BlockHeader { number: 16, hash: "0xh0".into(), parent_hash: "0x0".into(), timestamp: 100 });
+        let second = tokio::time::timeout(Duration::from_secs(5), blocks.next()).await.unwrap().unwrap();
+        assert_eq!(second.number, 17);
+    }
+
+    /// Ack the subscription and then go silent without reading (so pings
+    /// go unanswered) until the client gives up and reconnects; the second
+    /// connection delivers a header.
+    async fn silent_node() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut silent = Vec::new();
+            for i in 0..2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+                let Some(Ok(Message::Text(_))) = socket.next().await else { return };
+                let sub = format!("0xsub{}", i);
+                let ack = json!({ "jsonrpc": "2.0", "id": 1, "result": sub });
+                socket.send(Message::Text(ack.to_string())).await.unwrap();
+                if i == 0 {
+                    silent.push(socket);
+                    continue;
+                }
+                let note = json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_subscription",
+                    "params": { "subscription": sub, "result": { "number": "0x20", "hash": "0xh" } }
+                });
+                socket.send(Message::Text(note.to_string())).await.unwrap();
+                std::future::pending::<()>().await;
+            }
+        });
+        format!("ws://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_idle_connection_reconnects() {
+        let keepalive = Keepalive {
+            ping_interval: Duration::from_millis(50),
+            idle_timeout: Duration::from_millis(150),
+        };
+        let url = silent_node().await;
+        let mut blocks = Box::pin(subscribe(&url, json!(["newHeads"]), BlockHeader::from_value, keepalive));
+        let header = tokio::time::timeout(Duration::from_secs(5), blocks.next()).await.unwrap().unwrap();
+        assert_eq!(header.number, 32);
+    }
+
+    /// Ack the subscription, send a header for each of `numbers`, then stay
+    /// connected.
+    async fn steady_node(numbers: &'static [u64]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let Some(Ok(Message::Text(_))) = socket.next().await else { return };
+            let ack = json!({ "jsonrpc": "2.0", "id": 1, "result": "0xsub" });
+            socket.send(Message::Text(ack.to_string())).await.unwrap();
+            for number in numbers {
+                let note = json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_subscription",
+                    "params": { "subscription": "0xsub", "result": { "number": format!("{:#x}", number), "hash": "0xh" } }
+                });
+                socket.send(Message::Text(note.to_string())).await.unwrap();
+            }
+            std::future::pending::<()>().await;
+        });
+        format!("ws://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_balance_yields_changes() {
+        let mock = MockTransport::new()
+            .with_result("vex_getBalance", json!("100"))
+            .with_result("vex_getBalance", json!("100"))
+            .with_error("vex_getBalance", -32000, "state unavailable")
+            .with_result("vex_getBalance", json!("150"))
+            .with_result("vex_getBalance", json!("150"))
+            .with_result("vex_getBalance", json!("120"));
+        let client = WalletClient::with_mock(mock.clone());
+        let blocks = subscribe_blocks_at(&steady_node(&[1, 2, 3, 4, 5, 6]).await);
+        let updates = watch_balance(client, blocks, "0xaa").take(2).collect::<Vec<_>>();
+        let updates = tokio::time::timeout(Duration::from_secs(5), updates).await.unwrap();
+
+        let update = |block, previous: &str, balance: &str| BalanceUpdate {
+            address: "0xaa".into(),
+            block,
+            previous: previous.into(),
+            balance: balance.into(),
+        };
+        assert_eq!(updates, vec![update(4, "100", "150"), update(6, "150", "120")]);
+        assert_eq!(mock.calls()[0].1, json!(["0xaa", "VXS"]));
+    }
+
+    #[tokio::test]
+    async fn test_wss_urls_are_supported() {
+        use tokio_tungstenite::tungstenite::error::{Error, UrlError};
+
+        // Not a TLS server, so the handshake fails, but not for lack of TLS support
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("wss://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (_stream, _) = listener.accept().await.unwrap();
+        });
+        let err = tokio_tungstenite::connect_async(url).await.unwrap_err();
+        assert!(!matches!(err, Error::Url(UrlError::TlsFeatureNotEnabled)), "{}", err);
+    }
+
+    #[test]
+    fn test_ws_url_and_backoff() {
+        assert_eq!(ws_url("https://rpc.vexidus.io"), "wss://rpc.vexidus.io");
+        assert_eq!(reconnect_delay(0), RECONNECT_BACKOFF);
+        assert_eq!(reconnect_delay(1), RECONNECT_BACKOFF * 2);
+        assert_eq!(reconnect_delay(30), MAX_RECONNECT_BACKOFF);
+    }
+}